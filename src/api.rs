@@ -0,0 +1,138 @@
+//! Embedding API
+//!
+//! [`MiniBeads`] is a thin facade over [`Storage`] for Rust tools that want
+//! to read and file issues without going through the CLI: one method per
+//! common query, typed results, and [`IssueSpec`] builders for new issues.
+//! Everything it does goes through `Storage`, so locking and on-disk format
+//! are the same as for `mb`; [`MiniBeads::storage`] gives access to the rest.
+//!
+//! ```no_run
+//! use minibeads::{IssueSpec, MiniBeads};
+//! use minibeads::types::IssueType;
+//!
+//! let beads = MiniBeads::open(".beads")?;
+//! let bug = beads.create(IssueSpec::new("Crash on empty input").issue_type(IssueType::Bug))?;
+//! for issue in beads.ready()? {
+//!     println!("{} {}", issue.id, issue.title);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::error;
+pub use crate::storage::IssueSpec;
+use crate::storage::{IssueFilter, Storage};
+use crate::types::{BlockedIssue, Issue, Stats};
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// An open minibeads database
+pub struct MiniBeads {
+    storage: Storage,
+}
+
+impl MiniBeads {
+    /// Open an existing database directory (e.g. `.beads`)
+    pub fn open(beads_dir: impl Into<PathBuf>) -> Result<Self> {
+        Ok(Self {
+            storage: Storage::open(beads_dir.into())?,
+        })
+    }
+
+    /// Create a database in `beads_dir`, as `mb init` does. Without a
+    /// `prefix` it is inferred from the surrounding directory.
+    pub fn init(beads_dir: impl Into<PathBuf>, prefix: Option<&str>) -> Result<Self> {
+        Ok(Self {
+            storage: Storage::init(beads_dir.into(), prefix.map(str::to_string), false)?,
+        })
+    }
+
+    /// The underlying storage, for operations the facade does not cover
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    /// Whether creating an issue warns on stderr when hash-ID collisions
+    /// force a longer ID (on by default; `mb --mb-validation silent` turns
+    /// it off)
+    pub fn set_hash_id_warnings(&mut self, warn: bool) {
+        self.storage.set_hash_id_warnings(warn);
+    }
+
+    /// One issue by ID, with its `dependents` filled in
+    pub fn show(&self, id: &str) -> Result<Issue> {
+        self.storage
+            .get_issue(id)?
+            .ok_or_else(|| error::not_found(format!("Issue not found: {}", id)))
+    }
+
+    /// Issues matching `filter`, in `mb list` order
+    pub fn list(&self, filter: &IssueFilter) -> Result<Vec<Issue>> {
+        self.storage.list_issues(filter, None)
+    }
+
+    /// Open issues with no unresolved blockers, in `mb ready`'s default
+    /// (hybrid priority and age) order
+    pub fn ready(&self) -> Result<Vec<Issue>> {
+        self.storage.get_ready(&IssueFilter::new(), "hybrid")
+    }
+
+    /// Issues held up by unresolved blockers, with what blocks them
+    pub fn blocked(&self) -> Result<Vec<BlockedIssue>> {
+        self.storage.get_blocked()
+    }
+
+    /// The counts `mb stats` reports
+    pub fn stats(&self) -> Result<Stats> {
+        self.storage.get_stats()
+    }
+
+    /// Create an issue. Priority and type fall back to the database's
+    /// `mb-default-priority` / `mb-default-type`, then 2 and `task`, as for
+    /// `mb create`.
+    pub fn create(&self, spec: IssueSpec) -> Result<Issue> {
+        self.storage.create_issue(spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DependencyType, IssueType, Status};
+
+    #[test]
+    fn facade_creates_and_queries_issues() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads = MiniBeads::init(tmp.path().join(".beads"), Some("demo")).unwrap();
+
+        let blocker = beads
+            .create(IssueSpec::new("Blocker").priority(1).label("infra"))
+            .unwrap();
+        let blocked = beads
+            .create(
+                IssueSpec::new("Blocked")
+                    .issue_type(IssueType::Bug)
+                    .depends_on(&blocker.id, DependencyType::Blocks),
+            )
+            .unwrap();
+        assert_eq!(blocked.priority, 2);
+        assert_eq!(blocked.issue_type, IssueType::Bug);
+
+        let ready: Vec<_> = beads.ready().unwrap().into_iter().map(|i| i.id).collect();
+        assert_eq!(ready, [blocker.id.as_str()]);
+        let stuck = beads.blocked().unwrap();
+        assert_eq!(stuck.len(), 1);
+        assert_eq!(stuck[0].issue.id, blocked.id);
+
+        let shown = beads.show(&blocker.id).unwrap();
+        assert_eq!(shown.labels, ["infra"]);
+        assert_eq!(shown.dependents.len(), 1);
+        assert!(beads.show("demo-99").is_err());
+
+        let reopened = MiniBeads::open(tmp.path().join(".beads")).unwrap();
+        let open = reopened
+            .list(&IssueFilter::new().status(Status::Open))
+            .unwrap();
+        assert_eq!(open.len(), 2);
+        assert_eq!(reopened.stats().unwrap().total_issues, 2);
+    }
+}
@@ -45,7 +45,7 @@ use std::collections::HashSet;
 use std::process::Command;
 
 // Re-export production types instead of defining duplicates
-pub use crate::types::{DependencyType, IssueType, Status};
+pub use crate::types::{DependencyType, IssueType, Readiness, Status};
 
 /// Represents a beads command/action
 #[derive(Debug, Clone, PartialEq)]
@@ -79,6 +79,8 @@ pub enum BeadsAction {
         issue_id: String,
         status: Option<Status>,
         priority: Option<i32>,
+        title: Option<String>,
+        description: Option<String>,
     },
 
     /// Close an issue
@@ -87,6 +89,12 @@ pub enum BeadsAction {
     /// Reopen an issue
     Reopen { issue_id: String },
 
+    /// Set an issue's assignee (`update --assignee`)
+    SetAssignee { issue_id: String, assignee: String },
+
+    /// Add a label to an issue (`label add`)
+    AddLabel { issue_id: String, label: String },
+
     /// Add a dependency
     AddDependency {
         issue_id: String,
@@ -141,6 +149,8 @@ impl std::fmt::Display for BeadsAction {
                 issue_id,
                 status,
                 priority,
+                title,
+                description,
             } => {
                 let mut parts = vec![format!("update {}", issue_id)];
                 if let Some(s) = status {
@@ -149,10 +159,22 @@ impl std::fmt::Display for BeadsAction {
                 if let Some(p) = priority {
                     parts.push(format!("priority:{}", p));
                 }
+                if let Some(t) = title {
+                    parts.push(format!("title:{:?}", t));
+                }
+                if let Some(d) = description {
+                    parts.push(format!("description:{:?}", d));
+                }
                 write!(f, "{}", parts.join(" "))
             }
             BeadsAction::Close { issue_id, .. } => write!(f, "close {}", issue_id),
             BeadsAction::Reopen { issue_id } => write!(f, "reopen {}", issue_id),
+            BeadsAction::SetAssignee { issue_id, assignee } => {
+                write!(f, "assign {} → {}", issue_id, assignee)
+            }
+            BeadsAction::AddLabel { issue_id, label } => {
+                write!(f, "label add {} {}", issue_id, label)
+            }
             BeadsAction::AddDependency {
                 issue_id,
                 depends_on,
@@ -238,13 +260,17 @@ impl ActionGenerator {
                 1 // List
             } else if rand_val < 55 {
                 2 // Show
-            } else if rand_val < 70 {
+            } else if rand_val < 65 {
                 3 // Update
-            } else if rand_val < 80 {
+            } else if rand_val < 70 {
+                8 // SetAssignee
+            } else if rand_val < 75 {
+                9 // AddLabel
+            } else if rand_val < 83 {
                 4 // Close
-            } else if rand_val < 85 {
+            } else if rand_val < 87 {
                 5 // Reopen
-            } else if rand_val < 95 {
+            } else if rand_val < 96 {
                 6 // AddDependency
             } else {
                 7 // Export
@@ -260,6 +286,8 @@ impl ActionGenerator {
             5 => self.generate_reopen(),
             6 => self.generate_add_dependency(),
             7 => self.generate_export(),
+            8 => self.generate_set_assignee(),
+            9 => self.generate_add_label(),
             _ => unreachable!(),
         }
     }
@@ -352,10 +380,47 @@ impl ActionGenerator {
             None
         };
 
+        let title = if self.rng.gen_bool(0.2) {
+            Some(self.generate_title())
+        } else {
+            None
+        };
+
+        let description = if self.rng.gen_bool(0.2) {
+            Some(self.generate_description())
+        } else {
+            None
+        };
+
         BeadsAction::Update {
             issue_id,
             status,
             priority,
+            title,
+            description,
+        }
+    }
+
+    /// A replacement title, sometimes with characters that need quoting in
+    /// YAML frontmatter
+    fn generate_title(&mut self) -> String {
+        let n = self.rng.gen_range(1000..9999);
+        match self.rng.gen_range(0..3) {
+            0 => format!("Renamed {}", n),
+            1 => format!("Fix: \"quoted\" title {}", n),
+            _ => format!("# Not a heading {}", n),
+        }
+    }
+
+    /// A replacement description, sometimes with lines that look like the
+    /// markdown section headers issue files are split on
+    fn generate_description(&mut self) -> String {
+        let n = self.rng.gen_range(1000..9999);
+        match self.rng.gen_range(0..4) {
+            0 => format!("Updated description {}", n),
+            1 => format!("First line {}\nSecond line", n),
+            2 => format!("Intro {}\n# Design\nNot really the design section", n),
+            _ => format!("Steps {}:\n```\n# a shell comment\n## another\n```", n),
         }
     }
 
@@ -383,6 +448,21 @@ impl ActionGenerator {
         BeadsAction::Reopen { issue_id }
     }
 
+    fn generate_set_assignee(&mut self) -> BeadsAction {
+        let issue_id = self.pick_random_issue();
+        let assignee = ["alice", "bob", "carol"][self.rng.gen_range(0..3)].to_string();
+
+        BeadsAction::SetAssignee { issue_id, assignee }
+    }
+
+    fn generate_add_label(&mut self) -> BeadsAction {
+        let issue_id = self.pick_random_issue();
+        // A small pool, so the same label is sometimes added twice
+        let label = ["backend", "frontend", "urgent", "docs"][self.rng.gen_range(0..4)].to_string();
+
+        BeadsAction::AddLabel { issue_id, label }
+    }
+
     fn generate_add_dependency(&mut self) -> BeadsAction {
         // Need at least 2 issues for a dependency
         if self.existing_issues.len() < 2 {
@@ -521,7 +601,8 @@ impl ActionExecutor {
 
                 // Extract the actual issue ID from output
                 if output.status.success() {
-                    actual_issue_id = extract_issue_id(&String::from_utf8_lossy(&output.stdout));
+                    actual_issue_id = extract_issue_id(&String::from_utf8_lossy(&output.stdout))
+                        .or_else(|| extract_issue_id(&String::from_utf8_lossy(&output.stderr)));
                 }
 
                 // Verify the created issue ID matches our expectation
@@ -599,6 +680,8 @@ impl ActionExecutor {
                 issue_id,
                 status,
                 priority,
+                title,
+                description,
             } => {
                 let mut cmd = self.build_command();
                 cmd.arg("update").arg(issue_id);
@@ -609,6 +692,12 @@ impl ActionExecutor {
                 if let Some(p) = priority {
                     cmd.arg("--priority").arg(p.to_string());
                 }
+                if let Some(t) = title {
+                    cmd.arg("--title").arg(t);
+                }
+                if let Some(d) = description {
+                    cmd.arg("--description").arg(d);
+                }
 
                 cmd.output().context("Failed to execute update command")?
             }
@@ -625,6 +714,22 @@ impl ActionExecutor {
                 cmd.output().context("Failed to execute reopen command")?
             }
 
+            BeadsAction::SetAssignee { issue_id, assignee } => {
+                let mut cmd = self.build_command();
+                cmd.arg("update")
+                    .arg(issue_id)
+                    .arg("--assignee")
+                    .arg(assignee);
+                cmd.output().context("Failed to execute update command")?
+            }
+
+            BeadsAction::AddLabel { issue_id, label } => {
+                let mut cmd = self.build_command();
+                cmd.arg("label").arg("add").arg(issue_id).arg(label);
+                cmd.output()
+                    .context("Failed to execute label add command")?
+            }
+
             BeadsAction::AddDependency {
                 issue_id,
                 depends_on,
@@ -670,51 +775,40 @@ impl ActionExecutor {
 
 /// Extract issue ID from create command output
 ///
-/// Looks for patterns like:
+/// The token after a "Created issue:" or "Created:" label is authoritative,
+/// whatever its form:
 /// - "Created issue: test-1" (sequential)
-/// - "Created: test-1" (sequential)
-/// - "Created issue: test-4f10" (hash)
-/// - "Created: test-4f10" (hash)
+/// - "✓ Created issue: test-a1b2" (hash)
+/// - "Created: test-4f10"
+///
+/// `mb create` guarantees the "Created issue:" line (on stderr with
+/// `--json`); only when no line carries such a label does this fall back to
+/// the first word that looks like `prefix-<number>` or `prefix-<base36 hash>`.
 fn extract_issue_id(output: &str) -> Option<String> {
-    // Try to find issue ID in various formats
-    for line in output.lines() {
-        // Look for "Created issue: <id>"
-        if let Some(pos) = line.find("Created issue:") {
-            let id = line[pos + 14..].trim();
-            if !id.is_empty() {
-                return Some(id.to_string());
-            }
-        }
-
-        // Look for "Created: <id>"
-        if let Some(pos) = line.find("Created:") {
-            let id = line[pos + 8..].trim();
-            if !id.is_empty() {
-                return Some(id.to_string());
-            }
-        }
-
-        // Look for issue ID pattern (prefix-number or prefix-hexhash)
-        let words: Vec<&str> = line.split_whitespace().collect();
-        for word in words {
-            if word.contains('-') {
-                let suffix = word.split('-').next_back().unwrap_or("");
-
-                // Check if it's a sequential number
-                if suffix.parse::<usize>().is_ok() {
-                    return Some(word.to_string());
-                }
-
-                // Check if it's a hex hash (4-8 hex characters)
-                if (4..=8).contains(&suffix.len()) && suffix.chars().all(|c| c.is_ascii_hexdigit())
-                {
-                    return Some(word.to_string());
+    for label in ["Created issue:", "Created:"] {
+        for line in output.lines() {
+            if let Some(pos) = line.find(label) {
+                if let Some(id) = line[pos + label.len()..].split_whitespace().next() {
+                    return Some(id.to_string());
                 }
             }
         }
     }
 
-    None
+    output
+        .split_whitespace()
+        .find(|word| {
+            let Some((prefix, suffix)) = word.rsplit_once('-') else {
+                return false;
+            };
+            !prefix.is_empty()
+                && (suffix.parse::<usize>().is_ok()
+                    || ((3..=8).contains(&suffix.len())
+                        && suffix
+                            .chars()
+                            .all(|c| c.is_ascii_digit() || c.is_ascii_lowercase())))
+        })
+        .map(str::to_string)
 }
 
 /// Result of executing a beads action
@@ -748,6 +842,9 @@ pub struct ReferenceIssue {
     pub status: Status,
     pub priority: i32,
     pub issue_type: IssueType,
+    /// Empty when unassigned
+    pub assignee: String,
+    pub labels: std::collections::BTreeSet<String>,
     pub depends_on: std::collections::HashMap<String, DependencyType>,
 }
 
@@ -816,6 +913,8 @@ impl ReferenceInterpreter {
                     status: Status::Open,
                     priority: *priority,
                     issue_type: *issue_type,
+                    assignee: String::new(),
+                    labels: std::collections::BTreeSet::new(),
                     depends_on: std::collections::HashMap::new(),
                 };
 
@@ -838,14 +937,25 @@ impl ReferenceInterpreter {
                 issue_id,
                 status,
                 priority,
+                title,
+                description,
             } => {
                 if let Some(issue) = self.issues.get_mut(issue_id) {
+                    // The stored status is whatever was set, `blocked` included.
+                    // Whether the issue is actually ready or blocked also depends
+                    // on its dependencies; see `readiness`.
                     if let Some(s) = status {
                         issue.status = *s;
                     }
                     if let Some(p) = priority {
                         issue.priority = *p;
                     }
+                    if let Some(t) = title {
+                        issue.title = t.clone();
+                    }
+                    if let Some(d) = description {
+                        issue.description = d.clone();
+                    }
                 }
                 // Silently ignore updates to non-existent issues (matches bd behavior)
                 Ok(())
@@ -865,6 +975,21 @@ impl ReferenceInterpreter {
                 Ok(())
             }
 
+            BeadsAction::SetAssignee { issue_id, assignee } => {
+                if let Some(issue) = self.issues.get_mut(issue_id) {
+                    issue.assignee = assignee.clone();
+                }
+                Ok(())
+            }
+
+            BeadsAction::AddLabel { issue_id, label } => {
+                // Adding a label the issue already has is a no-op
+                if let Some(issue) = self.issues.get_mut(issue_id) {
+                    issue.labels.insert(label.clone());
+                }
+                Ok(())
+            }
+
             BeadsAction::AddDependency {
                 issue_id,
                 depends_on,
@@ -883,6 +1008,45 @@ impl ReferenceInterpreter {
         }
     }
 
+    /// Whether a `blocks` dependency on `id` is still unresolved, by the same
+    /// rule as storage's `BlockerStatuses`: the target is not closed, or it
+    /// does not exist
+    fn is_blocking(&self, id: &str) -> bool {
+        self.issues
+            .get(id)
+            .is_none_or(|issue| issue.status != Status::Closed)
+    }
+
+    /// Effective readiness of an issue, by the rules of
+    /// `Issue::matches_readiness`: `Ready` if open with no unresolved `blocks`
+    /// dependency, `Blocked` if not closed and has one, `None` otherwise
+    /// (closed, unknown, or in progress / marked blocked with nothing blocking)
+    pub fn readiness(&self, id: &str) -> Option<Readiness> {
+        let issue = self.issues.get(id)?;
+        let blocked = issue.depends_on.iter().any(|(dep_id, dep_type)| {
+            *dep_type == DependencyType::Blocks && self.is_blocking(dep_id)
+        });
+        match issue.status {
+            Status::Closed => None,
+            _ if blocked => Some(Readiness::Blocked),
+            Status::Open => Some(Readiness::Ready),
+            _ => None,
+        }
+    }
+
+    /// Sorted IDs of the issues with the given effective readiness, i.e. what
+    /// `mb ready` / `mb blocked` should list
+    pub fn issues_with_readiness(&self, readiness: Readiness) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .issues
+            .keys()
+            .filter(|id| self.readiness(id) == Some(readiness))
+            .cloned()
+            .collect();
+        ids.sort();
+        ids
+    }
+
     /// Get the final state as a reference to the issues HashMap
     pub fn get_final_state(&self) -> &std::collections::HashMap<String, ReferenceIssue> {
         &self.issues
@@ -898,3 +1062,31 @@ impl ReferenceInterpreter {
         self.next_id
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_issue_id_prefers_the_created_label() {
+        assert_eq!(
+            extract_issue_id("Created issue: test-1\nPath: /tmp/x/test-1.md\n"),
+            Some("test-1".to_string())
+        );
+        // Hash suffixes are base36, not just hex
+        assert_eq!(
+            extract_issue_id("✓ Created issue: test-llq\n  Title: see test-2\n"),
+            Some("test-llq".to_string())
+        );
+        // An ID-like word on an earlier line does not win over the label
+        assert_eq!(
+            extract_issue_id("Warning: test-9 is blocked\nCreated: test-a1b2\n"),
+            Some("test-a1b2".to_string())
+        );
+        assert_eq!(
+            extract_issue_id("Done: test-12\n"),
+            Some("test-12".to_string())
+        );
+        assert_eq!(extract_issue_id("nothing here\n"), None);
+    }
+}
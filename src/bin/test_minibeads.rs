@@ -9,7 +9,7 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use minibeads::beads_generator::{
-    ActionExecutor, ActionGenerator, BeadsAction, ReferenceInterpreter,
+    ActionExecutor, ActionGenerator, BeadsAction, Readiness, ReferenceInterpreter,
 };
 use rand::RngCore;
 use std::io::Write;
@@ -1640,10 +1640,46 @@ fn verify_minibeads_state(
 
     // Compare with reference
     compare_issue_states(&actual_issues, reference.get_final_state(), logger)?;
+    compare_readiness(beads_dir, reference, logger)?;
 
     Ok(())
 }
 
+/// Verify that ready/blocked, which storage derives from statuses and `blocks`
+/// dependencies rather than storing, agree with the reference model
+fn compare_readiness(
+    beads_dir: &std::path::Path,
+    reference: &ReferenceInterpreter,
+    logger: &Logger,
+) -> Result<()> {
+    use minibeads::storage::{IssueFilter, Storage};
+
+    let storage = Storage::open(beads_dir.to_path_buf())?;
+    for readiness in [Readiness::Ready, Readiness::Blocked] {
+        let mut actual: Vec<String> = storage
+            .list_issues(&IssueFilter::new().readiness(readiness), None)?
+            .into_iter()
+            .map(|issue| issue.id)
+            .collect();
+        actual.sort();
+        let expected = reference.issues_with_readiness(readiness);
+        if actual != expected {
+            return Err(anyhow::anyhow!(
+                "{:?} issues mismatch: expected {:?}, got {:?}",
+                readiness,
+                expected,
+                actual
+            ));
+        }
+        logger.verbose(format!(
+            "   ✓ {:?} issues match: {}",
+            readiness,
+            actual.len()
+        ));
+    }
+    Ok(())
+}
+
 /// Verify upstream state with dual export verification
 /// Exports database using both `bd export` and `bd sync --flush-only` and verifies both match reference
 fn verify_upstream_dual_export(
@@ -1792,6 +1828,8 @@ fn parse_minibeads_issue(path: &PathBuf) -> Result<minibeads::beads_generator::R
         status: issue.status,
         priority: issue.priority,
         issue_type: issue.issue_type,
+        assignee: issue.assignee,
+        labels: issue.labels.into_iter().collect(),
         depends_on: issue.depends_on,
     })
 }
@@ -1837,6 +1875,23 @@ fn parse_jsonl_to_reference_issue(
         .ok_or_else(|| anyhow::anyhow!("issue_type not found for {}", id))?;
     let issue_type = issue_type_str.parse()?;
 
+    let assignee = issue
+        .get("assignee")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    let labels = issue
+        .get("labels")
+        .and_then(|v| v.as_array())
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(|label| label.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
     // Parse dependencies
     let mut depends_on = std::collections::HashMap::new();
     if let Some(deps) = issue.get("dependencies").and_then(|v| v.as_array()) {
@@ -1859,6 +1914,8 @@ fn parse_jsonl_to_reference_issue(
         status,
         priority,
         issue_type,
+        assignee,
+        labels,
         depends_on,
     })
 }
@@ -1927,6 +1984,16 @@ fn compare_issue_states(
                 );
             }
 
+            // Compare description
+            if actual_issue.description != expected_issue.description {
+                eprintln!("\n❌ Description mismatch for issue {}:", id);
+                assert_eq!(
+                    &expected_issue.description, &actual_issue.description,
+                    "Description mismatch for {}",
+                    id
+                );
+            }
+
             // Compare status
             if actual_issue.status != expected_issue.status {
                 eprintln!("\n❌ Status mismatch for issue {}:", id);
@@ -1957,6 +2024,26 @@ fn compare_issue_states(
                 );
             }
 
+            // Compare assignee
+            if actual_issue.assignee != expected_issue.assignee {
+                eprintln!("\n❌ Assignee mismatch for issue {}:", id);
+                assert_eq!(
+                    &expected_issue.assignee, &actual_issue.assignee,
+                    "Assignee mismatch for {}",
+                    id
+                );
+            }
+
+            // Compare labels
+            if actual_issue.labels != expected_issue.labels {
+                eprintln!("\n❌ Labels mismatch for issue {}:", id);
+                assert_eq!(
+                    &expected_issue.labels, &actual_issue.labels,
+                    "Labels mismatch for {}",
+                    id
+                );
+            }
+
             // Compare dependencies
             if actual_issue.depends_on != expected_issue.depends_on {
                 eprintln!("\n❌ Dependencies mismatch for issue {}:", id);
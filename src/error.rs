@@ -0,0 +1,103 @@
+//! Error kinds that map to process exit codes, so scripts can tell a missing
+//! issue from a held lock or bad input without parsing messages.
+//!
+//! | Code | Meaning                                              |
+//! |------|------------------------------------------------------|
+//! | 0    | Success                                              |
+//! | 1    | Any other error                                      |
+//! | 2    | Not found: an issue, comment, or dependency          |
+//! | 3    | Locked: another process holds the database lock      |
+//! | 4    | Invalid input: bad arguments, IDs, or field values   |
+
+use std::fmt;
+
+/// Classification of an error, carried inside an `anyhow::Error`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    NotFound,
+    Locked,
+    InvalidInput,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::NotFound => 2,
+            ErrorKind::Locked => 3,
+            ErrorKind::InvalidInput => 4,
+        }
+    }
+}
+
+/// Exit code for errors that carry no `ErrorKind`
+pub const EXIT_GENERIC: i32 = 1;
+
+/// An error message tagged with its `ErrorKind`
+#[derive(Debug)]
+pub struct ClassifiedError {
+    pub kind: ErrorKind,
+    message: String,
+}
+
+impl fmt::Display for ClassifiedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ClassifiedError {}
+
+fn classified(kind: ErrorKind, message: String) -> anyhow::Error {
+    ClassifiedError { kind, message }.into()
+}
+
+pub fn not_found(message: impl Into<String>) -> anyhow::Error {
+    classified(ErrorKind::NotFound, message.into())
+}
+
+pub fn locked(message: impl Into<String>) -> anyhow::Error {
+    classified(ErrorKind::Locked, message.into())
+}
+
+pub fn invalid_input(message: impl Into<String>) -> anyhow::Error {
+    classified(ErrorKind::InvalidInput, message.into())
+}
+
+/// The kind of the outermost classified error in `err`'s chain, if any, so
+/// context added on the way up does not hide it
+pub fn kind_of(err: &anyhow::Error) -> Option<ErrorKind> {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<ClassifiedError>())
+        .map(|classified| classified.kind)
+}
+
+/// The process exit code for `err`
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    kind_of(err).map_or(EXIT_GENERIC, ErrorKind::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn kind_survives_added_context() {
+        let err = Err::<(), _>(not_found("Issue not found: demo-9"))
+            .context("Failed to close issue")
+            .unwrap_err();
+        assert_eq!(kind_of(&err), Some(ErrorKind::NotFound));
+        assert_eq!(exit_code(&err), 2);
+        assert_eq!(
+            format!("{:#}", err),
+            "Failed to close issue: Issue not found: demo-9"
+        );
+    }
+
+    #[test]
+    fn unclassified_errors_are_generic() {
+        assert_eq!(exit_code(&anyhow::anyhow!("disk full")), EXIT_GENERIC);
+        assert_eq!(exit_code(&locked("Lock held by process 1")), 3);
+        assert_eq!(exit_code(&invalid_input("Invalid priority")), 4);
+    }
+}
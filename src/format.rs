@@ -2,7 +2,7 @@ use crate::types::{DependencyType, Issue};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Frontmatter for markdown issues
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,10 +15,12 @@ pub struct Frontmatter {
     pub assignee: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub external_ref: Option<String>,
+    /// Sorted, like `depends_on`, so label order never shows up in diffs
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub labels: Vec<String>,
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub depends_on: HashMap<String, String>,
+    /// Sorted so the same issue always serializes to the same bytes
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub depends_on: BTreeMap<String, String>,
     pub created_at: String,
     pub updated_at: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -27,10 +29,114 @@ pub struct Frontmatter {
     pub claimed_at: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub claimed_until: Option<String>,
+    /// Sorted, like `labels`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watchers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<String>,
+}
+
+/// Header text used for each markdown body section.
+///
+/// Configured via the `mb-sections.*` keys in config-minibeads.yaml; the
+/// defaults are the English headers minibeads has always written. Parsing
+/// also accepts the default headers so files written before a custom mapping
+/// was configured keep their content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionHeaders {
+    pub description: String,
+    pub design: String,
+    pub acceptance_criteria: String,
+    pub notes: String,
+}
+
+impl Default for SectionHeaders {
+    fn default() -> Self {
+        SectionHeaders {
+            description: "Description".to_string(),
+            design: "Design".to_string(),
+            acceptance_criteria: "Acceptance Criteria".to_string(),
+            notes: "Notes".to_string(),
+        }
+    }
+}
+
+impl SectionHeaders {
+    /// Config keys for each logical field, in serialization order
+    pub const CONFIG_KEYS: [&'static str; 4] = [
+        "mb-sections.description",
+        "mb-sections.design",
+        "mb-sections.acceptance_criteria",
+        "mb-sections.notes",
+    ];
+
+    /// Build headers from a flat config map, falling back to the defaults for
+    /// any key that is not set.
+    pub fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        let mut headers = SectionHeaders::default();
+        for key in Self::CONFIG_KEYS {
+            let Some(value) = config.get(key) else {
+                continue;
+            };
+            let value = value.trim();
+            if value.is_empty() || value.contains('\n') || value.starts_with('#') {
+                anyhow::bail!(
+                    "Invalid {} in config-minibeads.yaml: {:?} (expected a single-line header without leading '#')",
+                    key,
+                    value
+                );
+            }
+            let slot = match key {
+                "mb-sections.description" => &mut headers.description,
+                "mb-sections.design" => &mut headers.design,
+                "mb-sections.acceptance_criteria" => &mut headers.acceptance_criteria,
+                _ => &mut headers.notes,
+            };
+            *slot = value.to_string();
+        }
+
+        let all = headers.all();
+        for (i, header) in all.iter().enumerate() {
+            if all[i + 1..].contains(header) {
+                anyhow::bail!(
+                    "Duplicate section header in config-minibeads.yaml: {:?}",
+                    header
+                );
+            }
+        }
+        Ok(headers)
+    }
+
+    fn all(&self) -> [&str; 4] {
+        [
+            &self.description,
+            &self.design,
+            &self.acceptance_criteria,
+            &self.notes,
+        ]
+    }
+
+    /// Map a header line (without the leading "# ") to its field index.
+    /// Configured headers win; the default English headers are accepted too.
+    fn field_index(&self, header: &str) -> Option<usize> {
+        let header = header.trim();
+        self.all().iter().position(|h| *h == header).or_else(|| {
+            SectionHeaders::default()
+                .all()
+                .iter()
+                .position(|h| *h == header)
+        })
+    }
 }
 
 /// Convert an Issue to markdown format
+#[allow(dead_code)] // Library entry point with the default headers; the CLI goes through Storage
 pub fn issue_to_markdown(issue: &Issue) -> Result<String> {
+    issue_to_markdown_with_sections(issue, &SectionHeaders::default())
+}
+
+/// Convert an Issue to markdown format using the given section headers
+pub fn issue_to_markdown_with_sections(issue: &Issue, sections: &SectionHeaders) -> Result<String> {
     let mut output = String::new();
 
     // Build frontmatter
@@ -41,7 +147,12 @@ pub fn issue_to_markdown(issue: &Issue) -> Result<String> {
         issue_type: issue.issue_type.to_string(),
         assignee: issue.assignee.clone(),
         external_ref: issue.external_ref.clone(),
-        labels: issue.labels.clone(),
+        labels: {
+            // Sorted on disk only; the in-memory order is left to the caller
+            let mut labels = issue.labels.clone();
+            labels.sort();
+            labels
+        },
         depends_on: issue
             .depends_on
             .iter()
@@ -52,6 +163,12 @@ pub fn issue_to_markdown(issue: &Issue) -> Result<String> {
         closed_at: issue.closed_at.map(|t| t.to_rfc3339()),
         claimed_at: issue.claimed_at.map(|t| t.to_rfc3339()),
         claimed_until: issue.claimed_until.map(|t| t.to_rfc3339()),
+        watchers: {
+            let mut watchers = issue.watchers.clone();
+            watchers.sort();
+            watchers
+        },
+        resolution: issue.resolution.map(|r| r.to_string()),
     };
 
     // Write YAML frontmatter
@@ -60,40 +177,46 @@ pub fn issue_to_markdown(issue: &Issue) -> Result<String> {
     output.push_str("---\n");
 
     // Write markdown sections
-    if !issue.description.is_empty() {
-        output.push_str("\n# Description\n\n");
-        output.push_str(&sanitize_section_content(&issue.description));
-        output.push('\n');
-    }
-
-    if !issue.design.is_empty() {
-        output.push_str("\n# Design\n\n");
-        output.push_str(&sanitize_section_content(&issue.design));
-        output.push('\n');
-    }
-
-    if !issue.acceptance_criteria.is_empty() {
-        output.push_str("\n# Acceptance Criteria\n\n");
-        output.push_str(&sanitize_section_content(&issue.acceptance_criteria));
-        output.push('\n');
-    }
-
-    if !issue.notes.is_empty() {
-        output.push_str("\n# Notes\n\n");
-        output.push_str(&sanitize_section_content(&issue.notes));
-        output.push('\n');
+    let bodies = [
+        &issue.description,
+        &issue.design,
+        &issue.acceptance_criteria,
+        &issue.notes,
+    ];
+    for (header, content) in sections.all().iter().zip(bodies) {
+        if !content.is_empty() {
+            output.push_str(&format!("\n# {}\n\n", header));
+            output.push_str(&escape_section_content(content));
+            output.push('\n');
+        }
     }
 
     Ok(output)
 }
 
-/// Sanitize section content to prevent top-level headers from breaking the format
-fn sanitize_section_content(content: &str) -> String {
-    content
-        .lines()
-        .map(|line| {
-            if line.starts_with("# ") {
-                format!("#{}", line) // Convert H1 to H2
+/// Escape section content so top-level headers can't break the format.
+///
+/// A line that would parse as a section header (optional indentation, then
+/// `# `) gets a backslash inserted before the `#`. Lines already carrying
+/// such backslashes get one more, so `unescape_section_line` can undo the
+/// transformation exactly and content survives any number of round-trips.
+///
+/// Lines inside closed code fences are left alone. An opening fence with no
+/// closing fence is escaped the same way, so it can't swallow the section
+/// headers that follow it.
+fn escape_section_content(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let fenced = closed_fence_lines(&lines);
+    lines
+        .iter()
+        .zip(fenced)
+        .map(|(line, fenced)| {
+            let indent = line.len() - line.trim_start().len();
+            let rest = &line[indent..];
+            if !fenced
+                && (rest.starts_with("# ") || fence_marker(rest).is_some() || is_escaped(rest))
+            {
+                format!("{}\\{}", &line[..indent], rest)
             } else {
                 line.to_string()
             }
@@ -102,8 +225,64 @@ fn sanitize_section_content(content: &str) -> String {
         .join("\n")
 }
 
+/// Reverse `escape_section_content` for a single line outside a code fence
+fn unescape_section_line(line: &str) -> String {
+    let indent = line.len() - line.trim_start().len();
+    if is_escaped(&line[indent..]) {
+        format!("{}{}", &line[..indent], &line[indent + 1..])
+    } else {
+        line.to_string()
+    }
+}
+
+/// True for one or more backslashes followed by `# ` or a fence marker
+fn is_escaped(text: &str) -> bool {
+    let rest = text.trim_start_matches('\\');
+    rest.len() < text.len() && (rest.starts_with("# ") || fence_marker(rest).is_some())
+}
+
+/// The fence marker (```` ``` ```` or `~~~`) a line opens or closes, if any
+fn fence_marker(line: &str) -> Option<&'static str> {
+    let trimmed = line.trim_start();
+    ["```", "~~~"]
+        .into_iter()
+        .find(|marker| trimmed.starts_with(marker))
+}
+
+/// Mark the lines that belong to a closed code fence, delimiters included.
+/// An opening fence without a matching closing fence is not a fence.
+fn closed_fence_lines(lines: &[&str]) -> Vec<bool> {
+    let mut fenced = vec![false; lines.len()];
+    let mut i = 0;
+    while i < lines.len() {
+        if let Some(marker) = fence_marker(lines[i]) {
+            let close = lines[i + 1..]
+                .iter()
+                .position(|line| line.trim_start().starts_with(marker));
+            if let Some(offset) = close {
+                let end = i + 1 + offset;
+                fenced[i..=end].iter_mut().for_each(|f| *f = true);
+                i = end + 1;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    fenced
+}
+
 /// Parse markdown format into an Issue
+#[allow(dead_code)] // Library entry point with the default headers; the CLI goes through Storage
 pub fn markdown_to_issue(issue_id: &str, content: &str) -> Result<Issue> {
+    markdown_to_issue_with_sections(issue_id, content, &SectionHeaders::default())
+}
+
+/// Parse markdown format into an Issue using the given section headers
+pub fn markdown_to_issue_with_sections(
+    issue_id: &str,
+    content: &str,
+    sections: &SectionHeaders,
+) -> Result<Issue> {
     // Split frontmatter and body
     let parts: Vec<&str> = content.splitn(3, "---\n").collect();
     if parts.len() < 3 {
@@ -165,7 +344,7 @@ pub fn markdown_to_issue(issue_id: &str, content: &str) -> Result<Issue> {
     })?;
 
     // Parse body sections
-    let (description, design, acceptance_criteria, notes) = parse_sections(parts[2]);
+    let (description, design, acceptance_criteria, notes) = parse_sections(parts[2], sections);
 
     // Build Issue
     let mut issue = Issue {
@@ -191,6 +370,8 @@ pub fn markdown_to_issue(issue_id: &str, content: &str) -> Result<Issue> {
             .claimed_until
             .as_ref()
             .and_then(|s| parse_timestamp(s).ok()),
+        watchers: fm.watchers,
+        resolution: fm.resolution.as_deref().map(str::parse).transpose()?,
     };
 
     // Convert dependencies
@@ -203,61 +384,121 @@ pub fn markdown_to_issue(issue_id: &str, content: &str) -> Result<Issue> {
 }
 
 /// Parse markdown sections from the body
-fn parse_sections(body: &str) -> (String, String, String, String) {
-    let mut description = String::new();
-    let mut design = String::new();
-    let mut acceptance_criteria = String::new();
-    let mut notes = String::new();
+fn parse_sections(body: &str, sections: &SectionHeaders) -> (String, String, String, String) {
+    let mut fields: [String; 4] = Default::default();
 
-    let mut current_section = "";
+    let mut current_section: Option<Option<usize>> = None;
     let mut current_content = String::new();
 
-    for line in body.lines() {
+    let lines: Vec<&str> = body.lines().collect();
+    let fenced = closed_fence_lines(&lines);
+
+    for (line, fenced) in lines.into_iter().zip(fenced) {
         let trimmed = line.trim();
 
-        // Check if this is a top-level header
-        if let Some(header) = trimmed.strip_prefix("# ") {
-            // Save previous section
-            if !current_section.is_empty() {
-                let content = current_content.trim().to_string();
-                match current_section {
-                    "Description" => description = content,
-                    "Design" => design = content,
-                    "Acceptance Criteria" => acceptance_criteria = content,
-                    "Notes" => notes = content,
-                    _ => {} // Ignore unknown sections
-                }
+        // Check if this is a top-level header (never inside a code fence)
+        let header = if fenced {
+            None
+        } else {
+            trimmed.strip_prefix("# ")
+        };
+        if let Some(header) = header {
+            // Save previous section (unknown sections are ignored)
+            if let Some(Some(index)) = current_section {
+                fields[index] = current_content.trim().to_string();
             }
 
             // Start new section
-            current_section = header;
+            current_section = Some(sections.field_index(header));
             current_content.clear();
-        } else if !current_section.is_empty() {
+        } else if current_section.is_some() {
             // Add line to current section
             if !current_content.is_empty() {
                 current_content.push('\n');
             }
-            current_content.push_str(line);
+            if fenced {
+                current_content.push_str(line);
+            } else {
+                current_content.push_str(&unescape_section_line(line));
+            }
         }
     }
 
     // Save last section
-    if !current_section.is_empty() {
-        let content = current_content.trim().to_string();
-        match current_section {
-            "Description" => description = content,
-            "Design" => design = content,
-            "Acceptance Criteria" => acceptance_criteria = content,
-            "Notes" => notes = content,
-            _ => {}
-        }
+    if let Some(Some(index)) = current_section {
+        fields[index] = current_content.trim().to_string();
     }
 
+    let [description, design, acceptance_criteria, notes] = fields;
     (description, design, acceptance_criteria, notes)
 }
 
-/// Parse a timestamp string
-fn parse_timestamp(s: &str) -> Result<DateTime<Utc>> {
+/// Section content parsed from a free-form markdown body
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BodySections {
+    pub description: String,
+    pub design: String,
+    pub acceptance_criteria: String,
+    pub notes: String,
+}
+
+/// Parse a markdown body (as written below an issue's frontmatter) supplied by
+/// the user, e.g. via `mb create --stdin`.
+///
+/// A body without any section headers is taken as the description. Text
+/// before the first header is prepended to the description. Unknown
+/// top-level headers are rejected rather than silently dropped.
+pub fn parse_body(body: &str, sections: &SectionHeaders) -> Result<BodySections> {
+    let lines: Vec<&str> = body.lines().collect();
+    let fenced = closed_fence_lines(&lines);
+
+    let mut first_header = None;
+    for (i, (line, fenced)) in lines.iter().zip(&fenced).enumerate() {
+        if *fenced {
+            continue;
+        }
+        if let Some(header) = line.trim().strip_prefix("# ") {
+            if sections.field_index(header).is_none() {
+                let known = sections.all().map(|h| format!("'# {}'", h)).join(", ");
+                anyhow::bail!(
+                    "Unknown section header '# {}' in body (expected one of: {})",
+                    header.trim(),
+                    known
+                );
+            }
+            first_header.get_or_insert(i);
+        }
+    }
+
+    let Some(first_header) = first_header else {
+        return Ok(BodySections {
+            description: body.trim().to_string(),
+            ..Default::default()
+        });
+    };
+
+    let preamble = lines[..first_header].join("\n").trim().to_string();
+    let rest = lines[first_header..].join("\n");
+    let (mut description, design, acceptance_criteria, notes) = parse_sections(&rest, sections);
+    if !preamble.is_empty() {
+        description = if description.is_empty() {
+            preamble
+        } else {
+            format!("{}\n\n{}", preamble, description)
+        };
+    }
+
+    Ok(BodySections {
+        description,
+        design,
+        acceptance_criteria,
+        notes,
+    })
+}
+
+/// Parse a timestamp string (RFC 3339 or one of the legacy frontmatter
+/// formats)
+pub fn parse_timestamp(s: &str) -> Result<DateTime<Utc>> {
     // Try RFC3339 format
     if let Ok(t) = DateTime::parse_from_rfc3339(s) {
         return Ok(t.with_timezone(&Utc));
@@ -306,6 +547,57 @@ mod tests {
         assert_eq!(issue.depends_on, parsed.depends_on);
     }
 
+    #[test]
+    fn test_serialization_is_deterministic() {
+        let mut issue = Issue::new(
+            "test-1".to_string(),
+            "Many deps".to_string(),
+            2,
+            IssueType::Task,
+        );
+        for n in (2..20).rev() {
+            issue
+                .depends_on
+                .insert(format!("test-{}", n), DependencyType::Related);
+        }
+
+        let first = issue_to_markdown(&issue).unwrap();
+        // A fresh map with the same entries has its own iteration order
+        issue.depends_on = issue.depends_on.clone().into_iter().collect();
+        assert_eq!(first, issue_to_markdown(&issue).unwrap());
+
+        let keys: Vec<&str> = first
+            .lines()
+            .filter_map(|line| line.trim().strip_suffix(": related"))
+            .collect();
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_eq!(keys.len(), 18);
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn test_label_order_does_not_affect_serialization() {
+        let mut issue = Issue::new(
+            "test-1".to_string(),
+            "Labelled".to_string(),
+            2,
+            IssueType::Task,
+        );
+        issue.labels = vec!["ui".to_string(), "api".to_string(), "db".to_string()];
+        let markdown = issue_to_markdown(&issue).unwrap();
+        assert!(
+            markdown.contains("labels:\n- api\n- db\n- ui\n"),
+            "{}",
+            markdown
+        );
+        // Serializing does not reorder the caller's labels
+        assert_eq!(issue.labels, vec!["ui", "api", "db"]);
+
+        issue.labels.reverse();
+        assert_eq!(markdown, issue_to_markdown(&issue).unwrap());
+    }
+
     #[test]
     fn test_claim_fields_roundtrip() {
         // A claimed issue must round-trip its assignee + claim window losslessly
@@ -333,6 +625,25 @@ mod tests {
         assert_eq!(parsed.claimed_until, issue.claimed_until);
     }
 
+    #[test]
+    fn test_watchers_round_trip_sorted() {
+        let mut issue = Issue::new(
+            "test-7".to_string(),
+            "Watched".to_string(),
+            2,
+            IssueType::Task,
+        );
+        issue.watchers = vec!["zoe".to_string(), "alice".to_string()];
+
+        let markdown = issue_to_markdown(&issue).unwrap();
+        assert!(markdown.contains("watchers:\n- alice\n- zoe\n"));
+        let parsed = markdown_to_issue("test-7", &markdown).unwrap();
+        assert_eq!(parsed.watchers, ["alice", "zoe"]);
+
+        issue.watchers.clear();
+        assert!(!issue_to_markdown(&issue).unwrap().contains("watchers"));
+    }
+
     #[test]
     fn test_unclaimed_issue_omits_claim_fields() {
         let issue = Issue::new(
@@ -347,11 +658,164 @@ mod tests {
         assert!(!markdown.contains("assignee"));
     }
 
+    fn custom_sections() -> SectionHeaders {
+        let config: HashMap<String, String> = [
+            ("mb-sections.description", "Beschreibung"),
+            ("mb-sections.design", "Entwurf"),
+            ("mb-sections.acceptance_criteria", "Abnahmekriterien"),
+            ("mb-sections.notes", "Notizen"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        SectionHeaders::from_config(&config).unwrap()
+    }
+
+    #[test]
+    fn test_custom_section_headers_roundtrip() {
+        let sections = custom_sections();
+        let mut issue = Issue::new(
+            "test-1".to_string(),
+            "Custom headers".to_string(),
+            2,
+            IssueType::Task,
+        );
+        issue.description = "What".to_string();
+        issue.design = "How".to_string();
+        issue.acceptance_criteria = "Done when".to_string();
+        issue.notes = "Misc".to_string();
+
+        let markdown = issue_to_markdown_with_sections(&issue, &sections).unwrap();
+        assert!(markdown.contains("\n# Beschreibung\n"));
+        assert!(markdown.contains("\n# Abnahmekriterien\n"));
+        assert!(!markdown.contains("# Description"));
+
+        let parsed = markdown_to_issue_with_sections("test-1", &markdown, &sections).unwrap();
+        assert_eq!(parsed.description, issue.description);
+        assert_eq!(parsed.design, issue.design);
+        assert_eq!(parsed.acceptance_criteria, issue.acceptance_criteria);
+        assert_eq!(parsed.notes, issue.notes);
+    }
+
+    #[test]
+    fn test_custom_sections_still_parse_default_headers() {
+        let mut issue = Issue::new("test-2".to_string(), "Old".to_string(), 2, IssueType::Task);
+        issue.description = "Written before the mapping".to_string();
+        issue.notes = "Still here".to_string();
+
+        let markdown = issue_to_markdown(&issue).unwrap();
+        let parsed =
+            markdown_to_issue_with_sections("test-2", &markdown, &custom_sections()).unwrap();
+        assert_eq!(parsed.description, issue.description);
+        assert_eq!(parsed.notes, issue.notes);
+    }
+
+    #[test]
+    fn test_section_headers_config_validation() {
+        let mut config = HashMap::new();
+        config.insert("mb-sections.notes".to_string(), "Description".to_string());
+        assert!(SectionHeaders::from_config(&config).is_err());
+
+        config.insert("mb-sections.notes".to_string(), "# Notes".to_string());
+        assert!(SectionHeaders::from_config(&config).is_err());
+
+        config.insert("mb-sections.notes".to_string(), "Remarks".to_string());
+        let headers = SectionHeaders::from_config(&config).unwrap();
+        assert_eq!(headers.notes, "Remarks");
+        assert_eq!(headers.description, "Description");
+    }
+
     #[test]
     fn test_sanitize_headers() {
         let content = "# This is a header\nNormal text\n## This is h2";
-        let sanitized = sanitize_section_content(content);
-        assert!(sanitized.starts_with("## This is a header"));
+        let sanitized = escape_section_content(content);
+        assert!(sanitized.starts_with("\\# This is a header"));
+        assert!(sanitized.ends_with("\n## This is h2"));
+    }
+
+    #[test]
+    fn test_headers_in_description_roundtrip() {
+        let mut issue = Issue::new(
+            "test-3".to_string(),
+            "Headers".to_string(),
+            2,
+            IssueType::Task,
+        );
+        issue.description = "# Overview\nIntro\n## Details\n  # indented\n\\# literal\n\\\\# double\n# Design\nnot a section".to_string();
+        issue.notes = "# Notes\nstill notes".to_string();
+
+        let mut current = issue.clone();
+        for _ in 0..3 {
+            let markdown = issue_to_markdown(&current).unwrap();
+            current = markdown_to_issue("test-3", &markdown).unwrap();
+            assert_eq!(current.description, issue.description);
+            assert_eq!(current.design, "");
+            assert_eq!(current.notes, issue.notes);
+        }
+    }
+
+    #[test]
+    fn test_fenced_code_block_is_not_sanitized_or_split() {
+        let mut issue = Issue::new(
+            "test-4".to_string(),
+            "Fences".to_string(),
+            2,
+            IssueType::Task,
+        );
+        issue.description =
+            "Run this:\n```sh\n# not a header\nmake test\n# Design\n```\nAfter the fence"
+                .to_string();
+        issue.design = "The design".to_string();
+
+        let markdown = issue_to_markdown(&issue).unwrap();
+        assert!(markdown.contains("\n# not a header\n"));
+        assert!(!markdown.contains("\\# not a header"));
+
+        let parsed = markdown_to_issue("test-4", &markdown).unwrap();
+        assert_eq!(parsed.description, issue.description);
+        assert_eq!(parsed.design, issue.design);
+    }
+
+    #[test]
+    fn test_unclosed_fence_does_not_swallow_sections() {
+        let mut issue = Issue::new(
+            "test-5".to_string(),
+            "Unclosed".to_string(),
+            2,
+            IssueType::Task,
+        );
+        issue.description = "```\n# comment\n\\```".to_string();
+        issue.design = "Design text\n```\nclosed later\n```".to_string();
+
+        let markdown = issue_to_markdown(&issue).unwrap();
+        let parsed = markdown_to_issue("test-5", &markdown).unwrap();
+        assert_eq!(parsed.description, issue.description);
+        assert_eq!(parsed.design, issue.design);
+    }
+
+    #[test]
+    fn test_parse_body_sections() {
+        let sections = SectionHeaders::default();
+
+        let plain = parse_body("Just a description\n\nwith two paragraphs\n", &sections).unwrap();
+        assert_eq!(
+            plain.description,
+            "Just a description\n\nwith two paragraphs"
+        );
+        assert_eq!(plain.design, "");
+
+        let body = "Intro\n\n# Description\nWhat\n```\n# shell comment\n```\n# Design\nHow\n# Notes\nMisc\n";
+        let parsed = parse_body(body, &sections).unwrap();
+        assert_eq!(
+            parsed.description,
+            "Intro\n\nWhat\n```\n# shell comment\n```"
+        );
+        assert_eq!(parsed.design, "How");
+        assert_eq!(parsed.acceptance_criteria, "");
+        assert_eq!(parsed.notes, "Misc");
+
+        let err = parse_body("# Description\nx\n# Bogus\ny", &sections).unwrap_err();
+        assert!(err.to_string().contains("Unknown section header '# Bogus'"));
     }
 
     #[test]
@@ -1,6 +1,6 @@
 //! GitHub Issues sync using the authenticated `gh` CLI.
 
-use crate::storage::Storage;
+use crate::storage::{IssueFilter, IssueSpec, Storage};
 use crate::types::{Comment, Issue, IssueType, Status};
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
@@ -571,7 +571,7 @@ async fn publish_issue_async(
 ) -> Result<GithubSyncReport> {
     let issue = storage
         .get_issue(issue_id)?
-        .ok_or_else(|| anyhow!("Issue not found: {}", issue_id))?;
+        .ok_or_else(|| crate::error::not_found(format!("Issue not found: {}", issue_id)))?;
 
     if issue
         .external_ref
@@ -688,7 +688,7 @@ async fn import_issues_with_store(
 ) -> Result<GithubImportReport> {
     let remote_issues = list_remote_issues(options, store).await?;
     let existing_refs: HashSet<String> = storage
-        .list_issues(None, None, None, None, None)?
+        .list_issues(&IssueFilter::new(), None)?
         .into_iter()
         .filter_map(|issue| issue.external_ref)
         .collect();
@@ -731,17 +731,11 @@ async fn import_issues_with_store(
         }
 
         let issue = storage.create_issue(
-            remote.title.clone(),
-            remote.body.clone(),
-            None,
-            None,
-            2,
-            IssueType::Task,
-            None,
-            Vec::new(),
-            Some(remote.url.clone()),
-            None,
-            Vec::new(),
+            IssueSpec::new(remote.title.clone())
+                .description(remote.body.clone())
+                .priority(2)
+                .issue_type(IssueType::Task)
+                .external_ref(remote.url.clone()),
         )?;
         let issue = if remote.state.eq_ignore_ascii_case("closed") {
             storage.close_issue(&issue.id, "Imported closed GitHub issue")?
@@ -874,7 +868,7 @@ async fn sync_linked_with_store(
         issues: Vec::new(),
     };
 
-    let mut issues = storage.list_issues(None, None, None, None, None)?;
+    let mut issues = storage.list_issues(&IssueFilter::new(), None)?;
     if !issue_ids.is_empty() {
         let wanted: HashSet<&str> = issue_ids.iter().map(String::as_str).collect();
         issues.retain(|issue| wanted.contains(issue.id.as_str()));
@@ -1273,17 +1267,10 @@ pub fn stress_test(
         let title = format!("mb gh sync stress {run_id} issue {i}");
         let body = format!("initial local body {run_id} issue {i}");
         let issue = storage.create_issue(
-            title.clone(),
-            body.clone(),
-            None,
-            None,
-            2,
-            IssueType::Task,
-            None,
-            Vec::new(),
-            None,
-            None,
-            Vec::new(),
+            IssueSpec::new(title.clone())
+                .description(body.clone())
+                .priority(2)
+                .issue_type(IssueType::Task),
         )?;
 
         let publish = publish_issue(&storage, &issue.id, Some(repo), false)
@@ -1529,17 +1516,10 @@ fn stress_test_adversarial(
         let title = format!("mb gh sync adversarial {} issue {i}", context.run_id);
         let body = format!("initial adversarial body {} issue {i}", context.run_id);
         let issue = storage.create_issue(
-            title.clone(),
-            body.clone(),
-            None,
-            None,
-            2,
-            IssueType::Task,
-            None,
-            Vec::new(),
-            None,
-            None,
-            Vec::new(),
+            IssueSpec::new(title.clone())
+                .description(body.clone())
+                .priority(2)
+                .issue_type(IssueType::Task),
         )?;
         let publish = publish_issue(storage, &issue.id, Some(context.repo), false)
             .with_context(|| format!("adversarial publish failed for {}", issue.id))?;
@@ -2795,19 +2775,7 @@ mod tests {
         let tmp = tempfile::tempdir().unwrap();
         let storage = Storage::init(tmp.path().join(".beads"), None, false).unwrap();
         let issue = storage
-            .create_issue(
-                "Local title".to_string(),
-                "Local body".to_string(),
-                None,
-                None,
-                2,
-                IssueType::Task,
-                None,
-                Vec::new(),
-                None,
-                None,
-                Vec::new(),
-            )
+            .create_issue(IssueSpec::new("Local title").description("Local body".to_string()))
             .unwrap();
         (tmp, storage, issue)
     }
@@ -3079,17 +3047,9 @@ mod tests {
         let (tmp, storage, _issue) = storage_with_issue();
         storage
             .create_issue(
-                "Already linked".to_string(),
-                "Existing body".to_string(),
-                None,
-                None,
-                2,
-                IssueType::Task,
-                None,
-                Vec::new(),
-                Some("https://github.com/example/repo/issues/1".to_string()),
-                None,
-                Vec::new(),
+                IssueSpec::new("Already linked")
+                    .description("Existing body".to_string())
+                    .external_ref("https://github.com/example/repo/issues/1".to_string()),
             )
             .unwrap();
         let (program, log) = fake_gh_for_import(&tmp);
@@ -3107,7 +3067,7 @@ mod tests {
         assert_eq!(report.imported, 1);
         assert_eq!(report.skipped_existing, 1);
         let imported = storage
-            .list_issues(None, None, None, None, None)
+            .list_issues(&IssueFilter::new(), None)
             .unwrap()
             .into_iter()
             .find(|issue| {
@@ -3151,17 +3111,9 @@ mod tests {
         let storage = Storage::init(tmp.path().join(".beads"), None, false).unwrap();
         let issue = storage
             .create_issue(
-                "Local title".to_string(),
-                "Local body".to_string(),
-                None,
-                None,
-                2,
-                IssueType::Task,
-                None,
-                Vec::new(),
-                Some("https://github.com/example/repo/issues/1".to_string()),
-                None,
-                Vec::new(),
+                IssueSpec::new("Local title")
+                    .description("Local body".to_string())
+                    .external_ref("https://github.com/example/repo/issues/1".to_string()),
             )
             .unwrap();
         let local_comment = storage
@@ -10,6 +10,17 @@ pub enum HashEncoding {
     Hex,
 }
 
+impl HashEncoding {
+    /// Valid hash lengths for this encoding. Base36 matches upstream bd's
+    /// 3..=8; hex starts at 4.
+    pub fn length_range(&self) -> std::ops::RangeInclusive<usize> {
+        match self {
+            HashEncoding::Base36 => 3..=8,
+            HashEncoding::Hex => 4..=8,
+        }
+    }
+}
+
 /// Base36 alphabet for encoding (0-9, a-z)
 const BASE36_ALPHABET: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
 
@@ -101,6 +112,33 @@ fn encode_base36(data: &[u8], length: usize) -> String {
     result
 }
 
+/// A hash ID picked by [`generate_hash_id_with_collision_check`], with how it
+/// was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedHashId {
+    pub id: String,
+    /// Hash length of `id`, in characters
+    pub length: usize,
+    /// Nonce that produced `id`
+    pub nonce: u32,
+    /// Length the search started at, chosen from the database size
+    pub initial_length: usize,
+}
+
+impl GeneratedHashId {
+    /// Whether collisions forced a longer ID than the database size called
+    /// for, a sign the database is large for its hash space
+    pub fn escalated(&self) -> bool {
+        self.length > self.initial_length
+    }
+
+    /// Whether the first candidate collided, so another nonce or length was
+    /// needed
+    pub fn collided(&self) -> bool {
+        self.nonce > 0 || self.escalated()
+    }
+}
+
 /// Generate a hash-based ID with collision handling
 ///
 /// Takes a collision checker function that returns true if the ID already exists.
@@ -113,28 +151,32 @@ fn encode_base36(data: &[u8], length: usize) -> String {
 /// * `description` - Issue description
 /// * `timestamp` - Creation timestamp for deterministic hashing
 /// * `estimated_db_size` - Approximate number of existing issues (for adaptive length)
+/// * `start_length` - Length to start at instead of the adaptive one (`mb-hash-min-length`)
 /// * `encoding` - Hash encoding format (base36 or hex)
 /// * `collision_check` - Function that returns true if an ID already exists
 ///
 /// # Returns
-/// A unique hash-based ID like "minibeads-4f10" or "minibeads-b127a5"
+/// A unique hash-based ID like "minibeads-4f10" or "minibeads-b127a5", with the
+/// length and nonce it was found at
+#[allow(clippy::too_many_arguments)]
 pub fn generate_hash_id_with_collision_check<F>(
     prefix: &str,
     title: &str,
     description: &str,
     timestamp: DateTime<Utc>,
     estimated_db_size: usize,
+    start_length: Option<usize>,
     encoding: HashEncoding,
     mut collision_check: F,
-) -> anyhow::Result<String>
+) -> anyhow::Result<GeneratedHashId>
 where
     F: FnMut(&str) -> bool,
 {
     let creator = "user"; // Default creator
 
-    // Adaptive length based on database size and encoding
+    // Adaptive length based on database size and encoding, unless overridden
     // Base36 starts at length 3 (vs hex which starts at 4)
-    let initial_length = match encoding {
+    let initial_length = start_length.unwrap_or(match encoding {
         HashEncoding::Base36 => {
             if estimated_db_size < 10 {
                 3
@@ -164,7 +206,7 @@ where
                 8
             }
         }
-    };
+    });
 
     // Try adaptive lengths starting from initial_length, checking for collisions
     for length in initial_length..=8 {
@@ -182,7 +224,12 @@ where
 
             // Check for collision using provided function
             if !collision_check(&candidate) {
-                return Ok(candidate);
+                return Ok(GeneratedHashId {
+                    id: candidate,
+                    length,
+                    nonce,
+                    initial_length,
+                });
             }
         }
     }
@@ -469,4 +516,59 @@ mod tests {
         assert!(id_base36.starts_with("test-"));
         assert!(id_hex.starts_with("test-"));
     }
+
+    #[test]
+    fn test_collision_check_escalates_length() {
+        let timestamp = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let first = generate_hash_id_with_collision_check(
+            "test",
+            "Title",
+            "",
+            timestamp,
+            0,
+            None,
+            HashEncoding::Base36,
+            |_| false,
+        )
+        .unwrap();
+        assert_eq!((first.length, first.nonce, first.initial_length), (3, 0, 3));
+        assert!(!first.escalated());
+
+        // Every 3-character candidate collides, so the search has to widen
+        let escalated = generate_hash_id_with_collision_check(
+            "test",
+            "Title",
+            "",
+            timestamp,
+            0,
+            None,
+            HashEncoding::Base36,
+            |candidate| candidate.len() == "test-".len() + 3,
+        )
+        .unwrap();
+        assert_eq!(escalated.length, 4);
+        assert_eq!(escalated.id.len(), "test-".len() + 4);
+        assert!(escalated.escalated());
+    }
+
+    #[test]
+    fn test_collision_check_honors_start_length() {
+        let timestamp = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+        let generated = generate_hash_id_with_collision_check(
+            "test",
+            "Title",
+            "",
+            timestamp,
+            0,
+            Some(6),
+            HashEncoding::Base36,
+            |_| false,
+        )
+        .unwrap();
+        assert_eq!(generated.length, 6);
+        assert_eq!(generated.initial_length, 6);
+        assert_eq!(generated.id.len(), "test-".len() + 6);
+    }
 }
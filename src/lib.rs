@@ -3,10 +3,14 @@
 //! This module exposes shared functionality used by both the main binary
 //! and test utilities.
 
+pub mod api;
 pub mod beads_generator;
+pub mod error;
 pub mod format;
 pub mod hash;
 pub mod lock;
 pub mod storage;
 pub mod sync;
 pub mod types;
+
+pub use api::{IssueSpec, MiniBeads};
@@ -1,3 +1,4 @@
+use crate::error;
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -33,7 +34,10 @@ impl Lock {
                 Err(e) => {
                     // Check if we've exceeded max backoff time
                     if total_wait >= MAX_BACKOFF_MS {
-                        anyhow::bail!("Failed to acquire lock after {}ms: {}", MAX_BACKOFF_MS, e);
+                        return Err(error::locked(format!(
+                            "Failed to acquire lock after {}ms: {}",
+                            MAX_BACKOFF_MS, e
+                        )));
                     }
 
                     // Wait with exponential backoff
@@ -1,9 +1,11 @@
 mod code_patch;
+mod error;
 mod format;
 mod github;
 mod hash;
 mod lock;
 mod storage;
+mod style;
 mod sync;
 mod types;
 
@@ -13,18 +15,26 @@ mod built_info {
 }
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Parser, Subcommand};
 use std::collections::{BTreeMap, HashMap};
 use std::env;
-use std::io::{IsTerminal, Write};
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command as ProcessCommand, Stdio};
-use storage::{is_github_issue_ref, Storage};
-use types::{ClaimDuration, Comment, DependencyType, EditField, Issue, IssueType, Status};
+use storage::{is_github_issue_ref, Change, IssueFilter, IssueSpec, ReportOptions, Storage};
+use types::{
+    ClaimDuration, ClosureDirection, Comment, DependencyType, EditField, FieldBlame, HistoryEntry,
+    ImportMode, Issue, IssueType, JsonlFlavor, ListSort, Readiness, ReadinessReport, Resolution,
+    Status, Transition,
+};
 
 const PRIMARY_STORAGE_DIR: &str = ".minibeads";
 const LEGACY_STORAGE_DIR: &str = ".beads";
 
+/// Title similarity at or above which `create --check-dupes` treats an open
+/// issue as a likely duplicate
+const SIMILAR_TITLE_THRESHOLD: f64 = 0.6;
+
 /// Generate long version string with git info and build date
 fn long_version() -> &'static str {
     // Allocate version string at runtime, leak it to get 'static lifetime
@@ -66,11 +76,17 @@ struct Cli {
 #[derive(clap::Args)]
 #[command(next_help_heading = "Global Options")]
 struct GlobalOpts {
-    /// Path to minibeads directory (minibeads-specific, preferred over --db)
-    #[arg(long = "mb-beads-dir", global = true)]
+    /// Path to the beads directory itself, e.g. ./.beads (minibeads-specific,
+    /// preferred over --db)
+    #[arg(
+        long = "beads-dir",
+        visible_alias = "mb-beads-dir",
+        value_name = "DIR",
+        global = true
+    )]
     mb_beads_dir: Option<PathBuf>,
 
-    /// Path to database (for upstream compatibility - use --mb-beads-dir instead)
+    /// Path to database (for upstream compatibility - use --beads-dir instead)
     /// Treated as syntactic sugar for specifying the minibeads directory.
     /// When path ends with .db, the parent directory is used.
     #[arg(long, global = true)]
@@ -124,7 +140,8 @@ struct GlobalOpts {
     #[arg(long, global = true, hide = true)]
     flat: bool,
 
-    /// Validation mode for parsing issues: silent, warn, error (minibeads-specific)
+    /// Validation mode for parsing issues: silent, warn, error; silent also mutes
+    /// hash-ID collision warnings (minibeads-specific)
     #[arg(
         long = "mb-validation",
         global = true,
@@ -133,6 +150,10 @@ struct GlobalOpts {
     )]
     mb_validation: ValidationMode,
 
+    /// Disable colored output (colors are also off when stdout is not a terminal or NO_COLOR is set)
+    #[arg(long = "no-color", global = true)]
+    no_color: bool,
+
     /// Disable command logging to the minibeads command_history.log (minibeads-specific)
     #[arg(long = "mb-no-cmd-logging", global = true)]
     mb_no_cmd_logging: bool,
@@ -169,6 +190,126 @@ impl std::str::FromStr for ValidationMode {
     }
 }
 
+/// Output format for `mb export` and `mb list --format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    /// One compact JSON object per line (machine format)
+    Jsonl,
+    /// A single pretty-printed JSON array (for human review)
+    Json,
+    /// JSONL on stdout, flushed after every line so a downstream consumer
+    /// can process issues as they arrive
+    NdjsonStream,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            "json" => Ok(ExportFormat::Json),
+            "ndjson-stream" => Ok(ExportFormat::NdjsonStream),
+            _ => Err(anyhow::anyhow!(
+                "Invalid export format: '{}'. Valid values are: jsonl, json, ndjson-stream",
+                s
+            )),
+        }
+    }
+}
+
+/// Output format for `mb graph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart, for GitHub/GitLab Markdown
+    Mermaid,
+}
+
+impl std::str::FromStr for GraphFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(GraphFormat::Dot),
+            "mermaid" => Ok(GraphFormat::Mermaid),
+            _ => Err(anyhow::anyhow!(
+                "Invalid graph format: '{}'. Valid values are: dot, mermaid",
+                s
+            )),
+        }
+    }
+}
+
+/// Issue filters shared by `list` and `export`, so the two commands select
+/// issues the same way
+#[derive(clap::Args)]
+struct FilterArgs {
+    /// Filter by status: open, in_progress, blocked, closed
+    #[arg(short = 's', long)]
+    status: Option<String>,
+
+    /// Filter by priority (repeatable and/or comma-separated, e.g. "-p 0 -p 1" or "-p 0,1")
+    #[arg(short = 'p', long)]
+    priority: Vec<String>,
+
+    /// Filter by type: bug, feature, task, epic, chore (repeatable and/or
+    /// comma-separated, e.g. "--type bug --type feature" or "--type bug,feature")
+    #[arg(long, value_delimiter = ',')]
+    r#type: Vec<IssueType>,
+
+    /// Filter closed issues by resolution: done, wontfix, duplicate, obsolete
+    /// (minibeads-specific)
+    #[arg(long)]
+    resolution: Option<Resolution>,
+
+    /// Filter by assignee
+    #[arg(long)]
+    assignee: Option<String>,
+
+    /// Show only issues with no assignee (minibeads-specific)
+    #[arg(long, conflicts_with = "assignee")]
+    unassigned: bool,
+
+    /// Filter by labels (must have ALL specified labels)
+    #[arg(short = 'l', long = "label")]
+    labels: Vec<String>,
+
+    /// Show only issues linked to GitHub Issues (minibeads-specific)
+    #[arg(long)]
+    github: bool,
+
+    /// Filter by specific issue IDs (comma-separated)
+    #[arg(long)]
+    id: Option<String>,
+
+    /// Filter by title substring (case-insensitive)
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Filter to direct children of a parent issue
+    #[arg(long)]
+    parent: Option<String>,
+
+    /// Show only ready issues: open with no blocking dependencies (minibeads-specific)
+    #[arg(long, conflicts_with = "blocked_only")]
+    ready_only: bool,
+
+    /// Show only blocked issues: not closed, with blocking dependencies (minibeads-specific)
+    #[arg(long)]
+    blocked_only: bool,
+
+    /// With --ready-only/--blocked-only, treat blocks dependencies on issues
+    /// that do not exist as resolved (minibeads-specific)
+    #[arg(long)]
+    ignore_missing_blockers: bool,
+
+    /// Also include issues moved to .beads/archive/ by `archive` (minibeads-specific)
+    #[arg(long)]
+    include_archived: bool,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Initialize beads in current directory
@@ -208,9 +349,18 @@ enum Commands {
         /// Skip git hook setup (ignored for upstream bd compatibility)
         #[arg(long = "skip-hooks", hide = true)]
         skip_hooks: bool,
+
+        /// Initialize here even if a parent directory already has a beads
+        /// database (minibeads-specific)
+        #[arg(long)]
+        here: bool,
     },
 
     /// Create a new issue
+    ///
+    /// The new ID is always reported on a line of its own, `Created issue: <id>`:
+    /// on stdout, or on stderr with --json so stdout stays pure JSON. Scripts
+    /// and agents can rely on that line to chain further commands.
     Create {
         /// Issue title (positional argument, or use --title flag)
         title: Option<String>,
@@ -219,18 +369,15 @@ enum Commands {
         #[arg(long = "title", allow_hyphen_values = true)]
         title_flag: Option<String>,
 
-        /// Priority (0-4, 0=highest)
-        #[arg(short, long, default_value = "2")]
-        priority: i32,
+        /// Priority (0-4, 0=highest; default: mb-default-priority from
+        /// config-minibeads.yaml, else 2)
+        #[arg(short, long)]
+        priority: Option<i32>,
 
-        /// Issue type: bug, feature, task, epic, chore
-        #[arg(
-            short = 't',
-            long = "issue-type",
-            visible_alias = "type",
-            default_value = "task"
-        )]
-        issue_type: IssueType,
+        /// Issue type: bug, feature, task, epic, chore (default: mb-default-type
+        /// from config-minibeads.yaml, else task)
+        #[arg(short = 't', long = "issue-type", visible_alias = "type")]
+        issue_type: Option<IssueType>,
 
         /// Description
         #[arg(short, long, default_value = "", allow_hyphen_values = true)]
@@ -264,20 +411,55 @@ enum Commands {
         #[arg(long)]
         id: Option<String>,
 
+        /// Replace an existing issue when --id names one (minibeads-specific)
+        #[arg(long, requires = "id")]
+        overwrite: bool,
+
+        /// Backdate the issue's creation time, e.g. 2024-01-01T00:00:00Z, for
+        /// imports and backfills (minibeads-specific)
+        #[arg(long, value_name = "TIMESTAMP")]
+        created_at: Option<String>,
+
+        /// Read the issue body from stdin: plain text becomes the description,
+        /// or use "# Description", "# Design", "# Acceptance Criteria" and
+        /// "# Notes" sections (minibeads-specific)
+        #[arg(long, conflicts_with_all = ["description", "design", "acceptance", "description_file"])]
+        stdin: bool,
+
+        /// Read the issue body from a file, parsed like --stdin (minibeads-specific)
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["description", "design", "acceptance"])]
+        description_file: Option<PathBuf>,
+
         /// Dependencies (comma-separated). Formats:
         /// Simple: "bd-1,bd-2" (defaults to 'blocks')
         /// Advanced: "blocks:bd-1,related:bd-2,discovered-from:bd-3"
         #[arg(long)]
         deps: Option<String>,
 
+        /// Dependency as ID[:TYPE], repeatable (e.g. --dep bd-1:blocks --dep bd-2:related).
+        /// TYPE defaults to 'blocks'. Combines with --deps (minibeads-specific)
+        #[arg(long = "dep", value_name = "ID[:TYPE]")]
+        dep: Vec<String>,
+
+        /// Fail instead of warning when a dependency target does not exist,
+        /// overriding mb-strict-deps (minibeads-specific)
+        #[arg(long)]
+        strict_deps: bool,
+
         /// Parent issue ID for hierarchical child (e.g., 'bd-a3f8e9')
         #[arg(long)]
         parent: Option<String>,
 
-        /// Force creation even if prefix doesn't match database prefix
+        /// Force creation even if prefix doesn't match database prefix, or when
+        /// --check-dupes finds similar open issues
         #[arg(long)]
         force: bool,
 
+        /// Refuse to create the issue when an open issue has a similar title
+        /// (word overlap), unless --force is given (minibeads-specific)
+        #[arg(long)]
+        check_dupes: bool,
+
         /// Create multiple issues from markdown file
         #[arg(short = 'f', long)]
         file: Option<PathBuf>,
@@ -289,53 +471,58 @@ enum Commands {
         /// Suppress non-JSON output (upstream bd compatibility)
         #[arg(long, hide = true)]
         silent: bool,
+
+        /// Validate the inputs and show the issue that would be created,
+        /// including its ID, without writing anything (minibeads-specific)
+        #[arg(long, conflicts_with = "file")]
+        dry_run: bool,
     },
 
     /// List issues
     List {
-        /// Filter by status: open, in_progress, blocked, closed
-        #[arg(short = 's', long)]
-        status: Option<String>,
-
-        /// Filter by priority (repeatable and/or comma-separated, e.g. "-p 0 -p 1" or "-p 0,1")
-        #[arg(short = 'p', long)]
-        priority: Vec<String>,
-
-        /// Filter by type: bug, feature, task, epic, chore
-        #[arg(long)]
-        r#type: Option<IssueType>,
-
-        /// Filter by assignee
-        #[arg(long)]
-        assignee: Option<String>,
+        #[command(flatten)]
+        filters: FilterArgs,
 
-        /// Filter by labels (must have ALL specified labels)
-        #[arg(short = 'l', long = "label")]
-        labels: Vec<String>,
-
-        /// Show only issues linked to GitHub Issues (minibeads-specific)
+        /// Maximum number of issues to return
         #[arg(long)]
-        github: bool,
+        limit: Option<usize>,
 
-        /// Filter by specific issue IDs (comma-separated)
-        #[arg(long)]
-        id: Option<String>,
+        /// Sort by 'id' (the default order) or 'updated' (least recently
+        /// updated first) (minibeads-specific)
+        #[arg(long, default_value = "id")]
+        sort: ListSort,
 
-        /// Filter by title substring (case-insensitive)
+        /// Reverse the sort order (minibeads-specific)
         #[arg(long)]
-        title: Option<String>,
+        reverse: bool,
 
-        /// Filter to direct children of a parent issue
+        /// Group issues by priority with headers
         #[arg(long)]
-        parent: Option<String>,
+        group_priority: bool,
 
-        /// Maximum number of issues to return
-        #[arg(long)]
-        limit: Option<usize>,
+        /// Print an aligned table (ID, STATUS, PRI, TYPE, ASSIGNEE, TITLE) with titles
+        /// truncated to the terminal width (minibeads-specific)
+        #[arg(long, conflicts_with = "group_priority")]
+        table: bool,
 
-        /// Group issues by priority with headers
+        /// Print only issue IDs, one per line, for piping into other commands
+        /// (a JSON string array with --json) (minibeads-specific)
         #[arg(long)]
-        group_priority: bool,
+        ids_only: bool,
+
+        /// Print each issue through a format string, e.g. '{id}\t{priority}\t{title}'.
+        /// Placeholders: id, title, status, priority, type, assignee, labels,
+        /// depends_on, external_ref, description, design, notes,
+        /// acceptance_criteria, created_at, updated_at, closed_at. '\t' and '\n'
+        /// are tab and newline; '{{' and '}}' are literal braces (minibeads-specific)
+        #[arg(long, conflicts_with_all = ["group_priority", "table", "ids_only"])]
+        template: Option<String>,
+
+        /// Print issues as 'json' (a pretty-printed array, like --json), 'jsonl'
+        /// (one compact issue per line), or 'ndjson-stream' (JSONL flushed after
+        /// every line, for live consumers) (minibeads-specific)
+        #[arg(long, conflicts_with_all = ["group_priority", "table", "ids_only", "template"])]
+        format: Option<ExportFormat>,
 
         /// Include infrastructure issues (accepted for upstream bd compatibility)
         #[arg(long = "include-infra", hide = true)]
@@ -350,6 +537,35 @@ enum Commands {
     Show {
         /// Issue IDs (supports shorthand: "14" expands to "prefix-14")
         issue_ids: Vec<String>,
+
+        /// Print one summary line per issue, as in `list` (minibeads-specific)
+        #[arg(long)]
+        oneline: bool,
+
+        /// Also look for the issues in .beads/archive/ (minibeads-specific)
+        #[arg(long)]
+        include_archived: bool,
+
+        /// Print the issue's stored markdown file as-is (minibeads-specific)
+        #[arg(long, conflicts_with = "oneline")]
+        markdown: bool,
+
+        /// With --markdown, re-render the file from the parsed issue instead
+        /// of printing it verbatim (minibeads-specific)
+        #[arg(long, requires = "markdown")]
+        normalized: bool,
+
+        /// Explain whether each issue is ready to work on and, if not, which
+        /// blocking dependencies (and their statuses) hold it up; adds a
+        /// "readiness" object with --json (minibeads-specific)
+        #[arg(long, conflicts_with_all = ["markdown", "oneline"])]
+        why: bool,
+
+        /// List the git commits that touched each issue's file (`git log
+        /// --follow`); adds a "history" array with --json, null outside a git
+        /// repository (minibeads-specific)
+        #[arg(long, conflicts_with_all = ["markdown", "oneline"])]
+        history: bool,
     },
 
     /// List direct child issues
@@ -383,7 +599,7 @@ enum Commands {
         #[arg(long, allow_hyphen_values = true)]
         title: Option<String>,
 
-        /// New description
+        /// New description (an empty string clears it)
         #[arg(short, long, allow_hyphen_values = true)]
         description: Option<String>,
 
@@ -395,7 +611,7 @@ enum Commands {
             long,
             allow_hyphen_values = true,
             requires = "replace",
-            conflicts_with_all = ["description", "title", "design", "acceptance", "notes", "claim"]
+            conflicts_with_all = ["description", "title", "design", "acceptance", "notes", "append_description", "append_notes", "claim"]
         )]
         search: Option<String>,
 
@@ -422,26 +638,42 @@ enum Commands {
         #[arg(
             long,
             allow_hyphen_values = true,
-            conflicts_with_all = ["search", "replace", "replace_all", "description", "title", "design", "acceptance", "notes", "claim"]
+            conflicts_with_all = ["search", "replace", "replace_all", "description", "title", "design", "acceptance", "notes", "append_description", "append_notes", "claim"]
         )]
         append: Option<String>,
 
-        /// New design notes
+        /// New design notes (an empty string clears them)
         #[arg(long, allow_hyphen_values = true)]
         design: Option<String>,
 
-        /// New acceptance criteria
+        /// New acceptance criteria (an empty string clears them)
         #[arg(long, allow_hyphen_values = true)]
         acceptance: Option<String>,
 
-        /// Additional notes
+        /// Additional notes (an empty string clears them)
         #[arg(long, allow_hyphen_values = true)]
         notes: Option<String>,
 
-        /// New external reference
+        /// New external reference (an empty string clears it)
         #[arg(long)]
         external_ref: Option<String>,
 
+        /// Append a line to the description, keeping what is already there
+        /// (minibeads-specific)
+        #[arg(long, allow_hyphen_values = true, conflicts_with = "description")]
+        append_description: Option<String>,
+
+        /// Append a line to the notes, e.g. to journal progress without
+        /// clobbering earlier entries (minibeads-specific)
+        #[arg(long, allow_hyphen_values = true, conflicts_with = "notes")]
+        append_notes: Option<String>,
+
+        /// Prefix the --append-notes entry with the current time and actor
+        /// (see `mb whoami`), as a list item:
+        /// `- 2025-01-02T10:00Z (alice): text` (minibeads-specific)
+        #[arg(long, requires = "append_notes")]
+        timestamped: bool,
+
         /// Add a label. May be provided multiple times.
         #[arg(long = "add-label")]
         add_label: Vec<String>,
@@ -515,6 +747,11 @@ enum Commands {
         /// Reason for closing
         #[arg(short, long, default_value = "Completed", allow_hyphen_values = true)]
         reason: String,
+
+        /// How the issue was resolved: done, wontfix, duplicate, obsolete;
+        /// stored in the frontmatter (minibeads-specific)
+        #[arg(long)]
+        resolution: Option<Resolution>,
     },
 
     /// Reopen closed issues
@@ -527,6 +764,44 @@ enum Commands {
         reason: Option<String>,
     },
 
+    /// Start work on open or deferred issues: moves them to in_progress
+    /// (minibeads-specific)
+    Start {
+        /// Issue IDs to start (supports shorthand: "14" expands to "prefix-14")
+        issue_ids: Vec<String>,
+    },
+
+    /// Mark open or in_progress issues as blocked (minibeads-specific)
+    Block {
+        /// Issue IDs to block (supports shorthand: "14" expands to "prefix-14")
+        issue_ids: Vec<String>,
+
+        /// Why the issues are blocked, appended to their notes
+        #[arg(short, long, allow_hyphen_values = true)]
+        reason: Option<String>,
+    },
+
+    /// Clear the blocked status: back to in_progress if the issue is
+    /// assigned, else open (minibeads-specific)
+    Unblock {
+        /// Issue IDs to unblock (supports shorthand: "14" expands to "prefix-14")
+        issue_ids: Vec<String>,
+    },
+
+    /// Close an issue as a duplicate of another: sets resolution=duplicate
+    /// and adds a related dependency on the canonical issue (minibeads-specific)
+    LinkDuplicate {
+        /// The duplicate issue, which gets closed
+        dup_id: String,
+
+        /// The issue to keep
+        canonical_id: String,
+
+        /// Also add labels only the duplicate has to the canonical issue
+        #[arg(long)]
+        copy_labels: bool,
+    },
+
     /// Rename an issue ID (minibeads-specific)
     MbRename {
         /// Current issue ID
@@ -562,12 +837,106 @@ enum Commands {
         force: bool,
     },
 
+    /// Move issues into another beads database (minibeads-specific)
+    MoveTo {
+        /// Target beads directory, or a directory containing .minibeads/.beads
+        target: PathBuf,
+
+        /// Issue IDs to move (supports shorthand: "14" expands to "prefix-14")
+        #[arg(required = true)]
+        issue_ids: Vec<String>,
+
+        /// Keep the issue IDs instead of numbering them in the target
+        #[arg(long)]
+        keep_ids: bool,
+
+        /// Delete the issues from this database once they are copied
+        #[arg(long)]
+        delete_source: bool,
+
+        /// Preview changes without applying them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Manage dependencies
     Dep {
         #[command(subcommand)]
         command: DepCommands,
     },
 
+    /// Print the path of an issue's markdown file (minibeads-specific)
+    Path {
+        /// Issue ID (supports shorthand: "14" expands to "prefix-14")
+        issue_id: String,
+    },
+
+    /// Create a new open issue from an existing one's title, description,
+    /// design, acceptance criteria, type, priority, and labels (minibeads-specific)
+    Copy {
+        /// Source issue ID (supports shorthand: "14" expands to "prefix-14")
+        source_id: String,
+    },
+
+    /// Show the longest chain of open blockers leading into an issue (minibeads-specific)
+    CriticalPath {
+        /// Issue ID (supports shorthand: "14" expands to "prefix-14")
+        issue_id: String,
+    },
+
+    /// Print the dependency graph of the selected issues as Graphviz DOT or a
+    /// Mermaid flowchart (minibeads-specific)
+    Graph {
+        #[command(flatten)]
+        filters: FilterArgs,
+
+        /// Output format: 'dot' (default) or 'mermaid' (a `flowchart TD` to
+        /// paste into GitHub/GitLab Markdown)
+        #[arg(long, default_value = "dot")]
+        format: GraphFormat,
+    },
+
+    /// Move closed issues into .beads/archive/ so they drop out of the active set (minibeads-specific)
+    Archive {
+        /// Archive issues closed before this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long, value_name = "DATE")]
+        closed_before: String,
+
+        /// Show which issues would be archived without moving them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Move archived issues back into the active set (minibeads-specific)
+    Restore {
+        /// Issue IDs to restore (supports shorthand: "14" expands to "prefix-14")
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        issue_ids: Vec<String>,
+
+        /// Restore every archived issue
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Add a user to an issue's watchers, the people a notifier should ping
+    /// about changes (minibeads-specific)
+    Watch {
+        /// Issue ID (supports shorthand: "14" expands to "prefix-14")
+        issue_id: String,
+
+        /// User to add
+        user: String,
+    },
+
+    /// Remove a user from an issue's watchers (minibeads-specific)
+    Unwatch {
+        /// Issue ID (supports shorthand: "14" expands to "prefix-14")
+        issue_id: String,
+
+        /// User to remove
+        user: String,
+    },
+
     /// Manage issue labels
     Label {
         #[command(subcommand)]
@@ -615,37 +984,182 @@ enum Commands {
         command: GithubCommands,
     },
 
+    /// Print the actor recorded on comments and timestamped notes: --actor,
+    /// then mb-default-actor in config-minibeads.yaml, then $USER, then "user"
+    /// (minibeads-specific)
+    Whoami {
+        /// Save this name as mb-default-actor for the database
+        #[arg(long, value_name = "NAME")]
+        set: Option<String>,
+    },
+
     /// Get statistics
     Stats,
 
+    /// Report issues whose markdown would change on a no-op rewrite, i.e. files
+    /// that do not round-trip through the parser cleanly (minibeads-specific)
+    VerifyRoundtrip,
+
+    /// Report issues whose titles match after trimming and lowercasing, often
+    /// work that was accidentally filed twice (minibeads-specific)
+    Dupes,
+
+    /// Check the database for likely problems: duplicate titles and
+    /// dependency cycles
+    Doctor,
+
+    /// Rewrite every issue file in canonical form without touching updated_at;
+    /// `verify-roundtrip` lists the files this would change (minibeads-specific)
+    Normalize {
+        /// Show which issues would be rewritten without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Copy the database into a new directory for consistent reads across
+    /// several commands; point them at it with --beads-dir (minibeads-specific)
+    Snapshot {
+        /// Directory to create (must not exist or be empty)
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+
+    /// Remove empty or unparseable issue files and temp files orphaned by
+    /// interrupted writes; parseable issues are never touched (minibeads-specific)
+    Gc {
+        /// List what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// List the most recently updated issues, newest first, with how long ago
+    /// each changed (minibeads-specific)
+    Recent {
+        /// Maximum number of issues to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Show who last changed a frontmatter field of an issue and when, from
+    /// `git blame` on its file (minibeads-specific)
+    Blame {
+        /// Issue ID (supports shorthand: "14" expands to "prefix-14")
+        issue_id: String,
+
+        /// Frontmatter field to look up, e.g. status, priority or assignee
+        #[arg(long, default_value = "status")]
+        field: String,
+    },
+
+    /// Show the number of open issues at the end of each recent day (minibeads-specific)
+    Burndown {
+        /// Number of days to show, ending today
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+    },
+
+    /// Write a Markdown status report: stats, ready, blocked, and recently closed issues (minibeads-specific)
+    Report {
+        /// Start of the "recently closed" window (YYYY-MM-DD or RFC 3339; default: 7 days ago)
+        #[arg(long, value_name = "DATE")]
+        since: Option<String>,
+
+        /// Write the report to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
     /// Get blocked issues
-    Blocked,
+    Blocked {
+        /// Treat blocks dependencies on issues that do not exist as resolved
+        /// (minibeads-specific)
+        #[arg(long)]
+        ignore_missing_blockers: bool,
+
+        /// Print only issue IDs, one per line, for piping into other commands
+        /// (a JSON string array with --json) (minibeads-specific)
+        #[arg(long)]
+        ids_only: bool,
+    },
 
     /// Export issues to JSONL format
+    #[command(group(
+        ArgGroup::new("native_schema")
+            .args(["mb_native", "mb_output_default"])
+            .multiple(true)
+    ))]
     Export {
         /// Output file path (defaults to stdout)
         #[arg(short = 'o', long)]
         output: Option<PathBuf>,
 
-        /// Use default file output (storage/issues.jsonl) instead of stdout
+        /// Use default file output (storage/issues.jsonl) instead of stdout.
+        /// That file is the sync mirror, so it is always written in
+        /// minibeads' own schema, as with --mb-native
         #[arg(long = "mb-output-default")]
         mb_output_default: bool,
 
-        /// Filter by status: open, in_progress, blocked, closed
-        #[arg(long)]
-        status: Option<Status>,
+        #[command(flatten)]
+        filters: FilterArgs,
 
-        /// Filter by priority
+        /// Write minibeads' own JSONL schema ({id, type} dependencies,
+        /// dependents, claim fields) instead of upstream bd's (minibeads-specific)
         #[arg(long)]
-        priority: Option<i32>,
+        mb_native: bool,
+
+        /// Write a schema header line ({"_schema":"minibeads-jsonl","version":N}) first (minibeads-specific)
+        #[arg(long, requires = "native_schema")]
+        with_header: bool,
+
+        /// Output format: 'jsonl' (default, one issue per line), 'json' (pretty-printed array),
+        /// or 'ndjson-stream' (JSONL on stdout, flushed after every line) (minibeads-specific)
+        #[arg(long, default_value = "jsonl")]
+        format: ExportFormat,
+
+        /// Export empty 'dependents' lists instead of computing them, skipping a full
+        /// pass over the issues on very large databases (minibeads-specific)
+        #[arg(long, requires = "native_schema")]
+        no_dependents: bool,
+
+        /// Export only this issue and everything it transitively depends on,
+        /// a self-contained slice for another database; other filters narrow
+        /// it further (supports shorthand: "14" expands to "prefix-14")
+        #[arg(long, value_name = "ID")]
+        closure: Option<String>,
+
+        /// With --closure, also include everything that transitively depends
+        /// on the issue
+        #[arg(long, requires = "closure")]
+        include_dependents: bool,
+
+        /// Write the dependency graph of the selected issues as one JSON
+        /// object, {nodes: [{id, title, status}], edges: [{from, to, type}]},
+        /// for graph front-ends such as d3 or cytoscape (minibeads-specific)
+        #[arg(long, conflicts_with_all = ["mb_native", "format", "mb_output_default"])]
+        pretty_dependents_graph: bool,
+    },
 
-        /// Filter by type: bug, feature, task, epic, chore
-        #[arg(long)]
-        r#type: Option<IssueType>,
+    /// Import issues from a JSONL file (upstream bd's schema or minibeads' own)
+    Import {
+        /// JSONL file to read
+        #[arg(short, long)]
+        input: PathBuf,
 
-        /// Filter by assignee
+        /// What to do with issues that already exist: 'skip' (default) keeps
+        /// them, 'overwrite' replaces them, 'merge' keeps whichever is newer
+        /// by the file's mtime and the imported updated_at (minibeads-specific)
+        #[arg(long, default_value = "skip")]
+        mode: ImportMode,
+    },
+
+    /// Show how an issue's markdown file differs from its JSONL entry (minibeads-specific)
+    Diff {
+        /// Issue ID (supports shorthand: "14" expands to "prefix-14")
+        issue_id: String,
+
+        /// Path to JSONL file (defaults to storage/issues.jsonl)
         #[arg(long)]
-        assignee: Option<String>,
+        jsonl: Option<PathBuf>,
     },
 
     /// Bidirectional sync between markdown and JSONL formats
@@ -661,6 +1175,22 @@ enum Commands {
         /// Direction: 'both' (default), 'to-jsonl', or 'to-markdown'
         #[arg(long, default_value = "both")]
         direction: String,
+
+        /// Skip copying issues.jsonl to issues.jsonl.bak before rewriting it
+        #[arg(long)]
+        no_backup: bool,
+
+        /// Resolve conflicts (same timestamp, different content): 'skip'
+        /// (default) leaves both sides alone, 'markdown' or 'jsonl' lets that
+        /// side win (minibeads-specific)
+        #[arg(long, default_value = "skip", value_name = "SIDE")]
+        prefer: sync::ConflictPolicy,
+
+        /// How far apart (ms) a markdown mtime and a JSONL updated_at may be
+        /// and still count as the same time; overrides mb-sync-tolerance-ms
+        /// (default: 1000) (minibeads-specific)
+        #[arg(long, value_name = "MS")]
+        tolerance_ms: Option<u64>,
     },
 
     /// Find ready work (issues with no blockers)
@@ -669,13 +1199,19 @@ enum Commands {
         #[arg(short = 'a', long)]
         assignee: Option<String>,
 
+        /// Show only issues with no assignee: work nobody has picked up
+        /// (minibeads-specific)
+        #[arg(long, conflicts_with = "assignee")]
+        unassigned: bool,
+
         /// Filter by priority (repeatable and/or comma-separated, e.g. "-p 0 -p 1" or "-p 0,1")
         #[arg(short = 'p', long)]
         priority: Vec<String>,
 
-        /// Filter by type: bug, feature, task, epic, chore
-        #[arg(long)]
-        r#type: Option<IssueType>,
+        /// Filter by type: bug, feature, task, epic, chore (repeatable and/or
+        /// comma-separated, e.g. "--type bug --type feature" or "--type bug,feature")
+        #[arg(long, value_delimiter = ',')]
+        r#type: Vec<IssueType>,
 
         /// Filter by labels (must have ALL specified labels)
         #[arg(short = 'l', long = "label")]
@@ -701,6 +1237,12 @@ enum Commands {
         #[arg(short = 'n', long)]
         limit: Option<usize>,
 
+        /// WIP limit for --assignee: return ready work only while they have
+        /// fewer than N in_progress issues, and no more than would bring
+        /// them to N (minibeads-specific)
+        #[arg(long, value_name = "N", requires = "assignee")]
+        capacity: Option<usize>,
+
         /// Group issues by priority with headers
         #[arg(long)]
         group_priority: bool,
@@ -708,6 +1250,16 @@ enum Commands {
         /// Sort policy: priority (by priority), oldest (by creation date), hybrid (priority + age), random (shuffled)
         #[arg(short = 's', long, default_value = "hybrid")]
         sort: String,
+
+        /// Treat blocks dependencies on issues that do not exist as resolved,
+        /// so a dangling dependency does not hide an issue (minibeads-specific)
+        #[arg(long)]
+        ignore_missing_blockers: bool,
+
+        /// Print only issue IDs, one per line, for piping into other commands
+        /// (a JSON string array with --json) (minibeads-specific)
+        #[arg(long)]
+        ids_only: bool,
     },
 
     /// Show quickstart guide
@@ -753,12 +1305,27 @@ enum DepCommands {
         /// Issue that has the dependency
         issue_id: String,
 
-        /// Issue that issue_id depends on
+        /// Issue that issue_id depends on; a comma-separated list (e.g.
+        /// "bd-2,bd-3") adds each edge under one lock and reports every target
         depends_on_id: String,
 
         /// Dependency type: blocks, related, parent-child, discovered-from
         #[arg(short = 't', long, default_value = "blocks")]
         r#type: DependencyType,
+
+        /// Fail instead of warning when depends_on_id does not exist,
+        /// overriding mb-strict-deps (minibeads-specific)
+        #[arg(long)]
+        strict_deps: bool,
+
+        /// Change the type of an existing dependency instead of failing
+        #[arg(long)]
+        force: bool,
+
+        /// Also add the reverse edge, so both issues list each other. Only
+        /// valid with --type related (minibeads-specific)
+        #[arg(long)]
+        bidirectional: bool,
     },
 
     /// Remove a dependency
@@ -770,6 +1337,18 @@ enum DepCommands {
         depends_on_id: String,
     },
 
+    /// Change the type of an existing dependency (minibeads-specific)
+    Type {
+        /// Issue that has the dependency
+        issue_id: String,
+
+        /// Issue that issue_id depends on
+        depends_on_id: String,
+
+        /// New dependency type: blocks, related, parent-child, discovered-from
+        new_type: DependencyType,
+    },
+
     /// List dependencies or dependents
     List {
         /// Issue IDs to inspect
@@ -793,9 +1372,15 @@ enum DepCommands {
         #[arg(short = 'd', long, default_value = "50")]
         max_depth: usize,
 
-        /// Show all paths to nodes (no deduplication for diamond dependencies)
+        /// Accepted for upstream bd compatibility: diamond dependencies are
+        /// always shown under every path, and cycles are always cut
         #[arg(long)]
         show_all_paths: bool,
+
+        /// Show at most N dependencies per issue, followed by "... and M more",
+        /// to keep trees of hub issues readable (minibeads-specific)
+        #[arg(long, value_name = "N")]
+        max_children: Option<usize>,
     },
 
     /// Detect dependency cycles
@@ -986,6 +1571,42 @@ struct GithubLinkView {
     github_url: String,
 }
 
+/// Print one changed field for `mb diff`: a unified line diff for multi-line
+/// text, otherwise the two values side by side.
+fn print_field_diff(field: &str, diff: &sync::FieldDiff) {
+    match (&diff.markdown, &diff.jsonl) {
+        (serde_json::Value::String(md), serde_json::Value::String(jsonl))
+            if md.contains('\n') || jsonl.contains('\n') =>
+        {
+            println!("\n{}:", field);
+            let text_diff = similar::TextDiff::from_lines(md.as_str(), jsonl.as_str());
+            print!(
+                "{}",
+                text_diff
+                    .unified_diff()
+                    .context_radius(2)
+                    .missing_newline_hint(false)
+                    .header("markdown", "jsonl")
+            );
+        }
+        (md, jsonl) => {
+            println!("\n{}:", field);
+            println!("  markdown: {}", md);
+            println!("  jsonl:    {}", jsonl);
+        }
+    }
+}
+
+/// Build metadata reported by `mb version --json`.
+#[derive(serde::Serialize)]
+struct VersionInfo {
+    version: &'static str,
+    git_commit: Option<&'static str>,
+    git_dirty: Option<bool>,
+    build_date: &'static str,
+    jsonl_schema_version: u32,
+}
+
 #[derive(serde::Serialize)]
 struct ShowCommentView<'a> {
     #[serde(flatten)]
@@ -1129,13 +1750,6 @@ fn print_github_import_report(report: &github::GithubImportReport, quiet: bool,
     print_github_import_summary(report);
 }
 
-fn should_color_stdout() -> bool {
-    std::io::stdout().is_terminal()
-        && env::var_os("NO_COLOR").is_none()
-        && env::var("CLICOLOR").map(|v| v != "0").unwrap_or(true)
-        && env::var("TERM").map(|v| v != "dumb").unwrap_or(true)
-}
-
 fn print_issue_show(issue: &Issue, comments: &[Comment], color: bool) -> Result<()> {
     print_issue_metadata(issue, color);
     let markdown = issue_show_markdown(issue, comments);
@@ -1150,7 +1764,7 @@ fn print_issue_show(issue: &Issue, comments: &[Comment], color: bool) -> Result<
     print!(
         "{}",
         if color {
-            colorize_markdown_fallback(&markdown)
+            style::markdown_fallback(&markdown)
         } else {
             markdown
         }
@@ -1161,53 +1775,133 @@ fn print_issue_show(issue: &Issue, comments: &[Comment], color: bool) -> Result<
 fn print_issue_metadata(issue: &Issue, color: bool) {
     println!(
         "{} {}",
-        style_label("ID:", color),
-        style_value(&issue.id, color)
+        style::label("ID:", color),
+        style::value(&issue.id, color)
+    );
+    println!(
+        "{} {}",
+        style::label("Title:", color),
+        style::title(&issue.title, color)
     );
     println!(
         "{} {}",
-        style_label("Title:", color),
-        style_title(&issue.title, color)
+        style::label("Status:", color),
+        style::status(issue.status.as_str(), color)
     );
     println!(
         "{} {}",
-        style_label("Status:", color),
-        style_status(issue.status.as_str(), color)
+        style::label("Priority:", color),
+        style::priority(issue.priority, color)
     );
-    println!("{} {}", style_label("Priority:", color), issue.priority);
-    println!("{} {}", style_label("Type:", color), issue.issue_type);
+    println!("{} {}", style::label("Type:", color), issue.issue_type);
     if let Some(external_ref) = &issue.external_ref {
         println!(
             "{} {}",
-            style_label("External ref:", color),
-            style_value(external_ref, color)
+            style::label("External ref:", color),
+            style::value(external_ref, color)
         );
     }
     if !issue.assignee.is_empty() {
-        println!("{} {}", style_label("Assignee:", color), issue.assignee);
+        println!("{} {}", style::label("Assignee:", color), issue.assignee);
     }
     if !issue.labels.is_empty() {
         println!(
             "{} {}",
-            style_label("Labels:", color),
+            style::label("Labels:", color),
             issue.labels.join(", ")
         );
     }
-    if let Some(until) = issue.claimed_until {
-        let state = if issue.is_actively_claimed(chrono::Utc::now()) {
-            "active"
+    if !issue.watchers.is_empty() {
+        println!(
+            "{} {}",
+            style::label("Watchers:", color),
+            issue.watchers.join(", ")
+        );
+    }
+    if let Some(until) = issue.claimed_until {
+        let state = if issue.is_actively_claimed(chrono::Utc::now()) {
+            "active"
         } else {
             "STALE"
         };
         println!(
             "{} {} ({})",
-            style_label("Claimed until:", color),
+            style::label("Claimed until:", color),
             until.to_rfc3339(),
             state
         );
     }
 }
 
+/// The `show --why` explanation of a [`ReadinessReport`]
+fn print_readiness(report: &ReadinessReport, color: bool) {
+    let label = style::label("Readiness:", color);
+    match report {
+        ReadinessReport::Ready => println!("{} ready to work on", label),
+        ReadinessReport::Closed => println!("{} closed", label),
+        ReadinessReport::NotOpen { status } => println!(
+            "{} not blocked, but not in `ready` while {}",
+            label,
+            style::status(status.as_str(), color)
+        ),
+        ReadinessReport::Blocked { by } => {
+            println!("{} blocked by {} issue(s):", label, by.len());
+            for blocker in by {
+                let status = match blocker.status {
+                    Some(status) => style::status(status.as_str(), color),
+                    None => "missing".to_string(),
+                };
+                println!("  {} [{}]", style::id(&blocker.id, color), status);
+            }
+        }
+    }
+}
+
+/// The `show --history` list of commits, or why there is none
+fn print_history(history: Option<&[HistoryEntry]>, color: bool) {
+    let label = style::label("History:", color);
+    match history {
+        None => println!("{} not available (not in a git repository)", label),
+        Some([]) => println!("{} no commits touch this issue's file yet", label),
+        Some(entries) => {
+            println!("{}", label);
+            for entry in entries {
+                println!(
+                    "  {} {} {} ({})",
+                    style::id(&entry.commit, color),
+                    entry.date,
+                    entry.summary,
+                    entry.author
+                );
+            }
+        }
+    }
+}
+
+/// The `blame` report: the field's value and the commit that last set it
+fn print_field_blame(issue_id: &str, blame: Option<&FieldBlame>, color: bool) {
+    let Some(blame) = blame else {
+        println!("Not available: the database is not in a git repository");
+        return;
+    };
+    println!(
+        "{} {}: {}",
+        style::id(issue_id, color),
+        blame.field,
+        blame.value
+    );
+    match &blame.commit {
+        Some(entry) => println!(
+            "  last changed by {} on {} in {} {}",
+            entry.author,
+            entry.date,
+            style::id(&entry.commit, color),
+            entry.summary
+        ),
+        None => println!("  changed in uncommitted edits"),
+    }
+}
+
 fn issue_show_markdown(issue: &Issue, comments: &[Comment]) -> String {
     let mut out = String::new();
     push_markdown_section(
@@ -1346,60 +2040,6 @@ fn print_markdown_with_external_highlighter(markdown: &str) -> Result<bool> {
     Ok(false)
 }
 
-fn colorize_markdown_fallback(markdown: &str) -> String {
-    markdown
-        .lines()
-        .map(|line| {
-            if line.chars().all(|c| c == '=') && !line.is_empty() {
-                format!("\x1b[36;1m{}\x1b[0m", line)
-            } else if line.starts_with("### ") {
-                format!("\x1b[35;1m{}\x1b[0m", line)
-            } else {
-                line.to_string()
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("\n")
-        + "\n"
-}
-
-fn style_label(text: &str, color: bool) -> String {
-    if color {
-        format!("\x1b[36;1m{}\x1b[0m", text)
-    } else {
-        text.to_string()
-    }
-}
-
-fn style_title(text: &str, color: bool) -> String {
-    if color {
-        format!("\x1b[1m{}\x1b[0m", text)
-    } else {
-        text.to_string()
-    }
-}
-
-fn style_value(text: &str, color: bool) -> String {
-    if color {
-        format!("\x1b[34m{}\x1b[0m", text)
-    } else {
-        text.to_string()
-    }
-}
-
-fn style_status(text: &str, color: bool) -> String {
-    if !color {
-        return text.to_string();
-    }
-    let code = match text {
-        "open" => "32",
-        "in_progress" => "33",
-        "closed" => "90",
-        _ => "37",
-    };
-    format!("\x1b[{}m{}\x1b[0m", code, text)
-}
-
 /// Print a dependency tree in a visual format
 fn print_dependency_tree(node: &types::TreeNode, depth: usize, prefix: &str, is_last: bool) {
     // Print the current node
@@ -1445,32 +2085,48 @@ fn print_dependency_tree(node: &types::TreeNode, depth: usize, prefix: &str, is_
     };
 
     for (i, child) in node.children.iter().enumerate() {
-        let is_last_child = i == node.children.len() - 1;
+        let is_last_child = i == node.children.len() - 1 && node.omitted_children == 0;
         print_dependency_tree(child, depth + 1, &child_prefix, is_last_child);
     }
+    if node.omitted_children > 0 {
+        println!("{}└── ... and {} more", child_prefix, node.omitted_children);
+    }
 }
 
+/// Exit codes are documented in `error.rs`: 1 generic, 2 not found,
+/// 3 locked, 4 invalid input
 fn main() {
     if let Err(e) = run() {
+        // Let clap print usage errors (and --help/--version) itself, but with
+        // the invalid-input code rather than clap's default of 2
+        if let Some(usage) = e.downcast_ref::<clap::Error>() {
+            let _ = usage.print();
+            let code = if usage.use_stderr() {
+                error::ErrorKind::InvalidInput.exit_code()
+            } else {
+                0
+            };
+            std::process::exit(code);
+        }
         eprintln!("Error: {:#}", e);
-        std::process::exit(1);
+        std::process::exit(error::exit_code(&e));
     }
 }
 
-/// In-memory issue filters shared by `list` and `ready`. The storage layer
-/// handles status/priority/type/assignee; everything here is a post-query
-/// `retain` so both commands filter identically.
-struct IssueFilters<'a> {
-    /// Must have ALL specified labels.
-    labels: &'a [String],
-    /// Only issues linked to a GitHub issue.
-    github: bool,
-    /// Restrict to a comma-separated set of exact issue IDs.
-    id: Option<&'a str>,
-    /// Case-insensitive title substring.
-    title: Option<&'a str>,
-    /// Only direct children of this parent issue.
-    parent: Option<&'a str>,
+/// Parse one `--dep ID[:TYPE]` value; TYPE defaults to `blocks`. Unlike the
+/// legacy `--deps` list, an unknown type is an error rather than a warning.
+fn parse_dep_arg(value: &str) -> Result<(String, DependencyType)> {
+    let (id, dep_type) = match value.split_once(':') {
+        Some((id, type_str)) => (id.trim(), type_str.trim().parse::<DependencyType>()?),
+        None => (value.trim(), DependencyType::Blocks),
+    };
+    if id.is_empty() {
+        return Err(error::invalid_input(format!(
+            "Invalid --dep '{}': missing issue ID",
+            value
+        )));
+    }
+    Ok((id.to_string(), dep_type))
 }
 
 /// Parse priority filters into a flat list, shared by `list` and `ready`. The
@@ -1485,7 +2141,10 @@ fn parse_priority_filters(values: &[String]) -> Result<Option<Vec<i32>>> {
             if part.is_empty() {
                 continue;
             }
-            priorities.push(part.parse::<i32>().context("Invalid priority value")?);
+            let priority = part
+                .parse::<i32>()
+                .map_err(|_| error::invalid_input(format!("Invalid priority value: '{}'", part)))?;
+            priorities.push(priority);
         }
     }
 
@@ -1496,9 +2155,346 @@ fn parse_priority_filters(values: &[String]) -> Result<Option<Vec<i32>>> {
     }
 }
 
+/// Type filters for `list` and `ready`; no `--type` means no filter
+fn type_filter(types: &[IssueType]) -> Option<Vec<IssueType>> {
+    if types.is_empty() {
+        None
+    } else {
+        Some(types.to_vec())
+    }
+}
+
+/// Compact elapsed time for `blocked` and `recent`: whole days, else hours,
+/// else minutes
+fn format_elapsed(elapsed: chrono::Duration) -> String {
+    if elapsed.num_days() > 0 {
+        format!("{}d", elapsed.num_days())
+    } else if elapsed.num_hours() > 0 {
+        format!("{}h", elapsed.num_hours())
+    } else {
+        format!("{}m", elapsed.num_minutes().max(0))
+    }
+}
+
+/// A `list --template` placeholder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TemplateField {
+    Id,
+    Title,
+    Status,
+    Priority,
+    Type,
+    Assignee,
+    Labels,
+    DependsOn,
+    ExternalRef,
+    Description,
+    Design,
+    Notes,
+    AcceptanceCriteria,
+    CreatedAt,
+    UpdatedAt,
+    ClosedAt,
+}
+
+impl TemplateField {
+    /// Every placeholder, in the order `--help` lists them
+    const ALL: [TemplateField; 16] = [
+        TemplateField::Id,
+        TemplateField::Title,
+        TemplateField::Status,
+        TemplateField::Priority,
+        TemplateField::Type,
+        TemplateField::Assignee,
+        TemplateField::Labels,
+        TemplateField::DependsOn,
+        TemplateField::ExternalRef,
+        TemplateField::Description,
+        TemplateField::Design,
+        TemplateField::Notes,
+        TemplateField::AcceptanceCriteria,
+        TemplateField::CreatedAt,
+        TemplateField::UpdatedAt,
+        TemplateField::ClosedAt,
+    ];
+
+    /// The placeholder name, as written between braces
+    fn as_str(self) -> &'static str {
+        match self {
+            TemplateField::Id => "id",
+            TemplateField::Title => "title",
+            TemplateField::Status => "status",
+            TemplateField::Priority => "priority",
+            TemplateField::Type => "type",
+            TemplateField::Assignee => "assignee",
+            TemplateField::Labels => "labels",
+            TemplateField::DependsOn => "depends_on",
+            TemplateField::ExternalRef => "external_ref",
+            TemplateField::Description => "description",
+            TemplateField::Design => "design",
+            TemplateField::Notes => "notes",
+            TemplateField::AcceptanceCriteria => "acceptance_criteria",
+            TemplateField::CreatedAt => "created_at",
+            TemplateField::UpdatedAt => "updated_at",
+            TemplateField::ClosedAt => "closed_at",
+        }
+    }
+}
+
+enum TemplatePart {
+    Literal(String),
+    Field(TemplateField),
+}
+
+/// A parsed `list --template` format string: `{field}` placeholders among
+/// literal text. `{{`/`}}` are literal braces and `\t`, `\n`, `\\` are
+/// unescaped, so shell-quoted templates can hold tabs and newlines.
+struct OutputTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+impl OutputTemplate {
+    /// Tokenize `template`, rejecting unknown placeholders and unbalanced braces
+    fn parse(template: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(error::invalid_input(format!(
+                                    "Unclosed placeholder '{{{}' in template",
+                                    name
+                                )))
+                            }
+                        }
+                    }
+                    let field = TemplateField::ALL
+                        .into_iter()
+                        .find(|field| field.as_str() == name)
+                        .ok_or_else(|| {
+                            let valid: Vec<&str> =
+                                TemplateField::ALL.iter().map(|f| f.as_str()).collect();
+                            error::invalid_input(format!(
+                                "Unknown template placeholder '{{{}}}'. Valid: {}",
+                                name,
+                                valid.join(", ")
+                            ))
+                        })?;
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(TemplatePart::Field(field));
+                }
+                '}' => {
+                    return Err(error::invalid_input(
+                        "Unmatched '}' in template (use '}}' for a literal brace)",
+                    ))
+                }
+                '\\' => match chars.peek() {
+                    Some('t') => {
+                        chars.next();
+                        literal.push('\t');
+                    }
+                    Some('n') => {
+                        chars.next();
+                        literal.push('\n');
+                    }
+                    Some('\\') => {
+                        chars.next();
+                        literal.push('\\');
+                    }
+                    _ => literal.push('\\'),
+                },
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+
+        Ok(Self { parts })
+    }
+
+    /// Substitute `issue`'s fields; unset optional fields render as empty
+    fn render(&self, issue: &Issue) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                TemplatePart::Literal(text) => out.push_str(text),
+                TemplatePart::Field(field) => {
+                    let value = match field {
+                        TemplateField::Id => issue.id.clone(),
+                        TemplateField::Title => issue.title.clone(),
+                        TemplateField::Status => issue.status.to_string(),
+                        TemplateField::Priority => issue.priority.to_string(),
+                        TemplateField::Type => issue.issue_type.to_string(),
+                        TemplateField::Assignee => issue.assignee.clone(),
+                        TemplateField::Labels => issue.labels.join(","),
+                        TemplateField::DependsOn => {
+                            let mut ids: Vec<&str> =
+                                issue.depends_on.keys().map(String::as_str).collect();
+                            ids.sort_unstable();
+                            ids.join(",")
+                        }
+                        TemplateField::ExternalRef => {
+                            issue.external_ref.clone().unwrap_or_default()
+                        }
+                        TemplateField::Description => issue.description.clone(),
+                        TemplateField::Design => issue.design.clone(),
+                        TemplateField::Notes => issue.notes.clone(),
+                        TemplateField::AcceptanceCriteria => issue.acceptance_criteria.clone(),
+                        TemplateField::CreatedAt => issue.created_at.to_rfc3339(),
+                        TemplateField::UpdatedAt => issue.updated_at.to_rfc3339(),
+                        TemplateField::ClosedAt => {
+                            issue.closed_at.map(|t| t.to_rfc3339()).unwrap_or_default()
+                        }
+                    };
+                    out.push_str(&value);
+                }
+            }
+        }
+        out
+    }
+}
+
+/// One-line issue summary used by `list`, `recent` and `show --oneline`
+fn issue_summary_line(issue: &Issue, color: bool) -> String {
+    format!(
+        "{}: {} [{}] (priority: {})",
+        style::id(&issue.id, color),
+        issue.title,
+        style::status(issue.status.as_str(), color),
+        style::priority(issue.priority, color)
+    )
+}
+
+/// Print issues for `list --format`: a pretty-printed JSON array, or one
+/// compact issue per line. `ndjson-stream` flushes stdout after each line
+/// instead of when the buffer fills.
+fn print_issues_in_format(issues: &[Issue], format: ExportFormat) -> Result<()> {
+    if format == ExportFormat::Json {
+        println!("{}", serde_json::to_string_pretty(issues)?);
+        return Ok(());
+    }
+
+    let stdout = std::io::stdout().lock();
+    let mut writer: Box<dyn Write> = if format == ExportFormat::NdjsonStream {
+        Box::new(std::io::LineWriter::new(stdout))
+    } else {
+        Box::new(std::io::BufWriter::new(stdout))
+    };
+    for issue in issues {
+        serde_json::to_writer(&mut writer, issue)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Print issues as an aligned table for `list --table`. Column widths are
+/// computed over the whole result set; the title column is truncated to fit
+/// the terminal when stdout is one.
+fn print_issues_table(issues: &[Issue], color: bool) {
+    let headers = ["ID", "STATUS", "PRI", "TYPE", "ASSIGNEE"];
+    let rows: Vec<[String; 5]> = issues
+        .iter()
+        .map(|issue| {
+            [
+                issue.id.clone(),
+                issue.status.to_string(),
+                format!("P{}", issue.priority),
+                issue.issue_type.to_string(),
+                issue.assignee.clone(),
+            ]
+        })
+        .collect();
+
+    let mut widths = headers.map(|h| h.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    // Room left for the title after the fixed columns and their separators
+    let title_width = terminal_size::terminal_size()
+        .map(|(terminal_size::Width(w), _)| w as usize)
+        .map(|w| w.saturating_sub(widths.iter().map(|w| w + 2).sum()).max(10));
+
+    let pad =
+        |text: &str, width: usize| format!("{}{}", text, " ".repeat(width - text.chars().count()));
+
+    let mut header_line = String::new();
+    for (header, width) in headers.iter().zip(widths) {
+        header_line.push_str(&pad(header, width));
+        header_line.push_str("  ");
+    }
+    header_line.push_str("TITLE");
+    println!("{}", style::label(&header_line, color));
+
+    for (issue, row) in issues.iter().zip(&rows) {
+        let cells = [
+            style::id(&pad(&row[0], widths[0]), color),
+            style::status(issue.status.as_str(), color)
+                + &" ".repeat(widths[1] - row[1].chars().count()),
+            pad(&row[2], widths[2]),
+            pad(&row[3], widths[3]),
+            pad(&row[4], widths[4]),
+        ];
+        let title = match title_width {
+            Some(max) if issue.title.chars().count() > max => {
+                let truncated: String = issue.title.chars().take(max - 1).collect();
+                format!("{}…", truncated)
+            }
+            _ => issue.title.clone(),
+        };
+        println!("{}  {}", cells.join("  "), title);
+    }
+}
+
+/// Print bare issue IDs, one per line, or as a JSON string array. Shared by
+/// `list`, `ready`, and `blocked` (`--ids-only`).
+fn print_ids<'a>(ids: impl Iterator<Item = &'a str>, json: bool) -> Result<()> {
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&ids.collect::<Vec<_>>())?
+        );
+    } else {
+        for id in ids {
+            println!("{}", id);
+        }
+    }
+    Ok(())
+}
+
+/// Number of issue files a rename or migration renames, for its summary line
+fn count_renamed_files(changes: &[Change]) -> usize {
+    changes
+        .iter()
+        .filter(|change| matches!(change, Change::RenameFile { .. }))
+        .count()
+}
+
 /// Print issues grouped by priority with a boxed header per group. Shared by
 /// `list` and `ready` (`--group-priority`).
-fn print_issues_grouped_by_priority(issues: &[Issue]) {
+fn print_issues_grouped_by_priority(issues: &[Issue], color: bool) {
     // Group issues by priority using BTreeMap for sorted keys
     let mut groups: BTreeMap<i32, Vec<&Issue>> = BTreeMap::new();
     for issue in issues {
@@ -1519,62 +2515,86 @@ fn print_issues_grouped_by_priority(issues: &[Issue]) {
         println!("{}", "=".repeat(header_width));
 
         for issue in group_issues {
-            println!("{}: {} [{}]", issue.id, issue.title, issue.status);
+            println!(
+                "{}: {} [{}]",
+                style::id(&issue.id, color),
+                issue.title,
+                style::status(issue.status.as_str(), color)
+            );
         }
         println!();
     }
 }
 
-impl IssueFilters<'_> {
-    fn apply(&self, issues: &mut Vec<Issue>) {
-        if !self.labels.is_empty() {
-            issues.retain(|issue| self.labels.iter().all(|label| issue.labels.contains(label)));
-        }
+impl FilterArgs {
+    /// The storage-level filter these options describe
+    fn to_filter(&self) -> Result<IssueFilter> {
+        let status = match self.status.as_deref() {
+            None | Some("all") => None,
+            Some(value) => Some(value.parse::<Status>()?),
+        };
 
-        if self.github {
-            issues.retain(|issue| {
-                issue
-                    .external_ref
-                    .as_deref()
-                    .is_some_and(is_github_issue_ref)
-            });
-        }
+        let readiness = if self.ready_only {
+            Some(Readiness::Ready)
+        } else if self.blocked_only {
+            Some(Readiness::Blocked)
+        } else {
+            None
+        };
 
-        if let Some(id_filter) = self.id {
-            let target_ids: Vec<&str> = id_filter.split(',').map(str::trim).collect();
-            issues.retain(|issue| target_ids.contains(&issue.id.as_str()));
-        }
+        Ok(IssueFilter {
+            status,
+            priority: parse_priority_filters(&self.priority)?,
+            issue_type: type_filter(&self.r#type),
+            resolution: self.resolution,
+            assignee: self.assignee.clone(),
+            unassigned: self.unassigned,
+            labels: self.labels.clone(),
+            github: self.github,
+            ids: self.id.as_deref().map(split_id_list),
+            title: self.title.clone(),
+            parent: self.parent.clone(),
+            readiness,
+            ..Default::default()
+        })
+    }
+}
 
-        if let Some(title_filter) = self.title {
-            let title_lower = title_filter.to_lowercase();
-            issues.retain(|issue| issue.title.to_lowercase().contains(&title_lower));
-        }
+/// Split a comma-separated `--id` value into trimmed IDs
+fn split_id_list(value: &str) -> Vec<String> {
+    value.split(',').map(|id| id.trim().to_string()).collect()
+}
 
-        if let Some(parent_id) = self.parent {
-            issues.retain(|issue| {
-                issue
-                    .depends_on
-                    .get(parent_id)
-                    .is_some_and(|dep_type| *dep_type == DependencyType::ParentChild)
-            });
-        }
+/// Parse an `archive --closed-before` or `report --since` value: a bare
+/// YYYY-MM-DD means midnight UTC at the start of that day
+fn parse_cutoff_date(value: &str) -> Result<chrono::DateTime<chrono::Utc>> {
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_time(chrono::NaiveTime::MIN).and_utc());
     }
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .map_err(|_| {
+            error::invalid_input(format!(
+                "Invalid date '{}'. Use YYYY-MM-DD or RFC 3339",
+                value
+            ))
+        })
 }
 
 fn run() -> Result<()> {
-    let cli = Cli::parse();
+    let cli = Cli::try_parse()?;
 
     if let Some(directory) = &cli.global_opts.directory {
         env::set_current_dir(directory)
             .with_context(|| format!("Failed to change directory to {}", directory.display()))?;
     }
 
-    // Extract fields needed for get_storage before matching on cli.command
-    // This avoids borrowing issues when we try to call get_storage(mb_beads_dir, db) inside match arms
-    let mb_beads_dir = &cli.global_opts.mb_beads_dir;
-    let db = &cli.global_opts.db;
+    // Borrow the global options before matching on cli.command, which moves
+    // the command out of `cli`
+    let global_opts = &cli.global_opts;
     let json = cli.global_opts.json;
     let mb_no_cmd_logging = cli.global_opts.mb_no_cmd_logging;
+    let color = !json && style::color_enabled(cli.global_opts.no_color);
     let actor = cli.global_opts.actor.clone();
 
     match cli.command {
@@ -1588,16 +2608,34 @@ fn run() -> Result<()> {
             quiet,
             skip_agents: _,
             skip_hooks: _,
+            here,
         } => {
             // IMPORTANT: init always creates the primary storage dir in current directory
-            // It does NOT use find_beads_dir() or respect --db/--mb-beads-dir flags
+            // It does NOT use find_beads_dir() or respect --db/--beads-dir flags
             // This ensures init always initializes in CWD, never in ancestor directories
-            if mb_beads_dir.is_some() || db.is_some() {
+            if global_opts.mb_beads_dir.is_some() || global_opts.db.is_some() {
                 eprintln!(
                     "Note: 'mb init' always creates {}/ in current directory",
                     PRIMARY_STORAGE_DIR
                 );
-                eprintln!("      --db and --mb-beads-dir flags are ignored for 'init'");
+                eprintln!("      --db and --beads-dir flags are ignored for 'init'");
+            }
+
+            // Every other command would keep finding the ancestor database
+            // from below it, so a nested one is usually a mistake
+            if !here {
+                let cwd = env::current_dir()?;
+                if let Some(parent) = cwd.parent() {
+                    if let Some(existing) = find_storage_dir(parent)? {
+                        eprintln!(
+                            "Warning: a beads database already exists in a parent directory: {}",
+                            existing.display()
+                        );
+                        anyhow::bail!(
+                            "Refusing to create a nested database; run 'mb init --here' to create one anyway"
+                        );
+                    }
+                }
             }
 
             let beads_dir = PathBuf::from(PRIMARY_STORAGE_DIR);
@@ -1630,14 +2668,31 @@ fn run() -> Result<()> {
             labels,
             external_ref,
             id,
+            overwrite,
+            stdin,
+            description_file,
             deps,
+            dep,
+            strict_deps,
             parent,
-            force: _force,
+            force,
+            check_dupes,
             file,
             ephemeral: _,
             silent,
+            created_at,
+            dry_run,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let created_at = created_at
+                .as_deref()
+                .map(|value| {
+                    format::parse_timestamp(value).map_err(|e| error::invalid_input(e.to_string()))
+                })
+                .transpose()?;
+            let mut storage = get_storage(global_opts)?;
+            if strict_deps {
+                storage.set_strict_deps(true);
+            }
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -1663,6 +2718,27 @@ fn run() -> Result<()> {
                 }
             };
 
+            if check_dupes {
+                let similar =
+                    storage.find_similar_titles(&actual_title, SIMILAR_TITLE_THRESHOLD)?;
+                if !similar.is_empty() {
+                    eprintln!("Warning: open issues with similar titles already exist:");
+                    for (issue, score) in &similar {
+                        eprintln!(
+                            "  {} ({:.0}% similar): {}",
+                            issue.id,
+                            score * 100.0,
+                            issue.title
+                        );
+                    }
+                    if !force {
+                        anyhow::bail!(
+                            "Not creating a likely duplicate; pass --force to create it anyway"
+                        );
+                    }
+                }
+            }
+
             // Parse dependencies
             // Supports two formats:
             // 1. Simple: "bd-1,bd-2" (defaults to 'blocks' type)
@@ -1702,6 +2778,11 @@ fn run() -> Result<()> {
                 Vec::new()
             };
 
+            // Per-dependency types from repeated --dep ID[:TYPE]
+            for value in &dep {
+                parsed_deps.push(parse_dep_arg(value)?);
+            }
+
             // Add parent as a parent-child dependency if specified
             if let Some(parent_id) = parent {
                 parsed_deps.push((parent_id, DependencyType::ParentChild));
@@ -1710,101 +2791,159 @@ fn run() -> Result<()> {
             let mut all_labels = label;
             all_labels.extend(split_label_args(labels));
 
-            let issue = storage.create_issue(
-                actual_title,
+            // A body from --stdin / --description-file goes through the same
+            // section parser as issue files
+            let body = if stdin {
+                let mut body = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut body)
+                    .context("Failed to read issue body from stdin")?;
+                Some(body)
+            } else if let Some(path) = &description_file {
+                Some(
+                    std::fs::read_to_string(path)
+                        .with_context(|| format!("Failed to read {}", path.display()))?,
+                )
+            } else {
+                None
+            };
+            let (description, design, acceptance, notes) = match body {
+                Some(body) => {
+                    let parsed = format::parse_body(&body, storage.section_headers())?;
+                    let non_empty = |s: String| if s.is_empty() { None } else { Some(s) };
+                    (
+                        parsed.description,
+                        non_empty(parsed.design),
+                        non_empty(parsed.acceptance_criteria),
+                        parsed.notes,
+                    )
+                }
+                None => (description, design, acceptance, String::new()),
+            };
+
+            // Priority and type left unset fall back to the database's
+            // configured defaults
+            let spec = IssueSpec {
+                title: actual_title,
                 description,
-                design,
-                acceptance,
+                design: design.unwrap_or_default(),
+                acceptance_criteria: acceptance.unwrap_or_default(),
+                notes,
                 priority,
                 issue_type,
-                assignee,
-                all_labels,
+                assignee: assignee.unwrap_or_default(),
+                labels: all_labels,
                 external_ref,
                 id,
-                parsed_deps,
-            )?;
+                dependencies: parsed_deps,
+                overwrite,
+                created_at,
+            };
+
+            if dry_run {
+                let issue = storage.preview_issue(spec)?;
+
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&issue)?);
+                } else {
+                    println!("Would create issue: {}", issue.id);
+                    println!();
+                    print_issue_show(&issue, &[], color)?;
+                }
+                return Ok(());
+            }
+
+            let issue = storage.create_issue(spec)?;
+
+            // Editor integrations open the file directly, so report where it is
+            let path = storage.issue_path(&issue.id);
+            let path = std::fs::canonicalize(&path).unwrap_or(path);
 
             if json {
-                println!("{}", serde_json::to_string_pretty(&issue)?);
+                if !silent {
+                    eprintln!("Created issue: {}", issue.id);
+                }
+                let mut value = serde_json::to_value(&issue)?;
+                if let serde_json::Value::Object(ref mut object) = value {
+                    object.insert(
+                        "path".to_string(),
+                        serde_json::Value::String(path.display().to_string()),
+                    );
+                }
+                println!("{}", serde_json::to_string_pretty(&value)?);
             } else if !silent {
                 println!("Created issue: {}", issue.id);
+                println!("Path: {}", path.display());
             }
             Ok(())
         }
 
         Commands::List {
-            status,
-            priority,
-            r#type,
-            assignee,
-            labels,
-            github,
-            id,
-            title,
-            parent,
+            filters,
             limit,
+            sort,
+            reverse,
             group_priority,
+            table,
+            ids_only,
+            template,
+            format,
             include_infra: _,
             no_pager: _,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let mut storage = get_storage(global_opts)?;
+            storage.set_ignore_missing_blockers(filters.ignore_missing_blockers);
+            storage.set_include_archived(filters.include_archived);
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
                 let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
             }
 
-            // Parse priority filters (repeatable and/or comma-separated)
-            let priority_list = parse_priority_filters(&priority)?;
+            // Reject a bad template before reading any issues
+            let template = template.as_deref().map(OutputTemplate::parse).transpose()?;
 
-            let status_filter = match status.as_deref() {
-                None | Some("all") => None,
-                Some(value) => Some(value.parse::<Status>()?),
+            let filter = IssueFilter {
+                sort,
+                reverse,
+                ..filters.to_filter()?
             };
+            let issues = storage.list_issues(&filter, limit)?;
 
-            let mut issues = storage.list_issues(
-                status_filter,
-                priority_list,
-                r#type,
-                assignee.as_deref(),
-                None,
-            )?;
-
-            // Apply in-memory filters shared with `ready`
-            IssueFilters {
-                labels: &labels,
-                github,
-                id: id.as_deref(),
-                title: title.as_deref(),
-                parent: parent.as_deref(),
-            }
-            .apply(&mut issues);
-
-            // Apply limit if specified
-            if let Some(limit_val) = limit {
-                if limit_val > 0 {
-                    issues.truncate(limit_val);
+            if let Some(template) = &template {
+                for issue in &issues {
+                    println!("{}", template.render(issue));
                 }
-            }
-
-            if json {
+            } else if ids_only {
+                print_ids(issues.iter().map(|issue| issue.id.as_str()), json)?;
+            } else if let Some(format) = format {
+                print_issues_in_format(&issues, format)?;
+            } else if json {
                 println!("{}", serde_json::to_string_pretty(&issues)?);
             } else if group_priority {
-                print_issues_grouped_by_priority(&issues);
+                print_issues_grouped_by_priority(&issues, color);
+            } else if table {
+                print_issues_table(&issues, color);
             } else {
                 // Standard output
-                for issue in issues {
-                    println!(
-                        "{}: {} [{}] (priority: {})",
-                        issue.id, issue.title, issue.status, issue.priority
-                    );
+                for issue in &issues {
+                    println!("{}", issue_summary_line(issue, color));
                 }
             }
             Ok(())
         }
 
-        Commands::Show { issue_ids } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+        Commands::Show {
+            issue_ids,
+            oneline,
+            include_archived,
+            markdown,
+            normalized,
+            why,
+            history,
+        } => {
+            let mut storage = get_storage(global_opts)?;
+            storage.set_include_archived(include_archived);
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -1815,20 +2954,33 @@ fn run() -> Result<()> {
                 anyhow::bail!("No issue IDs provided. Usage: mb show <issue-id> [issue-ids...]");
             }
 
-            let prefix = storage.get_prefix()?;
-            let mut issues = Vec::new();
+            let issue_ids = expand_issue_ids(&storage, issue_ids)?;
 
-            // Normalize issue IDs (expand numeric shorthand like "14" -> "prefix-14")
-            for id_str in &issue_ids {
-                let normalized_id = if id_str.parse::<u32>().is_ok() {
-                    format!("{}-{}", prefix, id_str)
-                } else {
-                    id_str.clone()
-                };
+            if markdown {
+                let contents = issue_ids
+                    .iter()
+                    .map(|issue_id| {
+                        storage
+                            .issue_markdown(issue_id, normalized)?
+                            .ok_or_else(|| {
+                                error::not_found(format!("Issue not found: {}", issue_id))
+                            })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                for (idx, content) in contents.iter().enumerate() {
+                    if idx > 0 {
+                        println!("\n{}\n", "=".repeat(70));
+                    }
+                    print!("{}", content);
+                }
+                return Ok(());
+            }
 
+            let mut issues = Vec::new();
+            for issue_id in &issue_ids {
                 let issue = storage
-                    .get_issue(&normalized_id)?
-                    .ok_or_else(|| anyhow::anyhow!("Issue not found: {}", normalized_id))?;
+                    .get_issue(issue_id)?
+                    .ok_or_else(|| error::not_found(format!("Issue not found: {}", issue_id)))?;
                 issues.push(issue);
             }
 
@@ -1847,33 +2999,77 @@ fn run() -> Result<()> {
                                 "comments".to_string(),
                                 serde_json::to_value(comment_views)?,
                             );
+                            object.insert(
+                                "content_hash".to_string(),
+                                serde_json::Value::String(issue.content_hash()),
+                            );
+                            object.insert(
+                                "references".to_string(),
+                                serde_json::to_value(storage.issue_references(issue)?)?,
+                            );
+                            if why {
+                                object.insert(
+                                    "readiness".to_string(),
+                                    serde_json::to_value(storage.readiness(&issue.id)?)?,
+                                );
+                            }
+                            if history {
+                                object.insert(
+                                    "history".to_string(),
+                                    serde_json::to_value(storage.issue_history(&issue.id)?)?,
+                                );
+                            }
                         }
                         Ok(value)
                     })
                     .collect::<Result<Vec<_>>>()?;
                 println!("{}", serde_json::to_string_pretty(&issues_with_comments)?);
+            } else if oneline {
+                for issue in &issues {
+                    println!("{}", issue_summary_line(issue, color));
+                }
             } else {
-                let use_color = should_color_stdout();
                 for (idx, issue) in issues.iter().enumerate() {
                     if idx > 0 {
                         println!("\n{}", "=".repeat(70));
                         println!();
                     }
                     let comments = storage.list_comments(&issue.id)?;
-                    print_issue_show(issue, &comments, use_color)?;
-                }
+                    print_issue_show(issue, &comments, color)?;
+                    let references = storage.issue_references(issue)?;
+                    if !references.is_empty() {
+                        println!();
+                        println!("{}", style::label("References:", color));
+                        for reference in &references {
+                            println!(
+                                "  {} [{}] {}",
+                                style::id(&reference.id, color),
+                                style::status(reference.status.as_str(), color),
+                                reference.title
+                            );
+                        }
+                    }
+                    if why {
+                        println!();
+                        print_readiness(&storage.readiness(&issue.id)?, color);
+                    }
+                    if history {
+                        println!();
+                        print_history(storage.issue_history(&issue.id)?.as_deref(), color);
+                    }
+                }
             }
             Ok(())
         }
 
         Commands::Children { parent_id } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             if !mb_no_cmd_logging {
                 let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
             }
 
-            let mut children = storage.list_issues(None, None, None, None, None)?;
+            let mut children = storage.list_issues(&IssueFilter::new(), None)?;
             children.retain(|issue| {
                 issue
                     .depends_on
@@ -1885,7 +3081,12 @@ fn run() -> Result<()> {
                 println!("{}", serde_json::to_string_pretty(&children)?);
             } else {
                 for child in children {
-                    println!("{}: {} [{}]", child.id, child.title, child.status);
+                    println!(
+                        "{}: {} [{}]",
+                        style::id(&child.id, color),
+                        child.title,
+                        style::status(child.status.as_str(), color)
+                    );
                 }
             }
             Ok(())
@@ -1908,6 +3109,9 @@ fn run() -> Result<()> {
             acceptance,
             notes,
             external_ref,
+            append_description,
+            append_notes,
+            timestamped,
             add_label,
             remove_label,
             set_labels,
@@ -1916,7 +3120,7 @@ fn run() -> Result<()> {
             claim_for,
             claim_as,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -1999,6 +3203,18 @@ fn run() -> Result<()> {
             if let Some(e) = external_ref {
                 updates.insert("external_ref".to_string(), e);
             }
+            if let Some(d) = append_description {
+                updates.insert("append_description".to_string(), d);
+            }
+            if let Some(n) = append_notes {
+                let entry = if timestamped {
+                    let (actor, _) = resolve_default_actor(actor.as_deref(), &storage)?;
+                    timestamped_note(&n, &actor, chrono::Utc::now())
+                } else {
+                    n
+                };
+                updates.insert("append_notes".to_string(), entry);
+            }
 
             // Update all specified issues
             let mut updated_issues = Vec::new();
@@ -2045,7 +3261,7 @@ fn run() -> Result<()> {
             claim_as,
             force,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             if !mb_no_cmd_logging {
                 let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
@@ -2086,8 +3302,12 @@ fn run() -> Result<()> {
             Ok(())
         }
 
-        Commands::Close { issue_ids, reason } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+        Commands::Close {
+            issue_ids,
+            reason,
+            resolution,
+        } => {
+            let storage = get_storage(global_opts)?;
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -2097,7 +3317,7 @@ fn run() -> Result<()> {
             // Close all specified issues
             let mut closed_issues = Vec::new();
             for issue_id in &issue_ids {
-                let issue = storage.close_issue(issue_id, &reason)?;
+                let issue = storage.close_issue_with_resolution(issue_id, &reason, resolution)?;
                 closed_issues.push(issue);
             }
 
@@ -2115,7 +3335,7 @@ fn run() -> Result<()> {
             issue_ids,
             reason: _,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -2139,6 +3359,84 @@ fn run() -> Result<()> {
             Ok(())
         }
 
+        Commands::Start { issue_ids } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let issue_ids = expand_issue_ids(&storage, issue_ids)?;
+            transition_issues(&storage, &issue_ids, Transition::Start, None, json)
+        }
+
+        Commands::Block { issue_ids, reason } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let note = match reason {
+                Some(reason) => {
+                    let (actor, _) = resolve_default_actor(actor.as_deref(), &storage)?;
+                    let text = format!("Blocked: {}", reason);
+                    Some(timestamped_note(&text, &actor, chrono::Utc::now()))
+                }
+                None => None,
+            };
+            let issue_ids = expand_issue_ids(&storage, issue_ids)?;
+            transition_issues(
+                &storage,
+                &issue_ids,
+                Transition::Block,
+                note.as_deref(),
+                json,
+            )
+        }
+
+        Commands::Unblock { issue_ids } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let issue_ids = expand_issue_ids(&storage, issue_ids)?;
+            transition_issues(&storage, &issue_ids, Transition::Unblock, None, json)
+        }
+
+        Commands::LinkDuplicate {
+            dup_id,
+            canonical_id,
+            copy_labels,
+        } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let (dup, canonical) = storage.mark_duplicate(&dup_id, &canonical_id, copy_labels)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "duplicate": dup,
+                        "canonical": canonical,
+                    }))?
+                );
+            } else {
+                println!("Closed {} as a duplicate of {}", dup.id, canonical.id);
+            }
+            Ok(())
+        }
+
         Commands::MbRename {
             old_id,
             new_id,
@@ -2146,7 +3444,7 @@ fn run() -> Result<()> {
             repair,
             mb_patch_code,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -2164,10 +3462,13 @@ fn run() -> Result<()> {
                     for change in &changes {
                         println!("  {}", change);
                     }
-                } else if changes.len() == 1 && changes[0] == "No broken references found" {
-                    println!("No broken references found");
                 } else {
-                    println!("Repaired {} broken reference(s)", changes.len());
+                    let repaired = changes.iter().filter(|c| !c.is_note()).count();
+                    if repaired == 0 {
+                        println!("No broken references found");
+                    } else {
+                        println!("Repaired {} broken reference(s)", repaired);
+                    }
                     for change in &changes {
                         println!("  {}", change);
                     }
@@ -2185,8 +3486,16 @@ fn run() -> Result<()> {
                     }
                 } else {
                     println!("Successfully renamed {} to {}", old_id, new_id);
-                    if changes.len() > 2 {
-                        println!("Updated {} file(s) with references", changes.len() - 2);
+                    let referencing: std::collections::BTreeSet<&str> = changes
+                        .iter()
+                        .filter_map(|change| match change {
+                            Change::UpdateDependency { issue, .. }
+                            | Change::UpdateTextReferences { issue, .. } => Some(issue.as_str()),
+                            _ => None,
+                        })
+                        .collect();
+                    if !referencing.is_empty() {
+                        println!("Updated {} file(s) with references", referencing.len());
                     }
 
                     // Patch code references if requested
@@ -2205,7 +3514,7 @@ fn run() -> Result<()> {
             dry_run,
             force,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -2223,13 +3532,84 @@ fn run() -> Result<()> {
                 }
             } else {
                 println!("Successfully renamed prefix to '{}'", new_prefix);
-                println!("Renamed {} issue(s)", changes.len() / 2); // Each issue has 2 changes: file rename + content update
+                println!("Renamed {} issue(s)", count_renamed_files(&changes));
+            }
+            Ok(())
+        }
+
+        Commands::MoveTo {
+            target,
+            issue_ids,
+            keep_ids,
+            delete_source,
+            dry_run,
+        } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let target_dir = [PRIMARY_STORAGE_DIR, LEGACY_STORAGE_DIR]
+                .iter()
+                .map(|name| target.join(name))
+                .find(|dir| dir.is_dir())
+                .unwrap_or(target);
+            let same_dir = std::fs::canonicalize(&target_dir).ok()
+                == std::fs::canonicalize(storage.get_beads_dir()).ok();
+            if same_dir {
+                anyhow::bail!("Target is the current database: {}", target_dir.display());
+            }
+            let target_storage = open_storage(target_dir.clone(), global_opts)
+                .with_context(|| format!("Failed to open target {}", target_dir.display()))?;
+
+            let ids = expand_issue_ids(&storage, issue_ids)?;
+
+            let subset = storage.export_subset(&ids)?;
+            let (mapping, mut changes) =
+                target_storage.import_moved_issues(subset, keep_ids, dry_run)?;
+            if delete_source {
+                changes.extend(storage.remove_moved_issues(&ids, dry_run)?);
+            }
+
+            if json {
+                let moved: Vec<_> = mapping
+                    .iter()
+                    .map(|(from, to)| serde_json::json!({ "from": from, "to": to }))
+                    .collect();
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "target": target_dir.display().to_string(),
+                        "moved": moved,
+                        "changes": changes,
+                        "dry_run": dry_run,
+                    }))?
+                );
+            } else if dry_run {
+                println!("Dry run - would make the following changes:");
+                for change in &changes {
+                    println!("  {}", change);
+                }
+            } else {
+                println!(
+                    "Moved {} issue(s) to {}",
+                    mapping.len(),
+                    target_dir.display()
+                );
+                for (from, to) in &mapping {
+                    println!("  {} -> {}", from, to);
+                }
+                for note in changes.iter().filter(|c| c.is_note()) {
+                    println!("  {}", note);
+                }
             }
             Ok(())
         }
 
         Commands::Dep { command } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let mut storage = get_storage(global_opts)?;
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -2241,14 +3621,90 @@ fn run() -> Result<()> {
                     issue_id,
                     depends_on_id,
                     r#type,
+                    strict_deps,
+                    force,
+                    bidirectional,
                 } => {
-                    storage.add_dependency(&issue_id, &depends_on_id, r#type)?;
+                    if strict_deps {
+                        storage.set_strict_deps(true);
+                    }
+
+                    let targets: Vec<String> = split_id_list(&depends_on_id)
+                        .into_iter()
+                        .filter(|id| !id.is_empty())
+                        .collect();
+                    if targets.len() > 1 {
+                        if bidirectional {
+                            return Err(error::invalid_input(
+                                "--bidirectional takes a single target",
+                            ));
+                        }
+                        let results =
+                            storage.add_dependencies(&issue_id, &targets, r#type, force)?;
+                        let mut failed = 0;
+                        for (target, result) in &results {
+                            match result {
+                                Ok(added) if !json => {
+                                    let what = if *added {
+                                        "Added dependency"
+                                    } else {
+                                        "Dependency already exists"
+                                    };
+                                    println!(
+                                        "{}: {} depends on {} ({})",
+                                        what, issue_id, target, r#type
+                                    );
+                                }
+                                Ok(_) => {}
+                                Err(e) => {
+                                    failed += 1;
+                                    eprintln!("Error: {} -> {}: {}", issue_id, target, e);
+                                }
+                            }
+                        }
+                        if failed > 0 {
+                            anyhow::bail!(
+                                "{} of {} dependencies could not be added",
+                                failed,
+                                results.len()
+                            );
+                        }
+                        return Ok(());
+                    }
+
+                    let edges = if bidirectional {
+                        if r#type != DependencyType::Related {
+                            return Err(error::invalid_input(format!(
+                                "--bidirectional only applies to related dependencies; {} is directional",
+                                r#type
+                            )));
+                        }
+                        let (forward, reverse) =
+                            storage.add_related_dependency(&issue_id, &depends_on_id, force)?;
+                        vec![
+                            (forward, &issue_id, &depends_on_id),
+                            (reverse, &depends_on_id, &issue_id),
+                        ]
+                    } else {
+                        let added =
+                            storage.add_dependency(&issue_id, &depends_on_id, r#type, force)?;
+                        vec![(added, &issue_id, &depends_on_id)]
+                    };
 
                     if !json {
-                        println!(
-                            "Added dependency: {} depends on {} ({})",
-                            issue_id, depends_on_id, r#type
-                        );
+                        for (added, from, to) in edges {
+                            if added {
+                                println!(
+                                    "Added dependency: {} depends on {} ({})",
+                                    from, to, r#type
+                                );
+                            } else {
+                                println!(
+                                    "Dependency already exists: {} depends on {} ({})",
+                                    from, to, r#type
+                                );
+                            }
+                        }
                     }
                 }
                 DepCommands::Remove {
@@ -2264,6 +3720,27 @@ fn run() -> Result<()> {
                         );
                     }
                 }
+                DepCommands::Type {
+                    issue_id,
+                    depends_on_id,
+                    new_type,
+                } => {
+                    let previous =
+                        storage.set_dependency_type(&issue_id, &depends_on_id, new_type)?;
+
+                    if !json {
+                        match previous {
+                            Some(previous) => println!(
+                                "Changed dependency: {} depends on {} ({} -> {})",
+                                issue_id, depends_on_id, previous, new_type
+                            ),
+                            None => println!(
+                                "Dependency already has that type: {} depends on {} ({})",
+                                issue_id, depends_on_id, new_type
+                            ),
+                        }
+                    }
+                }
                 DepCommands::List {
                     issue_ids,
                     direction,
@@ -2271,9 +3748,9 @@ fn run() -> Result<()> {
                 } => {
                     let mut rows = Vec::new();
                     for issue_id in issue_ids {
-                        let issue = storage
-                            .get_issue(&issue_id)?
-                            .ok_or_else(|| anyhow::anyhow!("Issue not found: {}", issue_id))?;
+                        let issue = storage.get_issue(&issue_id)?.ok_or_else(|| {
+                            error::not_found(format!("Issue not found: {}", issue_id))
+                        })?;
 
                         match direction.as_str() {
                             "down" => {
@@ -2332,8 +3809,14 @@ fn run() -> Result<()> {
                     issue_id,
                     max_depth,
                     show_all_paths,
+                    max_children,
                 } => {
-                    let tree = storage.get_dependency_tree(&issue_id, max_depth, show_all_paths)?;
+                    let tree = storage.get_dependency_tree(
+                        &issue_id,
+                        max_depth,
+                        show_all_paths,
+                        max_children,
+                    )?;
 
                     if json {
                         println!("{}", serde_json::to_string_pretty(&tree)?);
@@ -2367,8 +3850,228 @@ fn run() -> Result<()> {
             Ok(())
         }
 
+        Commands::Copy { source_id } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let source_id = expand_issue_id(&storage, source_id)?;
+
+            let issue = storage.copy_issue(&source_id)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&issue)?);
+            } else {
+                println!("Created issue: {} (copy of {})", issue.id, source_id);
+            }
+            Ok(())
+        }
+
+        Commands::Path { issue_id } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let issue_id = expand_issue_id(&storage, issue_id)?;
+
+            let path = storage
+                .resolve_issue_path(&issue_id)
+                .ok_or_else(|| error::not_found(format!("Issue not found: {}", issue_id)))?;
+            let path = std::fs::canonicalize(&path).unwrap_or(path);
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "id": issue_id,
+                        "path": path.display().to_string(),
+                    }))?
+                );
+            } else {
+                println!("{}", path.display());
+            }
+            Ok(())
+        }
+
+        Commands::CriticalPath { issue_id } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let issue_id = expand_issue_id(&storage, issue_id)?;
+
+            let path = storage.get_critical_path(&issue_id)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "issue_id": issue_id,
+                        "path": path,
+                        "length": path.len(),
+                    }))?
+                );
+            } else if path.len() == 1 {
+                println!("{} has no open blockers.", issue_id);
+            } else {
+                println!("Critical path to {} ({} issues):", issue_id, path.len());
+                for (i, id) in path.iter().enumerate() {
+                    let title = storage
+                        .get_issue(id)?
+                        .map(|issue| issue.title)
+                        .unwrap_or_default();
+                    println!("  {}. {}: {}", i + 1, id, title);
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Graph { filters, format } => {
+            let mut storage = get_storage(global_opts)?;
+            storage.set_ignore_missing_blockers(filters.ignore_missing_blockers);
+            storage.set_include_archived(filters.include_archived);
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let graph = storage.dependency_graph(&filters.to_filter()?)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&graph)?);
+            } else {
+                match format {
+                    GraphFormat::Dot => print!("{}", graph.to_dot()),
+                    GraphFormat::Mermaid => print!("{}", graph.to_mermaid()),
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Archive {
+            closed_before,
+            dry_run,
+        } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let cutoff = parse_cutoff_date(&closed_before)?;
+            let archived = storage.archive_closed_before(cutoff, dry_run)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "archived": archived,
+                        "dry_run": dry_run,
+                    }))?
+                );
+            } else if archived.is_empty() {
+                println!("No closed issues before {} to archive.", closed_before);
+            } else {
+                let verb = if dry_run { "Would archive" } else { "Archived" };
+                println!("{} {} issue(s):", verb, archived.len());
+                for id in &archived {
+                    println!("  {}", id);
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Restore { issue_ids, all } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let ids: Vec<String> = if all {
+                storage.archived_ids()?.into_iter().collect()
+            } else {
+                expand_issue_ids(&storage, issue_ids)?
+            };
+
+            storage.restore_issues(&ids)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({ "restored": ids }))?
+                );
+            } else if ids.is_empty() {
+                println!("No archived issues to restore.");
+            } else {
+                println!("Restored {} issue(s):", ids.len());
+                for id in &ids {
+                    println!("  {}", id);
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Watch { issue_id, user } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let user = user.trim();
+            if user.is_empty() {
+                anyhow::bail!("User must not be empty");
+            }
+            let issue_id = expand_issue_id(&storage, issue_id)?;
+            let issue = storage.add_watcher(&issue_id, user)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&issue)?);
+            } else {
+                println!("{} is watching {}", user, issue.id);
+            }
+            Ok(())
+        }
+
+        Commands::Unwatch { issue_id, user } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let user = user.trim();
+            if user.is_empty() {
+                anyhow::bail!("User must not be empty");
+            }
+            let issue_id = expand_issue_id(&storage, issue_id)?;
+            let issue = storage.remove_watcher(&issue_id, user)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&issue)?);
+            } else {
+                println!("{} is no longer watching {}", user, issue.id);
+            }
+            Ok(())
+        }
+
         Commands::Label { command } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             if !mb_no_cmd_logging {
                 let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
@@ -2404,9 +4107,9 @@ fn run() -> Result<()> {
                     }
                 }
                 LabelCommands::List { issue_id } => {
-                    let issue = storage
-                        .get_issue(&issue_id)?
-                        .ok_or_else(|| anyhow::anyhow!("Issue not found: {}", issue_id))?;
+                    let issue = storage.get_issue(&issue_id)?.ok_or_else(|| {
+                        error::not_found(format!("Issue not found: {}", issue_id))
+                    })?;
                     if json {
                         println!("{}", serde_json::to_string_pretty(&issue.labels)?);
                     } else {
@@ -2430,7 +4133,7 @@ fn run() -> Result<()> {
         }
 
         Commands::Config { command } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             if !mb_no_cmd_logging {
                 let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
@@ -2497,7 +4200,7 @@ fn run() -> Result<()> {
         }
 
         Commands::Comments { command } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             if !mb_no_cmd_logging {
                 let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
@@ -2505,10 +4208,7 @@ fn run() -> Result<()> {
 
             match command {
                 CommentCommands::Add { issue_id, body } => {
-                    let author = actor
-                        .as_deref()
-                        .map(ToString::to_string)
-                        .unwrap_or_else(|| resolve_actor(None, None));
+                    let (author, _) = resolve_default_actor(actor.as_deref(), &storage)?;
                     let comment = storage.add_comment(&issue_id, &author, &body)?;
                     if json {
                         println!("{}", serde_json::to_string_pretty(&comment)?);
@@ -2556,7 +4256,7 @@ fn run() -> Result<()> {
         }
 
         Commands::Github { command } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             if !mb_no_cmd_logging {
                 let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
@@ -2565,7 +4265,7 @@ fn run() -> Result<()> {
             let report = match command {
                 GithubCommands::List => {
                     let links: Vec<GithubLinkView> = storage
-                        .list_issues(None, None, None, None, None)?
+                        .list_issues(&IssueFilter::new(), None)?
                         .into_iter()
                         .filter_map(|issue| {
                             let github_url = issue.external_ref.clone()?;
@@ -2702,42 +4402,376 @@ fn run() -> Result<()> {
             };
 
             if json {
-                println!("{}", serde_json::to_string_pretty(&report)?);
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print_github_report(&report, false, false);
+            }
+            Ok(())
+        }
+
+        Commands::VerifyRoundtrip => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let unstable = storage.check_roundtrip()?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({ "unstable": unstable }))?
+                );
+            } else if unstable.is_empty() {
+                println!("All issues round-trip cleanly");
+            } else {
+                for id in &unstable {
+                    println!("{}: would change on rewrite", id);
+                }
+            }
+            if !unstable.is_empty() {
+                anyhow::bail!("{} issue(s) do not round-trip cleanly", unstable.len());
+            }
+            Ok(())
+        }
+
+        Commands::Dupes => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let groups = storage.find_duplicate_titles()?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({ "duplicates": groups }))?
+                );
+            } else if groups.is_empty() {
+                println!("No duplicate titles found");
+            } else {
+                let issues: HashMap<String, Issue> = storage
+                    .list_issues(&IssueFilter::new(), None)?
+                    .into_iter()
+                    .map(|issue| (issue.id.clone(), issue))
+                    .collect();
+                for (i, group) in groups.iter().enumerate() {
+                    if i > 0 {
+                        println!();
+                    }
+                    for issue in group.iter().filter_map(|id| issues.get(id)) {
+                        println!("{} [{}] {}", issue.id, issue.status, issue.title);
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Doctor => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let duplicates = storage.find_duplicate_titles()?;
+            let cycles = storage.detect_dependency_cycles()?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "duplicate_titles": duplicates,
+                        "dependency_cycles": cycles,
+                    }))?
+                );
+                return Ok(());
+            }
+
+            if duplicates.is_empty() {
+                println!("✓ No duplicate titles");
+            } else {
+                println!(
+                    "✗ {} group(s) of issues with the same title (see `mb dupes`):",
+                    duplicates.len()
+                );
+                for group in &duplicates {
+                    println!("  {}", group.join(", "));
+                }
+            }
+            if cycles.is_empty() {
+                println!("✓ No dependency cycles");
+            } else {
+                println!(
+                    "✗ {} dependency cycle(s) (see `mb dep cycles`):",
+                    cycles.len()
+                );
+                for cycle in &cycles {
+                    println!("  {} -> {}", cycle.join(" -> "), cycle[0]);
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Snapshot { output } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let files = storage.snapshot(&output)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "path": output.display().to_string(),
+                        "files": files,
+                    }))?
+                );
+            } else {
+                println!("Copied {} file(s) to {}", files, output.display());
+                println!("Read it with: mb --beads-dir {} list", output.display());
+            }
+            Ok(())
+        }
+
+        Commands::Gc { dry_run } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let changes = storage.gc(dry_run)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&changes)?);
+            } else if changes.is_empty() {
+                println!("Nothing to clean up");
+            } else {
+                if dry_run {
+                    println!("Dry run - would make the following changes:");
+                } else {
+                    println!("Removed {} file(s):", changes.len());
+                }
+                for change in &changes {
+                    println!("  {}", change);
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Normalize { dry_run } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let normalized = storage.normalize_all(dry_run)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "normalized": normalized,
+                        "dry_run": dry_run,
+                    }))?
+                );
+            } else if normalized.is_empty() {
+                println!("All issues are already in canonical form.");
+            } else {
+                let verb = if dry_run {
+                    "Would normalize"
+                } else {
+                    "Normalized"
+                };
+                println!("{} {} issue(s):", verb, normalized.len());
+                for id in &normalized {
+                    println!("  {}", id);
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Whoami { set } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            if let Some(name) = &set {
+                if name.trim().is_empty() {
+                    return Err(error::invalid_input("--set needs a non-empty name"));
+                }
+                storage.set_default_actor(name.trim())?;
+            }
+            let (resolved, source) = resolve_default_actor(actor.as_deref(), &storage)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "actor": resolved,
+                        "source": source,
+                    }))?
+                );
+            } else {
+                println!("{}", resolved);
+            }
+            Ok(())
+        }
+
+        Commands::Stats => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let stats = storage.get_stats()?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&stats)?);
+            } else {
+                println!("Total issues: {}", stats.total_issues);
+                println!("Open: {}", stats.open_issues);
+                println!("In Progress: {}", stats.in_progress_issues);
+                println!("Blocked: {}", stats.blocked_issues);
+                println!("Closed: {}", stats.closed_issues);
+                for (resolution, count) in &stats.closed_by_resolution {
+                    println!("  {}: {}", resolution, count);
+                }
+                println!("Ready: {}", stats.ready_issues);
+                println!("Ready and unassigned: {}", stats.ready_unassigned_issues);
+                if let Some(length) = stats.max_hash_id_length {
+                    println!("Longest hash ID: {} characters", length);
+                }
+                println!(
+                    "Average lead time: {:.1} hours",
+                    stats.average_lead_time_hours
+                );
+            }
+            Ok(())
+        }
+
+        Commands::Recent { limit } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let issues = storage.recently_updated(Some(limit))?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&issues)?);
+            } else if issues.is_empty() {
+                println!("No issues found");
+            } else {
+                let now = chrono::Utc::now();
+                for issue in &issues {
+                    println!(
+                        "{} (updated {} ago)",
+                        issue_summary_line(issue, color),
+                        format_elapsed(now - issue.updated_at)
+                    );
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Blame { issue_id, field } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let issue_id = expand_issue_id(&storage, issue_id)?;
+            let blame = storage.field_blame(&issue_id, &field)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&blame)?);
             } else {
-                print_github_report(&report, false, false);
+                print_field_blame(&issue_id, blame.as_ref(), color);
             }
             Ok(())
         }
 
-        Commands::Stats => {
-            let storage = get_storage(mb_beads_dir, db)?;
+        Commands::Burndown { days } => {
+            let storage = get_storage(global_opts)?;
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
                 let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
             }
 
-            let stats = storage.get_stats()?;
+            let series = storage.burndown(days)?;
 
             if json {
-                println!("{}", serde_json::to_string_pretty(&stats)?);
+                let points: Vec<_> = series
+                    .iter()
+                    .map(|(date, open)| {
+                        serde_json::json!({ "date": date.to_string(), "open": open })
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&points)?);
             } else {
-                println!("Total issues: {}", stats.total_issues);
-                println!("Open: {}", stats.open_issues);
-                println!("In Progress: {}", stats.in_progress_issues);
-                println!("Blocked: {}", stats.blocked_issues);
-                println!("Closed: {}", stats.closed_issues);
-                println!("Ready: {}", stats.ready_issues);
-                println!(
-                    "Average lead time: {:.1} hours",
-                    stats.average_lead_time_hours
-                );
+                for (date, open) in &series {
+                    println!("{}  {}", date, open);
+                }
+            }
+            Ok(())
+        }
+
+        Commands::Report { since, output } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let since = match since {
+                Some(date) => parse_cutoff_date(&date)?,
+                None => chrono::Utc::now() - chrono::Duration::days(7),
+            };
+            let report = storage.generate_report(&ReportOptions { since })?;
+
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, &report)
+                        .with_context(|| format!("Failed to write {}", path.display()))?;
+                    eprintln!("Wrote report to {}", path.display());
+                }
+                None => print!("{}", report),
             }
             Ok(())
         }
 
-        Commands::Blocked => {
-            let storage = get_storage(mb_beads_dir, db)?;
+        Commands::Blocked {
+            ignore_missing_blockers,
+            ids_only,
+        } => {
+            let mut storage = get_storage(global_opts)?;
+            storage.set_ignore_missing_blockers(ignore_missing_blockers);
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -2746,72 +4780,218 @@ fn run() -> Result<()> {
 
             let blocked = storage.get_blocked()?;
 
-            if json {
+            if ids_only {
+                print_ids(blocked.iter().map(|item| item.issue.id.as_str()), json)?;
+            } else if json {
                 println!("{}", serde_json::to_string_pretty(&blocked)?);
             } else {
+                let now = chrono::Utc::now();
                 for item in blocked {
+                    let since = item
+                        .blocked_since
+                        .map(|since| format!(" (for {})", format_elapsed(now - since)))
+                        .unwrap_or_default();
                     println!(
-                        "{}: {} - blocked by: {}",
+                        "{}: {} - blocked by: {}{}",
                         item.issue.id,
                         item.issue.title,
-                        item.blocked_by.join(", ")
+                        item.blocked_by.join(", "),
+                        since
                     );
                 }
             }
             Ok(())
         }
 
+        Commands::Import { input, mode } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let (imported, skipped, errors) = storage.import_from_jsonl(&input, mode)?;
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "imported": imported,
+                        "skipped": skipped,
+                        "errors": errors,
+                    }))?
+                );
+            } else {
+                println!("Imported {} issue(s), skipped {}", imported, skipped);
+                for error in &errors {
+                    eprintln!("Error: {}", error);
+                }
+            }
+            if !errors.is_empty() {
+                anyhow::bail!("{} line(s) could not be imported", errors.len());
+            }
+            Ok(())
+        }
+
         Commands::Export {
             output,
             mb_output_default,
-            status,
-            priority,
-            r#type,
-            assignee,
+            filters,
+            mb_native,
+            with_header,
+            format,
+            no_dependents,
+            closure,
+            include_dependents,
+            pretty_dependents_graph,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let mut storage = get_storage(global_opts)?;
+            storage.set_ignore_missing_blockers(filters.ignore_missing_blockers);
+            storage.set_include_archived(filters.include_archived);
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
                 let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
             }
 
+            if with_header && format == ExportFormat::Json {
+                anyhow::bail!("--with-header only applies to JSONL output");
+            }
+            if no_dependents && format == ExportFormat::Json {
+                anyhow::bail!("--no-dependents only applies to JSONL output");
+            }
+            if format == ExportFormat::NdjsonStream && (output.is_some() || mb_output_default) {
+                anyhow::bail!(
+                    "--format ndjson-stream writes to stdout; it cannot be combined with -o or --mb-output-default"
+                );
+            }
+
+            let mut filter = filters.to_filter()?;
+            if let Some(root) = closure {
+                let root = expand_issue_id(&storage, root)?;
+                let direction = if include_dependents {
+                    ClosureDirection::Both
+                } else {
+                    ClosureDirection::Dependencies
+                };
+                let ids = storage.dependency_closure(&root, direction)?;
+                filter.ids = Some(match filter.ids {
+                    Some(selected) => selected.into_iter().filter(|id| ids.contains(id)).collect(),
+                    None => ids.into_iter().collect(),
+                });
+            }
+
+            if pretty_dependents_graph {
+                if let Some(path) = output {
+                    let count = storage.export_graph_json(&path, &filter)?;
+                    eprintln!(
+                        "Exported dependency graph of {} issues to {}",
+                        count,
+                        path.display()
+                    );
+                } else {
+                    let graph = storage.dependency_graph(&filter)?;
+                    println!("{}", serde_json::to_string_pretty(&graph)?);
+                }
+                return Ok(());
+            }
+
+            if format == ExportFormat::Json {
+                let path = if mb_output_default {
+                    Some(storage.get_beads_dir().join("issues.json"))
+                } else {
+                    output
+                };
+                if let Some(path) = path {
+                    let count = storage.export_to_json_array(&path, &filter)?;
+                    eprintln!("Exported {} issues to {}", count, path.display());
+                } else {
+                    let issues = storage.list_issues_for_export(&filter)?;
+                    println!("{}", serde_json::to_string_pretty(&issues)?);
+                }
+                return Ok(());
+            }
+
+            // The default output file is the mirror `mb sync` reads back, so
+            // it must keep the fields the upstream schema has no room for
+            let flavor = if mb_native || mb_output_default {
+                JsonlFlavor::Native
+            } else {
+                JsonlFlavor::Upstream
+            };
+
             // Determine output destination
             if let Some(path) = output {
                 // -o flag provided: write to specified file
-                let count = storage.export_to_jsonl(
-                    &path,
-                    status,
-                    priority,
-                    r#type,
-                    assignee.as_deref(),
-                )?;
+                let count =
+                    storage.export_to_jsonl(&path, &filter, flavor, with_header, !no_dependents)?;
                 eprintln!("Exported {} issues to {}", count, path.display());
             } else if mb_output_default {
                 // --mb-output-default: write to storage/issues.jsonl
                 let path = storage.get_beads_dir().join("issues.jsonl");
-                let count = storage.export_to_jsonl(
-                    &path,
-                    status,
-                    priority,
-                    r#type,
-                    assignee.as_deref(),
-                )?;
+                let count =
+                    storage.export_to_jsonl(&path, &filter, flavor, with_header, !no_dependents)?;
                 eprintln!("Exported {} issues to {}", count, path.display());
             } else {
-                // Default: write to stdout (matching upstream bd)
-                // Convert single priority to vector for list_issues
-                let priority_list = priority.map(|p| vec![p]);
-                let issues = storage.list_issues(
-                    status,
-                    priority_list,
-                    r#type,
-                    assignee.as_deref(),
-                    None,
+                // Default: write to stdout (matching upstream bd). A stream
+                // is flushed at every newline, i.e. after each issue
+                let stdout = std::io::stdout().lock();
+                let mut writer: Box<dyn Write> = if format == ExportFormat::NdjsonStream {
+                    Box::new(std::io::LineWriter::new(stdout))
+                } else {
+                    Box::new(std::io::BufWriter::new(stdout))
+                };
+                storage.export_jsonl_to_writer(
+                    &mut writer,
+                    &filter,
+                    flavor,
+                    with_header,
+                    !no_dependents,
                 )?;
-                for issue in &issues {
-                    let json = serde_json::to_string(&issue)?;
-                    println!("{}", json);
+            }
+            Ok(())
+        }
+
+        Commands::Diff { issue_id, jsonl } => {
+            let storage = get_storage(global_opts)?;
+
+            // Log command after storage is validated
+            if !mb_no_cmd_logging {
+                let _ = log_command(&storage.get_beads_dir(), &env::args().collect::<Vec<_>>());
+            }
+
+            let issue_id = expand_issue_id(&storage, issue_id)?;
+            let jsonl_path = jsonl.unwrap_or_else(|| storage.get_beads_dir().join("issues.jsonl"));
+
+            let markdown_issue = storage
+                .get_issue(&issue_id)?
+                .ok_or_else(|| error::not_found(format!("Issue not found: {}", issue_id)))?;
+            let jsonl_issue = sync::load_jsonl_issues(&jsonl_path)?
+                .remove(&issue_id)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Issue not found in JSONL: {} ({})",
+                        issue_id,
+                        jsonl_path.display()
+                    )
+                })?
+                .issue;
+
+            let diffs = sync::diff_issue_fields(&markdown_issue, &jsonl_issue)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&diffs)?);
+            } else if diffs.is_empty() {
+                println!("No differences between markdown and JSONL for {}", issue_id);
+            } else {
+                println!(
+                    "{}: markdown vs JSONL ({} field(s) differ)",
+                    issue_id,
+                    diffs.len()
+                );
+                for (field, diff) in &diffs {
+                    print_field_diff(field, diff);
                 }
             }
             Ok(())
@@ -2821,8 +5001,11 @@ fn run() -> Result<()> {
             jsonl,
             dry_run,
             direction,
+            no_backup,
+            prefer,
+            tolerance_ms,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -2834,10 +5017,39 @@ fn run() -> Result<()> {
 
             // Load issues from both sources
             let markdown_issues = sync::load_markdown_issues(&beads_dir)?;
-            let jsonl_issues = sync::load_jsonl_issues(&jsonl_path)?;
+            let mut jsonl_issues = sync::load_jsonl_issues(&jsonl_path)?;
+
+            // Archived issues stay in older JSONL exports; don't recreate them
+            for id in storage.archived_ids()? {
+                jsonl_issues.remove(&id);
+            }
+
+            // The winning side must be one this direction is allowed to write
+            match (direction.as_str(), prefer) {
+                ("to-jsonl", sync::ConflictPolicy::Jsonl) => {
+                    return Err(error::invalid_input(
+                        "--prefer jsonl writes markdown, which --direction to-jsonl does not allow",
+                    ));
+                }
+                ("to-markdown", sync::ConflictPolicy::Markdown) => {
+                    return Err(error::invalid_input(
+                        "--prefer markdown writes JSONL, which --direction to-markdown does not allow",
+                    ));
+                }
+                _ => {}
+            }
+
+            let tolerance_ms = match tolerance_ms {
+                Some(ms) => ms,
+                None => storage
+                    .sync_tolerance_ms()?
+                    .unwrap_or(sync::DEFAULT_TOLERANCE_MS),
+            };
 
             // Create sync engine and analyze
-            let engine = sync::SyncEngine::new();
+            let engine = sync::SyncEngine::with_tolerance_ms(tolerance_ms)
+                .with_backup(!no_backup)
+                .with_conflict_policy(prefer);
             let plan = engine.analyze(markdown_issues.clone(), jsonl_issues.clone())?;
 
             // Filter plan based on direction
@@ -2901,7 +5113,16 @@ fn run() -> Result<()> {
                     }
                 }
                 if !filtered_plan.conflicts.is_empty() {
-                    println!("  Conflicts ({}):", filtered_plan.conflicts.len());
+                    let resolution = match prefer {
+                        sync::ConflictPolicy::Skip => "",
+                        sync::ConflictPolicy::Markdown => ", keeping markdown",
+                        sync::ConflictPolicy::Jsonl => ", keeping JSONL",
+                    };
+                    println!(
+                        "  Conflicts ({}{}):",
+                        filtered_plan.conflicts.len(),
+                        resolution
+                    );
                     for id in &filtered_plan.conflicts {
                         println!("    {}", id);
                     }
@@ -2939,9 +5160,15 @@ fn run() -> Result<()> {
                 if report.updated_markdown > 0 {
                     println!("  Updated markdown: {}", report.updated_markdown);
                 }
+                if report.resolved_conflicts > 0 {
+                    println!("  Resolved conflicts: {}", report.resolved_conflicts);
+                }
                 if report.skipped_conflicts > 0 {
                     println!("  Skipped conflicts: {}", report.skipped_conflicts);
                 }
+                if let Some(backup_path) = &report.backup_path {
+                    println!("  Backed up JSONL to {}", backup_path.display());
+                }
                 if !report.errors.is_empty() {
                     println!("\nErrors:");
                     for error in &report.errors {
@@ -2954,6 +5181,7 @@ fn run() -> Result<()> {
 
         Commands::Ready {
             assignee,
+            unassigned,
             priority,
             r#type,
             labels,
@@ -2962,10 +5190,14 @@ fn run() -> Result<()> {
             title,
             parent,
             limit,
+            capacity,
             group_priority,
             sort,
+            ignore_missing_blockers,
+            ids_only,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let mut storage = get_storage(global_opts)?;
+            storage.set_ignore_missing_blockers(ignore_missing_blockers);
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -2981,44 +5213,71 @@ fn run() -> Result<()> {
                 }
             };
 
-            let priority_list = parse_priority_filters(&priority)?;
-
-            let mut ready =
-                storage.get_ready(assignee.as_deref(), priority_list, r#type, sort_policy)?;
-
-            // Apply in-memory filters shared with `list`
-            IssueFilters {
-                labels: &labels,
+            // The same filter `list` and `export` build
+            let filter = IssueFilter {
+                priority: parse_priority_filters(&priority)?,
+                issue_type: type_filter(&r#type),
+                assignee,
+                unassigned,
+                labels,
                 github,
-                id: id.as_deref(),
-                title: title.as_deref(),
-                parent: parent.as_deref(),
-            }
-            .apply(&mut ready);
+                ids: id.as_deref().map(split_id_list),
+                title,
+                parent,
+                ..Default::default()
+            };
 
-            // Shuffle after filtering so `-n 1 -s random` picks uniformly from
-            // the whole filtered set, not just its head.
-            if sort_policy == "random" {
-                use rand::seq::SliceRandom;
-                ready.shuffle(&mut rand::thread_rng());
+            let mut limit = limit.filter(|&limit_val| limit_val > 0);
+            // WIP limit: only offer what keeps the assignee within capacity
+            if let (Some(capacity), Some(assignee)) = (capacity, filter.assignee.as_deref()) {
+                let in_progress = storage.in_progress_count(assignee)?;
+                let room = capacity.saturating_sub(in_progress);
+                if room == 0 && !json && !ids_only {
+                    eprintln!(
+                        "{} is at capacity: {} in progress, limit {}",
+                        assignee, in_progress, capacity
+                    );
+                }
+                limit = Some(limit.map_or(room, |limit_val| limit_val.min(room)));
             }
+            let ready = match limit {
+                Some(0) => Vec::new(),
+                // Only the top `limit` issues are needed: keep a bounded heap
+                // instead of sorting every ready issue
+                Some(limit_val) if sort_policy != "random" => {
+                    storage.get_ready_limited(&filter, sort_policy, limit_val)?
+                }
+                _ => {
+                    let mut ready = storage.get_ready(&filter, sort_policy)?;
+
+                    // Shuffle after filtering so `-n 1 -s random` picks uniformly from
+                    // the whole filtered set, not just its head.
+                    if sort_policy == "random" {
+                        use rand::seq::SliceRandom;
+                        ready.shuffle(&mut rand::thread_rng());
+                    }
 
-            // Apply limit after every filter has run
-            if let Some(limit_val) = limit {
-                if limit_val > 0 {
-                    ready.truncate(limit_val);
+                    // Apply limit after every filter has run
+                    if let Some(limit_val) = limit {
+                        ready.truncate(limit_val);
+                    }
+                    ready
                 }
-            }
+            };
 
-            if json {
+            if ids_only {
+                print_ids(ready.iter().map(|issue| issue.id.as_str()), json)?;
+            } else if json {
                 println!("{}", serde_json::to_string_pretty(&ready)?);
             } else if group_priority {
-                print_issues_grouped_by_priority(&ready);
+                print_issues_grouped_by_priority(&ready, color);
             } else {
                 for issue in ready {
                     println!(
                         "{}: {} [priority: {}]",
-                        issue.id, issue.title, issue.priority
+                        style::id(&issue.id, color),
+                        issue.title,
+                        style::priority(issue.priority, color)
                     );
                 }
             }
@@ -3031,7 +5290,18 @@ fn run() -> Result<()> {
         }
 
         Commands::Version => {
-            println!("mb version {}", env!("CARGO_PKG_VERSION"));
+            if json {
+                let info = VersionInfo {
+                    version: env!("CARGO_PKG_VERSION"),
+                    git_commit: built_info::GIT_COMMIT_HASH,
+                    git_dirty: built_info::GIT_DIRTY,
+                    build_date: env!("BUILD_DATE"),
+                    jsonl_schema_version: types::JSONL_SCHEMA_VERSION,
+                };
+                println!("{}", serde_json::to_string_pretty(&info)?);
+            } else {
+                println!("mb version {}", env!("CARGO_PKG_VERSION"));
+            }
             Ok(())
         }
 
@@ -3043,7 +5313,7 @@ fn run() -> Result<()> {
             repack_contiguous,
             closed_issue_start,
         } => {
-            let storage = get_storage(mb_beads_dir, db)?;
+            let storage = get_storage(global_opts)?;
 
             // Log command after storage is validated
             if !mb_no_cmd_logging {
@@ -3062,32 +5332,26 @@ fn run() -> Result<()> {
 
                 if json {
                     println!("{}", serde_json::to_string_pretty(&changes)?);
+                } else if changes.is_empty() {
+                    println!("No changes needed - IDs already contiguous");
                 } else if dry_run {
-                    println!("Dry run - would make the following changes:");
+                    println!(
+                        "Dry run - would repack {} numeric ID(s) to fill gaps:",
+                        count_renamed_files(&changes)
+                    );
                     for change in &changes {
                         println!("  {}", change);
                     }
                 } else {
-                    let issue_count = changes
-                        .iter()
-                        .filter(|c| c.starts_with("Rename file:"))
-                        .count();
-                    if issue_count > 0 {
-                        println!(
-                            "Successfully repacked {} issue(s) to fill gaps",
-                            issue_count
-                        );
+                    println!(
+                        "Successfully repacked {} issue(s) to fill gaps",
+                        count_renamed_files(&changes)
+                    );
 
-                        // Patch code references if requested
-                        if mb_patch_code {
-                            if let Err(e) = code_patch::patch_code_for_migration(&id_mapping) {
-                                eprintln!("Warning: Code patching failed: {}", e);
-                            }
-                        }
-                    } else {
-                        // No changes made
-                        for change in &changes {
-                            println!("{}", change);
+                    // Patch code references if requested
+                    if mb_patch_code {
+                        if let Err(e) = code_patch::patch_code_for_migration(&id_mapping) {
+                            eprintln!("Warning: Code patching failed: {}", e);
                         }
                     }
                 }
@@ -3110,11 +5374,14 @@ fn run() -> Result<()> {
                     } else {
                         println!(
                             "Successfully migrated {} issue(s) to hash-based IDs",
-                            changes.len() / 3
+                            count_renamed_files(&changes)
                         );
                         if update_config {
                             println!("Updated config-minibeads.yaml: mb-hash-ids: true");
                         }
+                        for note in changes.iter().filter(|change| change.is_note()) {
+                            println!("{}", note);
+                        }
 
                         // Patch code references if requested
                         if mb_patch_code {
@@ -3137,10 +5404,7 @@ fn run() -> Result<()> {
                             println!("  {}", change);
                         }
                     } else {
-                        let issue_count = changes
-                            .iter()
-                            .filter(|c| c.starts_with("Rename file:"))
-                            .count();
+                        let issue_count = count_renamed_files(&changes);
                         println!(
                             "Successfully migrated {} issue(s) to numeric IDs",
                             issue_count
@@ -3169,57 +5433,90 @@ fn run() -> Result<()> {
     }
 }
 
-fn get_storage(mb_beads_dir: &Option<PathBuf>, db: &Option<PathBuf>) -> Result<Storage> {
-    // Priority order for determining minibeads directory:
-    // 1. --mb-beads-dir flag (preferred, minibeads-specific)
-    // 2. --db flag (for upstream compatibility, treated as syntactic sugar for BEADS_DIR)
-    // 3. MB_BEADS_DIR environment variable (minibeads-specific)
-    // 4. BEADS_DIR environment variable (upstream compatibility)
-    // 5. BEADS_DB environment variable (for upstream compatibility)
-    // 6. Search for .minibeads first, then .beads, in current directory and ancestors
-
-    let beads_dir = if let Some(dir) = mb_beads_dir {
-        // --mb-beads-dir: use directly
-        dir.clone()
-    } else if let Some(db_path) = db {
-        // --db: syntactic sugar for BEADS_DIR
-        // If it points to a .db file, use its parent directory
-        if db_path.extension().is_some_and(|e| e == "db") {
-            db_path
-                .parent()
-                .ok_or_else(|| anyhow::anyhow!("Invalid database path"))?
-                .to_path_buf()
-        } else {
-            db_path.clone()
-        }
-    } else if let Ok(beads_dir) = env::var("MB_BEADS_DIR") {
-        PathBuf::from(beads_dir)
-    } else if let Ok(beads_dir) = env::var("BEADS_DIR") {
-        PathBuf::from(beads_dir)
-    } else if let Ok(beads_db) = env::var("BEADS_DB") {
-        let db_path = PathBuf::from(beads_db);
-        if db_path.extension().is_some_and(|e| e == "db") {
-            db_path
-                .parent()
-                .ok_or_else(|| anyhow::anyhow!("Invalid BEADS_DB path"))?
-                .to_path_buf()
-        } else {
-            db_path
-        }
+fn get_storage(opts: &GlobalOpts) -> Result<Storage> {
+    let beads_dir =
+        match resolve_beads_dir(opts.mb_beads_dir.as_deref(), opts.db.as_deref(), |name| {
+            env::var(name).ok()
+        })? {
+            Some(dir) => dir,
+            // Search for the primary directory first, then legacy .beads
+            None => find_beads_dir()?,
+        };
+
+    open_storage(beads_dir, opts).context("Failed to open storage")
+}
+
+/// Open the database in `beads_dir` with the global `--mb-validation` and
+/// `--verbose` settings applied, so every command that creates issues reports
+/// hash-ID collisions the same way
+fn open_storage(beads_dir: PathBuf, opts: &GlobalOpts) -> Result<Storage> {
+    let mut storage = Storage::open(beads_dir)?;
+    storage.set_hash_id_warnings(opts.mb_validation != ValidationMode::Silent);
+    storage.set_verbose(opts.verbose);
+    Ok(storage)
+}
+
+/// Expand numeric shorthand like "14" to "prefix-14"; other IDs are returned
+/// unchanged
+fn expand_issue_id(storage: &Storage, id: String) -> Result<String> {
+    if id.parse::<u32>().is_ok() {
+        Ok(format!("{}-{}", storage.get_prefix()?, id))
     } else {
-        // Search for the primary directory first, then legacy .beads
-        find_beads_dir()?
-    };
+        Ok(id)
+    }
+}
 
-    Storage::open(beads_dir).context("Failed to open storage")
+fn expand_issue_ids(storage: &Storage, ids: Vec<String>) -> Result<Vec<String>> {
+    ids.into_iter()
+        .map(|id| expand_issue_id(storage, id))
+        .collect()
 }
 
-fn find_beads_dir() -> Result<PathBuf> {
-    let start = env::current_dir()?;
-    if let Some(dir) = find_storage_dir_named(&start, PRIMARY_STORAGE_DIR)? {
-        return Ok(dir);
+/// Pick the beads directory from flags and environment, or `None` to search
+/// upward from the current directory. The first one set wins:
+///
+/// 1. `--beads-dir` (alias `--mb-beads-dir`): the directory itself
+/// 2. `--db` (upstream compatibility): a `.db` file means its parent
+///    directory, anything else is the directory itself
+/// 3. `MB_BEADS_DIR`
+/// 4. `BEADS_DIR`
+/// 5. `BEADS_DB`, interpreted like `--db`
+fn resolve_beads_dir(
+    beads_dir_flag: Option<&Path>,
+    db_flag: Option<&Path>,
+    env_var: impl Fn(&str) -> Option<String>,
+) -> Result<Option<PathBuf>> {
+    if let Some(dir) = beads_dir_flag {
+        return Ok(Some(dir.to_path_buf()));
+    }
+    if let Some(db_path) = db_flag {
+        return db_path_to_dir(db_path, "--db").map(Some);
+    }
+    for name in ["MB_BEADS_DIR", "BEADS_DIR"] {
+        if let Some(dir) = env_var(name) {
+            return Ok(Some(PathBuf::from(dir)));
+        }
+    }
+    if let Some(db_path) = env_var("BEADS_DB") {
+        return db_path_to_dir(Path::new(&db_path), "BEADS_DB").map(Some);
+    }
+    Ok(None)
+}
+
+/// Upstream `--db`/`BEADS_DB` name a database file; its directory is ours
+fn db_path_to_dir(db_path: &Path, source: &str) -> Result<PathBuf> {
+    if db_path.extension().is_some_and(|e| e == "db") {
+        db_path
+            .parent()
+            .map(Path::to_path_buf)
+            .ok_or_else(|| anyhow::anyhow!("Invalid {} path: {}", source, db_path.display()))
+    } else {
+        Ok(db_path.to_path_buf())
     }
-    if let Some(dir) = find_storage_dir_named(&start, LEGACY_STORAGE_DIR)? {
+}
+
+fn find_beads_dir() -> Result<PathBuf> {
+    if let Some(dir) = find_storage_dir(&env::current_dir()?)? {
         return Ok(dir);
     }
 
@@ -3230,6 +5527,15 @@ fn find_beads_dir() -> Result<PathBuf> {
     );
 }
 
+/// Search `start` and its ancestors for the primary storage dir, then for
+/// legacy .beads
+fn find_storage_dir(start: &Path) -> Result<Option<PathBuf>> {
+    if let Some(dir) = find_storage_dir_named(start, PRIMARY_STORAGE_DIR)? {
+        return Ok(Some(dir));
+    }
+    find_storage_dir_named(start, LEGACY_STORAGE_DIR)
+}
+
 fn find_storage_dir_named(start: &Path, dir_name: &str) -> Result<Option<PathBuf>> {
     let mut current = start.to_path_buf();
     loop {
@@ -3307,6 +5613,81 @@ fn detect_host() -> String {
     "unknown-host".to_string()
 }
 
+/// Format an `update --append-notes --timestamped` entry as a list item:
+/// `- 2025-01-02T10:00Z (alice): text`
+fn timestamped_note(text: &str, actor: &str, now: chrono::DateTime<chrono::Utc>) -> String {
+    format!("- {} ({}): {}", now.format("%Y-%m-%dT%H:%MZ"), actor, text)
+}
+
+/// Apply a guarded status transition (`start`, `block`, `unblock`) to each
+/// issue in turn, stopping at the first one it does not apply to
+fn transition_issues(
+    storage: &storage::Storage,
+    issue_ids: &[String],
+    transition: Transition,
+    note: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    if issue_ids.is_empty() {
+        anyhow::bail!(
+            "No issue IDs provided. Usage: mb {} <issue-id> [issue-ids...]",
+            transition.as_str()
+        );
+    }
+
+    let mut updated = Vec::new();
+    for issue_id in issue_ids {
+        updated.push(storage.transition_issue(issue_id, transition, note)?);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&updated)?);
+    } else {
+        for issue in &updated {
+            println!(
+                "{}: {} is now {}",
+                transition.as_str(),
+                issue.id,
+                issue.status
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Pick the actor recorded for provenance (comment authors, timestamped
+/// notes): the `--actor` flag, then `mb-default-actor` from
+/// config-minibeads.yaml, then `$USER`, then `"user"`. Returns the actor and
+/// where it came from.
+fn pick_actor(
+    flag: Option<&str>,
+    config: Option<String>,
+    user_env: Option<String>,
+) -> (String, &'static str) {
+    let non_empty = |value: String| (!value.trim().is_empty()).then_some(value);
+    if let Some(actor) = flag.map(str::to_string).and_then(non_empty) {
+        (actor, "--actor flag")
+    } else if let Some(actor) = config.and_then(non_empty) {
+        (actor, "mb-default-actor in config-minibeads.yaml")
+    } else if let Some(actor) = user_env.and_then(non_empty) {
+        (actor, "$USER")
+    } else {
+        ("user".to_string(), "default")
+    }
+}
+
+/// [`pick_actor`] against this database's config and the environment
+fn resolve_default_actor(
+    flag: Option<&str>,
+    storage: &storage::Storage,
+) -> Result<(String, &'static str)> {
+    Ok(pick_actor(
+        flag,
+        storage.default_actor()?,
+        env::var("USER").ok(),
+    ))
+}
+
 /// Resolve the claim identity (`assignee` value) for a claim.
 ///
 /// The base identity is the detected hostname, or the `--as` override when given.
@@ -3457,3 +5838,125 @@ Run mb create "My first issue" to create your first issue.
 "#
     );
 }
+
+#[cfg(test)]
+mod resolve_beads_dir_tests {
+    use super::*;
+
+    fn resolve(beads_dir: Option<&str>, db: Option<&str>, env: &[(&str, &str)]) -> Option<PathBuf> {
+        resolve_beads_dir(beads_dir.map(Path::new), db.map(Path::new), |name| {
+            env.iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        })
+        .unwrap()
+    }
+
+    const ALL_ENV: &[(&str, &str)] = &[
+        ("MB_BEADS_DIR", "/env/mb"),
+        ("BEADS_DIR", "/env/beads"),
+        ("BEADS_DB", "/env/db/beads.db"),
+    ];
+
+    #[test]
+    fn beads_dir_flag_wins_over_everything() {
+        let dir = resolve(Some("/flag/.beads"), Some("/db/beads.db"), ALL_ENV);
+        assert_eq!(dir, Some(PathBuf::from("/flag/.beads")));
+    }
+
+    #[test]
+    fn db_flag_uses_the_parent_of_a_db_file() {
+        assert_eq!(
+            resolve(None, Some("/db/beads.db"), ALL_ENV),
+            Some(PathBuf::from("/db"))
+        );
+        assert_eq!(
+            resolve(None, Some("/db/.beads"), &[]),
+            Some(PathBuf::from("/db/.beads"))
+        );
+    }
+
+    #[test]
+    fn environment_variables_apply_in_order() {
+        assert_eq!(resolve(None, None, ALL_ENV), Some(PathBuf::from("/env/mb")));
+        assert_eq!(
+            resolve(None, None, &ALL_ENV[1..]),
+            Some(PathBuf::from("/env/beads"))
+        );
+        assert_eq!(
+            resolve(None, None, &ALL_ENV[2..]),
+            Some(PathBuf::from("/env/db"))
+        );
+        assert_eq!(
+            resolve(None, None, &[("BEADS_DB", "/env/.beads")]),
+            Some(PathBuf::from("/env/.beads"))
+        );
+    }
+
+    #[test]
+    fn nothing_set_means_search() {
+        assert_eq!(resolve(None, None, &[]), None);
+    }
+}
+
+#[cfg(test)]
+mod template_tests {
+    use super::*;
+
+    #[test]
+    fn renders_placeholders_and_escapes() {
+        let mut issue = Issue::new(
+            "demo-3".to_string(),
+            "Fix it".to_string(),
+            1,
+            IssueType::Bug,
+        );
+        issue.labels = vec!["backend".to_string(), "auth".to_string()];
+
+        let template =
+            OutputTemplate::parse(r"{id}\t{priority}\t{title} {{{labels}}}{closed_at}").unwrap();
+        assert_eq!(template.render(&issue), "demo-3\t1\tFix it {backend,auth}");
+    }
+
+    #[test]
+    fn rejects_unknown_placeholders_and_stray_braces() {
+        let err = OutputTemplate::parse("{id} {owner}").err().unwrap();
+        assert!(err
+            .to_string()
+            .contains("Unknown template placeholder '{owner}'"));
+        assert_eq!(error::exit_code(&err), 4);
+
+        assert!(OutputTemplate::parse("{id").is_err());
+        assert!(OutputTemplate::parse("id}").is_err());
+    }
+}
+
+#[cfg(test)]
+mod actor_tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn formats_entry_with_actor() {
+        let now = chrono::Utc.with_ymd_and_hms(2025, 1, 2, 10, 0, 42).unwrap();
+        assert_eq!(
+            timestamped_note("fixed the flaky test", "alice", now),
+            "- 2025-01-02T10:00Z (alice): fixed the flaky test"
+        );
+    }
+
+    #[test]
+    fn actor_prefers_flag_then_config_then_user() {
+        let some = |name: &str| Some(name.to_string());
+        assert_eq!(
+            pick_actor(Some("flag"), some("config"), some("env")).0,
+            "flag"
+        );
+        assert_eq!(pick_actor(None, some("config"), some("env")).0, "config");
+        assert_eq!(pick_actor(Some(""), None, some("env")).0, "env");
+        assert_eq!(
+            pick_actor(None, None, None),
+            ("user".to_string(), "default")
+        );
+    }
+}
@@ -1,34 +1,116 @@
-use crate::format::{issue_to_markdown, markdown_to_issue};
+use crate::error;
+use crate::format::{
+    issue_to_markdown_with_sections, markdown_to_issue_with_sections, SectionHeaders,
+};
 use crate::hash;
 use crate::lock::Lock;
 use crate::types::{
-    BlockedIssue, Comment, DependencyType, EditField, Issue, IssueType, Stats, Status,
+    BlockedIssue, Blocker, BlockerStatuses, ClosureDirection, Comment, DependencyGraph,
+    DependencyType, EditField, FieldBlame, GraphEdge, GraphNode, HistoryEntry, ImportMode, Issue,
+    IssueId, IssueReference, IssueType, JsonlFlavor, JsonlHeader, ListSort, Readiness,
+    ReadinessReport, Resolution, Stats, Status, Transition, UpstreamIssue,
 };
 use anyhow::{Context, Result};
 use regex::Regex;
 use sha2::{Digest, Sha256};
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
 pub struct Storage {
     beads_dir: PathBuf,
     issues_dir: PathBuf,
+    sections: SectionHeaders,
+    strict_deps: bool,
+    ignore_missing_blockers: bool,
+    include_archived: bool,
+    hash_id_warnings: bool,
+    verbose: bool,
+}
+
+/// Check an explicitly given or imported issue ID: it must be safe to use as
+/// a file name and match `mb-id-pattern`, if one is configured.
+fn validate_id(id: &str, pattern: Option<&Regex>) -> Result<()> {
+    IssueId::validate_explicit(id)?;
+    if let Some(pattern) = pattern.filter(|pattern| !pattern.is_match(id)) {
+        return Err(error::invalid_input(format!(
+            "Invalid issue ID '{}': does not match the ID pattern {} (mb-id-pattern in config-minibeads.yaml)",
+            id,
+            pattern.as_str()
+        )));
+    }
+    Ok(())
+}
+
+/// `git <args> -- <file name>`, run from the file's directory so the path is
+/// relative to wherever the repository root is
+fn git_command(path: &Path, args: &[&str]) -> std::process::Command {
+    let mut command = std::process::Command::new("git");
+    command
+        .current_dir(path.parent().expect("issue file has a directory"))
+        .args(args)
+        .arg("--")
+        .arg(path.file_name().expect("issue file has a name"));
+    command
+}
+
+/// Run [`git_command`] and return its stdout, failing with git's stderr
+fn run_git_on_file(path: &Path, args: &[&str]) -> Result<String> {
+    let output = git_command(path, args)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args[0]))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The commit of a single-line `git blame --line-porcelain` output; `None`
+/// for a line with uncommitted changes
+fn parse_blame_commit(output: &str) -> Option<HistoryEntry> {
+    let mut lines = output.lines();
+    let commit = lines.next()?.split_whitespace().next()?;
+    if commit.chars().all(|c| c == '0') {
+        return None;
+    }
+    let mut entry = HistoryEntry {
+        commit: commit.chars().take(7).collect(),
+        date: String::new(),
+        author: String::new(),
+        summary: String::new(),
+    };
+    for line in lines {
+        if let Some(author) = line.strip_prefix("author ") {
+            entry.author = author.to_string();
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            if let Some(time) = time
+                .parse()
+                .ok()
+                .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            {
+                entry.date = time.format("%Y-%m-%d").to_string();
+            }
+        } else if let Some(summary) = line.strip_prefix("summary ") {
+            entry.summary = summary.to_string();
+        }
+    }
+    Some(entry)
 }
 
-/// Replace issue ID references in text fields using word boundaries
-///
-/// This function replaces all occurrences of issue IDs in text, but only when they appear
-/// as standalone tokens (delimited by non-alphanumeric characters or word boundaries).
-/// This prevents matching IDs that are embedded in longer strings.
-///
-/// Uses a HashMap for O(1) lookup of replacement mappings.
 /// Extract the trailing numeric component of an issue ID (the part after the
-/// last hyphen) if it is a plain integer, e.g. `minibeads-42` -> `Some(42)`.
-/// Hash-based IDs like `minibeads-a3f9` return `None`.
+/// last hyphen, or the whole ID without one) if it is all ASCII digits, e.g.
+/// `minibeads-42` -> `Some(42)`. Hash-based IDs like `minibeads-a3f9` return
+/// `None`.
 fn numeric_id_suffix(id: &str) -> Option<u32> {
     let suffix = id.rsplit_once('-').map(|(_, s)| s).unwrap_or(id);
-    suffix.parse::<u32>().ok()
+    if !suffix.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    suffix.parse().ok()
 }
 
 /// Ordering for `list`: numeric IDs first (ascending, so the most recent
@@ -42,28 +124,21 @@ fn compare_for_list(a: &Issue, b: &Issue) -> std::cmp::Ordering {
     }
 }
 
+/// Replace issue ID references in text fields using word boundaries
+///
+/// This function replaces all occurrences of issue IDs in text, but only when they appear
+/// as standalone tokens (delimited by non-alphanumeric characters or word boundaries).
+/// This prevents matching IDs that are embedded in longer strings.
+///
+/// Uses a HashMap for O(1) lookup of replacement mappings.
 fn replace_issue_ids_in_text(text: &str, id_mapping: &HashMap<String, String>) -> String {
     if text.is_empty() || id_mapping.is_empty() {
         return text.to_string();
     }
 
-    // Build a regex pattern that matches any issue ID with word boundaries
-    // Pattern: \b(prefix1-suffix1|prefix2-suffix2|...)\b
-    let mut patterns: Vec<String> = id_mapping.keys().map(|id| regex::escape(id)).collect();
-
-    if patterns.is_empty() {
-        return text.to_string();
-    }
-
-    // Sort by length (longest first) to avoid partial matches
-    patterns.sort_by_key(|b| std::cmp::Reverse(b.len()));
-
-    let pattern = format!(r"\b({})\b", patterns.join("|"));
-
     // Compile regex (note: in production code, this could be cached)
-    let re = match Regex::new(&pattern) {
-        Ok(r) => r,
-        Err(_) => return text.to_string(), // Fallback: return original text
+    let Some(re) = issue_id_regex(id_mapping.keys()) else {
+        return text.to_string(); // Fallback: return original text
     };
 
     // Replace all matches using the mapping
@@ -77,6 +152,23 @@ fn replace_issue_ids_in_text(text: &str, id_mapping: &HashMap<String, String>) -
     .to_string()
 }
 
+/// Regex matching any of `ids` as a standalone token, capturing the ID in
+/// group 1. `None` when `ids` is empty or the pattern does not compile.
+fn issue_id_regex<'a>(ids: impl IntoIterator<Item = &'a String>) -> Option<Regex> {
+    // Build a regex pattern that matches any issue ID with word boundaries
+    // Pattern: \b(prefix1-suffix1|prefix2-suffix2|...)\b
+    let mut patterns: Vec<String> = ids.into_iter().map(|id| regex::escape(id)).collect();
+
+    if patterns.is_empty() {
+        return None;
+    }
+
+    // Sort by length (longest first) to avoid partial matches
+    patterns.sort_by_key(|b| std::cmp::Reverse(b.len()));
+
+    Regex::new(&format!(r"\b({})\b", patterns.join("|"))).ok()
+}
+
 /// Apply ID replacements to all text fields of an issue
 fn replace_ids_in_issue_text(issue: &mut Issue, id_mapping: &HashMap<String, String>) {
     issue.title = replace_issue_ids_in_text(&issue.title, id_mapping);
@@ -86,16 +178,437 @@ fn replace_ids_in_issue_text(issue: &mut Issue, id_mapping: &HashMap<String, Str
     issue.notes = replace_issue_ids_in_text(&issue.notes, id_mapping);
 }
 
+/// Options for `Storage::generate_report`
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// Start of the "recently closed" window
+    pub since: chrono::DateTime<chrono::Utc>,
+}
+
+/// Old and new ID of each issue moved by `Storage::import_moved_issues`, in
+/// the order they were given
+pub type MovedIds = Vec<(String, String)>;
+
+/// One planned or applied change from a rename, migration, move, repair, or
+/// `gc`.
+///
+/// `Display` gives the one-line description printed by dry runs; `Serialize`
+/// gives a `{"kind": ...}` object for `--json`. Issue fields are IDs, not
+/// file names.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    /// Change a config value
+    UpdateConfig {
+        file: String,
+        key: String,
+        from: String,
+        to: String,
+    },
+    /// Rename an issue's file
+    RenameFile { from: String, to: String },
+    /// Rewrite the ID in an issue's frontmatter
+    UpdateId { from: String, to: String },
+    /// Point one of `issue`'s dependencies at a new ID
+    UpdateDependency {
+        issue: String,
+        from: String,
+        to: String,
+    },
+    /// Rewrite mentions of an ID in `issue`'s text fields
+    UpdateTextReferences {
+        issue: String,
+        from: String,
+        to: String,
+    },
+    /// Drop a dependency on an issue that does not exist
+    RemoveBrokenReference { issue: String, target: String },
+    /// Create a moved issue in the target database
+    CreateIssue { id: String, from: String },
+    /// Delete a moved issue from the source database
+    DeleteIssue { id: String },
+    /// Note (no change): a moved issue depends on one missing in the target
+    MissingInTarget { issue: String, depends_on: String },
+    /// Note (no change): an issue left in the source depends on a moved one
+    DependsOnMoved { issue: String, depends_on: String },
+    /// Note (no change): an issue depends on an archived one, which is not a
+    /// broken reference
+    DependsOnArchived { issue: String, depends_on: String },
+    /// Remove an issue file that is empty or whitespace-only (`file` is
+    /// relative to the database directory, as are the other removals)
+    RemoveEmptyFile { file: String },
+    /// Remove an issue file that does not parse
+    RemoveUnparseableFile { file: String, error: String },
+    /// Remove a temporary file orphaned by an interrupted atomic write
+    RemoveTempFile { file: String },
+    /// Note (no change): of `migrated` new hash IDs, `retried` collided and
+    /// needed another nonce or length; the longest is `max_length` characters
+    HashIdCollisions {
+        migrated: usize,
+        retried: usize,
+        max_length: usize,
+    },
+}
+
+impl Change {
+    /// Whether this only reports a situation rather than changing anything
+    pub fn is_note(&self) -> bool {
+        matches!(
+            self,
+            Change::MissingInTarget { .. }
+                | Change::DependsOnMoved { .. }
+                | Change::DependsOnArchived { .. }
+                | Change::HashIdCollisions { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::UpdateConfig {
+                file,
+                key,
+                from,
+                to,
+            } => write!(f, "Update {}: {}: {} -> {}", file, key, from, to),
+            Change::RenameFile { from, to } => write!(f, "Rename file: {}.md -> {}.md", from, to),
+            Change::UpdateId { from, to } => {
+                write!(f, "Update ID in frontmatter: {} -> {}", from, to)
+            }
+            Change::UpdateDependency { issue, from, to } => {
+                write!(f, "Update dependency in {}: {} -> {}", issue, from, to)
+            }
+            Change::UpdateTextReferences { issue, from, to } => {
+                write!(f, "Update text references in {}: {} -> {}", issue, from, to)
+            }
+            Change::RemoveBrokenReference { issue, target } => write!(
+                f,
+                "Remove broken reference in {}: {} (does not exist)",
+                issue, target
+            ),
+            Change::CreateIssue { id, from } => write!(f, "Create {} (from {})", id, from),
+            Change::DeleteIssue { id } => write!(f, "Delete {} from source", id),
+            Change::RemoveEmptyFile { file } => write!(f, "Remove empty file: {}", file),
+            Change::RemoveUnparseableFile { file, error } => {
+                write!(f, "Remove unparseable file: {} ({})", file, error)
+            }
+            Change::RemoveTempFile { file } => write!(f, "Remove orphaned temp file: {}", file),
+            Change::MissingInTarget { issue, depends_on } => write!(
+                f,
+                "Note: {} depends on {}, which does not exist in the target",
+                issue, depends_on
+            ),
+            Change::DependsOnMoved { issue, depends_on } => write!(
+                f,
+                "Note: {} in source still depends on moved {}",
+                issue, depends_on
+            ),
+            Change::DependsOnArchived { issue, depends_on } => write!(
+                f,
+                "Note: {} depends on archived {} (kept)",
+                issue, depends_on
+            ),
+            Change::HashIdCollisions {
+                migrated,
+                retried,
+                max_length,
+            } => write!(
+                f,
+                "Note: {} of {} hash ID(s) needed a retry after a collision; the longest is {} characters",
+                retried, migrated, max_length
+            ),
+        }
+    }
+}
+
 pub fn is_github_issue_ref(value: &str) -> bool {
     value.starts_with("https://github.com/") && value.contains("/issues/")
 }
 
+/// Issue selection shared by `list`, `export`, and the commands built on
+/// them, so every command accepts the same filters. The default selects
+/// every issue.
+#[derive(Debug, Clone, Default)]
+pub struct IssueFilter {
+    pub status: Option<Status>,
+    /// Any of these priorities
+    pub priority: Option<Vec<i32>>,
+    /// Any of these types
+    pub issue_type: Option<Vec<IssueType>>,
+    /// Only closed issues with this resolution
+    pub resolution: Option<Resolution>,
+    pub assignee: Option<String>,
+    /// Only issues with no assignee
+    pub unassigned: bool,
+    /// Must have ALL of these labels
+    pub labels: Vec<String>,
+    /// Only issues linked to a GitHub issue
+    pub github: bool,
+    /// Only these exact issue IDs
+    pub ids: Option<Vec<String>>,
+    /// Case-insensitive title substring
+    pub title: Option<String>,
+    /// Only direct children of this parent issue
+    pub parent: Option<String>,
+    /// Only ready or only blocked issues. Deciding this needs the status of
+    /// every blocker, so storage applies it on top of [`IssueFilter::matches`].
+    pub readiness: Option<Readiness>,
+    /// Order of the result, applied before any limit
+    pub sort: ListSort,
+    /// Reverse the whole order, ties included
+    pub reverse: bool,
+}
+
+/// Builder methods, for filters assembled in code rather than from CLI options.
+/// Each one narrows the selection; `IssueFilter::new()` selects everything.
+#[allow(dead_code)] // library API; the CLI fills the fields from its options
+impl IssueFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Keep issues with any of `priorities`
+    pub fn priorities(mut self, priorities: impl IntoIterator<Item = i32>) -> Self {
+        self.priority = Some(priorities.into_iter().collect());
+        self
+    }
+
+    pub fn issue_type(mut self, issue_type: IssueType) -> Self {
+        self.issue_type = Some(vec![issue_type]);
+        self
+    }
+
+    /// Keep issues of any of `issue_types`
+    pub fn issue_types(mut self, issue_types: impl IntoIterator<Item = IssueType>) -> Self {
+        self.issue_type = Some(issue_types.into_iter().collect());
+        self
+    }
+
+    pub fn resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = Some(resolution);
+        self
+    }
+
+    pub fn assignee(mut self, assignee: impl Into<String>) -> Self {
+        self.assignee = Some(assignee.into());
+        self
+    }
+
+    /// Keep only issues with no assignee
+    pub fn unassigned(mut self) -> Self {
+        self.unassigned = true;
+        self
+    }
+
+    /// Require `label`; repeated calls require every label
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    /// Keep only issues linked to a GitHub issue
+    pub fn github_only(mut self) -> Self {
+        self.github = true;
+        self
+    }
+
+    pub fn ids(mut self, ids: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.ids = Some(ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Case-insensitive title substring
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Keep only direct children of `parent`
+    pub fn parent(mut self, parent: impl Into<String>) -> Self {
+        self.parent = Some(parent.into());
+        self
+    }
+
+    pub fn readiness(mut self, readiness: Readiness) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    pub fn sort(mut self, sort: ListSort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Reverse the sort order
+    pub fn reversed(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+}
+
+impl IssueFilter {
+    /// Whether `issue` passes every filter except `readiness`
+    pub fn matches(&self, issue: &Issue) -> bool {
+        self.status.is_none_or(|s| issue.status == s)
+            && self
+                .priority
+                .as_ref()
+                .is_none_or(|priorities| priorities.contains(&issue.priority))
+            && self
+                .issue_type
+                .as_ref()
+                .is_none_or(|types| types.contains(&issue.issue_type))
+            && self.resolution.is_none_or(|r| issue.resolution == Some(r))
+            && self.assignee.as_ref().is_none_or(|a| issue.assignee == *a)
+            && (!self.unassigned || issue.assignee.is_empty())
+            && self.labels.iter().all(|label| issue.labels.contains(label))
+            && (!self.github
+                || issue
+                    .external_ref
+                    .as_deref()
+                    .is_some_and(is_github_issue_ref))
+            && self.ids.as_ref().is_none_or(|ids| ids.contains(&issue.id))
+            && self
+                .title
+                .as_ref()
+                .is_none_or(|title| issue.title.to_lowercase().contains(&title.to_lowercase()))
+            && self.parent.as_ref().is_none_or(|parent| {
+                issue
+                    .depends_on
+                    .get(parent)
+                    .is_some_and(|dep_type| *dep_type == DependencyType::ParentChild)
+            })
+    }
+
+    /// `matches` plus the `readiness` filter, decided against `blockers`
+    fn matches_with_blockers(&self, issue: &Issue, blockers: &BlockerStatuses) -> bool {
+        self.matches(issue)
+            && self
+                .readiness
+                .is_none_or(|r| issue.matches_readiness(r, blockers))
+    }
+}
+
+/// A new issue for [`Storage::create_issue`]. Only the title is required;
+/// priority and type fall back as described at [`Storage::create_defaults`].
+#[derive(Debug, Clone, Default)]
+pub struct IssueSpec {
+    pub title: String,
+    pub description: String,
+    pub design: String,
+    pub acceptance_criteria: String,
+    pub notes: String,
+    /// 0 (highest) to 4
+    pub priority: Option<i32>,
+    pub issue_type: Option<IssueType>,
+    pub assignee: String,
+    pub labels: Vec<String>,
+    pub external_ref: Option<String>,
+    /// Use this ID instead of generating one; it is validated as for
+    /// `mb create --id`
+    pub id: Option<String>,
+    pub dependencies: Vec<(String, DependencyType)>,
+    /// Replace an existing issue with the same ID instead of failing
+    pub overwrite: bool,
+    /// Backdate the issue (and its file mtime) for imports and backfills
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Builder methods, for issues assembled in code rather than from CLI options
+#[allow(dead_code)] // library API; the CLI fills the fields from its options
+impl IssueSpec {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    pub fn design(mut self, design: impl Into<String>) -> Self {
+        self.design = design.into();
+        self
+    }
+
+    pub fn acceptance_criteria(mut self, acceptance_criteria: impl Into<String>) -> Self {
+        self.acceptance_criteria = acceptance_criteria.into();
+        self
+    }
+
+    pub fn notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = notes.into();
+        self
+    }
+
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    pub fn issue_type(mut self, issue_type: IssueType) -> Self {
+        self.issue_type = Some(issue_type);
+        self
+    }
+
+    pub fn assignee(mut self, assignee: impl Into<String>) -> Self {
+        self.assignee = assignee.into();
+        self
+    }
+
+    /// Add a label; repeat for several
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    pub fn external_ref(mut self, external_ref: impl Into<String>) -> Self {
+        self.external_ref = Some(external_ref.into());
+        self
+    }
+
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Depend on another issue; repeat for several
+    pub fn depends_on(mut self, id: impl Into<String>, dep_type: DependencyType) -> Self {
+        self.dependencies.push((id.into(), dep_type));
+        self
+    }
+
+    pub fn overwrite(mut self) -> Self {
+        self.overwrite = true;
+        self
+    }
+
+    pub fn created_at(mut self, created_at: chrono::DateTime<chrono::Utc>) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+}
+
 impl Storage {
     /// Get the beads directory path
     pub fn get_beads_dir(&self) -> PathBuf {
         self.beads_dir.clone()
     }
 
+    /// Path of an active issue's markdown file (whether or not it exists)
+    pub fn issue_path(&self, id: &str) -> PathBuf {
+        self.issues_dir.join(format!("{}.md", id))
+    }
+
     fn config_path(&self) -> PathBuf {
         self.beads_dir.join("config.yaml")
     }
@@ -138,9 +651,17 @@ impl Storage {
         // Ensure .gitignore exists and has required entries
         ensure_gitignore(&beads_dir)?;
 
+        let sections = load_section_headers(&beads_dir)?;
+        let strict_deps = load_strict_deps(&beads_dir)?;
         Ok(Self {
             beads_dir,
             issues_dir,
+            sections,
+            strict_deps,
+            ignore_missing_blockers: false,
+            include_archived: false,
+            hash_id_warnings: true,
+            verbose: false,
         })
     }
 
@@ -171,9 +692,17 @@ impl Storage {
         // Ensure .gitignore exists and has required entries
         ensure_gitignore(&beads_dir)?;
 
+        let sections = load_section_headers(&beads_dir)?;
+        let strict_deps = load_strict_deps(&beads_dir)?;
         Ok(Self {
             beads_dir,
             issues_dir,
+            sections,
+            strict_deps,
+            ignore_missing_blockers: false,
+            include_archived: false,
+            hash_id_warnings: true,
+            verbose: false,
         })
     }
 
@@ -245,18 +774,88 @@ impl Storage {
             .collect())
     }
 
-    /// Check if hash-based IDs are enabled in config-minibeads.yaml
-    fn use_hash_ids(&self) -> Result<bool> {
-        let config_path = self.beads_dir.join("config-minibeads.yaml");
+    /// Section headers configured for this database
+    pub fn section_headers(&self) -> &SectionHeaders {
+        &self.sections
+    }
 
-        if !config_path.exists() {
-            return Ok(false); // Default to false if no config
-        }
+    /// Serialize an issue using the configured section headers
+    fn to_markdown(&self, issue: &Issue) -> Result<String> {
+        issue_to_markdown_with_sections(issue, &self.sections)
+    }
 
-        let content =
-            fs::read_to_string(&config_path).context("Failed to read config-minibeads.yaml")?;
-        let config: HashMap<String, String> =
-            serde_yaml::from_str(&content).context("Failed to parse config-minibeads.yaml")?;
+    /// Turn missing dependency targets into errors for this invocation,
+    /// regardless of `mb-strict-deps` (used by `--strict-deps`)
+    pub fn set_strict_deps(&mut self, strict: bool) {
+        self.strict_deps = strict;
+    }
+
+    /// Treat `blocks` edges whose target issue does not exist as resolved
+    /// when deciding readiness (used by `--ignore-missing-blockers`)
+    pub fn set_ignore_missing_blockers(&mut self, ignore: bool) {
+        self.ignore_missing_blockers = ignore;
+    }
+
+    /// Also read issues from the archive in listings and exports (used by
+    /// `--include-archived`)
+    pub fn set_include_archived(&mut self, include: bool) {
+        self.include_archived = include;
+    }
+
+    /// Warn on stderr when hash-ID collisions force a longer ID than the
+    /// database size calls for (on by default; off with `--mb-validation silent`)
+    pub fn set_hash_id_warnings(&mut self, warn: bool) {
+        self.hash_id_warnings = warn;
+    }
+
+    /// Log diagnostic details, such as the length and nonce of each new hash
+    /// ID, to stderr (used by `--verbose`)
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
+    /// Write an issue file and set its mtime to the issue's `updated_at`, so
+    /// the two always agree (sync compares markdown mtimes against JSONL
+    /// `updated_at`), including when `updated_at` is not the current time
+    fn write_issue_file(&self, path: &Path, issue: &Issue) -> Result<()> {
+        write_atomic(path, self.to_markdown(issue)?)?;
+        set_file_mtime_from_issue(path, issue)
+    }
+
+    /// Parse an issue file using the configured section headers
+    fn parse_markdown(&self, issue_id: &str, content: &str) -> Result<Issue> {
+        markdown_to_issue_with_sections(issue_id, content, &self.sections)
+    }
+
+    /// The key/value pairs in config-minibeads.yaml (empty if there is none)
+    fn read_minibeads_config(&self) -> Result<HashMap<String, String>> {
+        load_minibeads_config(&self.beads_dir)
+    }
+
+    /// The `mb-default-actor` from config-minibeads.yaml, if set and non-empty
+    pub fn default_actor(&self) -> Result<Option<String>> {
+        let config = self.read_minibeads_config()?;
+
+        Ok(config
+            .get("mb-default-actor")
+            .map(|actor| actor.trim().to_string())
+            .filter(|actor| !actor.is_empty()))
+    }
+
+    /// Persist `actor` as `mb-default-actor` in config-minibeads.yaml
+    pub fn set_default_actor(&self, actor: &str) -> Result<()> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        upsert_yaml_key_value(
+            &self.beads_dir.join("config-minibeads.yaml"),
+            "mb-default-actor",
+            actor,
+        )
+    }
+
+    /// Check if hash-based IDs are enabled in config-minibeads.yaml
+    fn use_hash_ids(&self) -> Result<bool> {
+        let config = self.read_minibeads_config()?;
 
         // Parse mb-hash-ids field (default to false if not present)
         match config.get("mb-hash-ids") {
@@ -267,16 +866,7 @@ impl Storage {
 
     /// Get hash encoding format from config-minibeads.yaml
     fn get_hash_encoding(&self) -> Result<hash::HashEncoding> {
-        let config_path = self.beads_dir.join("config-minibeads.yaml");
-
-        if !config_path.exists() {
-            return Ok(hash::HashEncoding::Base36); // Default to base36 if no config
-        }
-
-        let content =
-            fs::read_to_string(&config_path).context("Failed to read config-minibeads.yaml")?;
-        let config: HashMap<String, String> =
-            serde_yaml::from_str(&content).context("Failed to parse config-minibeads.yaml")?;
+        let config = self.read_minibeads_config()?;
 
         // Parse hash-encoding field (default to base36 if not present)
         match config.get("hash-encoding") {
@@ -295,6 +885,131 @@ impl Storage {
         }
     }
 
+    /// `mb-sync-tolerance-ms` from config-minibeads.yaml: how far apart, in
+    /// milliseconds, a markdown mtime and a JSONL `updated_at` may be and
+    /// still count as the same time in `mb sync`. `None` if unset.
+    pub fn sync_tolerance_ms(&self) -> Result<Option<u64>> {
+        let config = self.read_minibeads_config()?;
+
+        match config.get("mb-sync-tolerance-ms") {
+            Some(value) => match value.trim().parse::<u64>() {
+                Ok(ms) => Ok(Some(ms)),
+                Err(_) => {
+                    eprintln!(
+                        "Warning: Invalid mb-sync-tolerance-ms value '{}' in config-minibeads.yaml, using the default",
+                        value
+                    );
+                    Ok(None)
+                }
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// `mb-default-priority` from config-minibeads.yaml: the priority `create`
+    /// uses when `--priority` is not given. `None` if unset or not 0-4.
+    pub fn default_priority(&self) -> Result<Option<i32>> {
+        let config = self.read_minibeads_config()?;
+
+        let Some(value) = config.get("mb-default-priority") else {
+            return Ok(None);
+        };
+        match value.trim().parse::<i32>() {
+            Ok(priority) if (0..=4).contains(&priority) => Ok(Some(priority)),
+            _ => {
+                eprintln!(
+                    "Warning: Invalid mb-default-priority value '{}' in config-minibeads.yaml (expected 0-4), using 2",
+                    value
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// `mb-default-type` from config-minibeads.yaml: the issue type `create`
+    /// uses when `--type` is not given. `None` if unset or not a known type.
+    pub fn default_issue_type(&self) -> Result<Option<IssueType>> {
+        let config = self.read_minibeads_config()?;
+
+        let Some(value) = config.get("mb-default-type") else {
+            return Ok(None);
+        };
+        match value.trim().parse::<IssueType>() {
+            Ok(issue_type) => Ok(Some(issue_type)),
+            Err(_) => {
+                eprintln!(
+                    "Warning: Invalid mb-default-type value '{}' in config-minibeads.yaml, using task",
+                    value
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// The priority and type for a new issue: explicit values win, then
+    /// `mb-default-priority` / `mb-default-type`, then 2 and `task`
+    pub fn create_defaults(
+        &self,
+        priority: Option<i32>,
+        issue_type: Option<IssueType>,
+    ) -> Result<(i32, IssueType)> {
+        let priority = match priority {
+            Some(priority) => priority,
+            None => self.default_priority()?.unwrap_or(2),
+        };
+        let issue_type = match issue_type {
+            Some(issue_type) => issue_type,
+            None => self.default_issue_type()?.unwrap_or(IssueType::Task),
+        };
+        Ok((priority, issue_type))
+    }
+
+    /// `mb-id-pattern` from config-minibeads.yaml: the regex explicit and
+    /// imported issue IDs must match. `None` if unset or not a valid regex,
+    /// in which case any ID that is safe as a file name is accepted, as the
+    /// prefix (`mb init --prefix`) may use any such characters.
+    pub fn id_pattern(&self) -> Result<Option<Regex>> {
+        let config = self.read_minibeads_config()?;
+
+        let Some(value) = config.get("mb-id-pattern") else {
+            return Ok(None);
+        };
+        match Regex::new(value.trim()) {
+            Ok(pattern) => Ok(Some(pattern)),
+            Err(e) => {
+                eprintln!(
+                    "Warning: Invalid mb-id-pattern value '{}' in config-minibeads.yaml ({}), ignoring it",
+                    value, e
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// `mb-hash-min-length` from config-minibeads.yaml: the hash length new IDs
+    /// start at instead of the adaptive one (collisions still lengthen them).
+    /// `None` if unset, or outside the range the hash encoding allows.
+    pub fn hash_min_length(&self) -> Result<Option<usize>> {
+        let config = self.read_minibeads_config()?;
+
+        let Some(value) = config.get("mb-hash-min-length") else {
+            return Ok(None);
+        };
+        let range = self.get_hash_encoding()?.length_range();
+        match value.trim().parse::<usize>() {
+            Ok(length) if range.contains(&length) => Ok(Some(length)),
+            _ => {
+                eprintln!(
+                    "Warning: Invalid mb-hash-min-length value '{}' in config-minibeads.yaml (expected {}..={} for this hash-encoding), using the adaptive length",
+                    value,
+                    range.start(),
+                    range.end()
+                );
+                Ok(None)
+            }
+        }
+    }
+
     /// Infer prefix from existing issues in the filesystem
     fn infer_prefix_from_issues(&self) -> Result<String> {
         let entries = fs::read_dir(&self.issues_dir).context("Failed to read issues directory")?;
@@ -306,9 +1021,8 @@ impl Storage {
             let name_str = name.to_string_lossy();
 
             if let Some(issue_id) = name_str.strip_suffix(".md") {
-                if let Some(pos) = issue_id.rfind('-') {
-                    let prefix = &issue_id[..pos];
-                    *prefixes.entry(prefix.to_string()).or_insert(0) += 1;
+                if let Ok(issue_id) = IssueId::parse(issue_id) {
+                    *prefixes.entry(issue_id.prefix().to_string()).or_insert(0) += 1;
                 }
             }
         }
@@ -321,22 +1035,23 @@ impl Storage {
             .ok_or_else(|| anyhow::anyhow!("No issues found to infer prefix"))
     }
 
-    /// Get the next issue number
+    /// Get the next issue number (archived issues count, so their numbers
+    /// are never reused)
     fn get_next_number(&self, prefix: &str) -> Result<u32> {
         let entries = fs::read_dir(&self.issues_dir).context("Failed to read issues directory")?;
 
-        let mut max_num = 0;
+        let mut names = Vec::new();
         for entry in entries {
-            let entry = entry?;
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
+            names.push(entry?.file_name().to_string_lossy().into_owned());
+        }
+        names.extend(self.archived_file_names()?);
 
-            if let Some(issue_id) = name_str.strip_suffix(".md") {
-                if let Some(pos) = issue_id.rfind('-') {
-                    let issue_prefix = &issue_id[..pos];
-                    let num_str = &issue_id[pos + 1..];
-                    if issue_prefix == prefix {
-                        if let Ok(num) = num_str.parse::<u32>() {
+        let mut max_num = 0;
+        for name in &names {
+            if let Some(issue_id) = name.strip_suffix(".md") {
+                if let Ok(issue_id) = IssueId::parse(issue_id) {
+                    if issue_id.prefix() == prefix {
+                        if let Some(num) = issue_id.number() {
                             max_num = max_num.max(num);
                         }
                     }
@@ -347,51 +1062,104 @@ impl Storage {
         Ok(max_num + 1)
     }
 
-    /// Generate a hash-based ID with adaptive length and collision handling
-    fn generate_hash_id(&self, prefix: &str, title: &str, description: &str) -> Result<String> {
-        use chrono::Utc;
-
-        let timestamp = Utc::now();
-
-        // Count existing issues to determine adaptive length
-        let entries = fs::read_dir(&self.issues_dir).context("Failed to read issues directory")?;
+    /// Generate a hash-based ID with adaptive length and collision handling,
+    /// also avoiding the IDs in `taken` (handed out but not yet written)
+    fn generate_hash_id(
+        &self,
+        prefix: &str,
+        title: &str,
+        description: &str,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        taken: &HashSet<String>,
+    ) -> Result<String> {
+        let generated = self.pick_hash_id(prefix, title, description, timestamp, taken)?;
+        if self.hash_id_warnings && generated.escalated() {
+            eprintln!(
+                "Warning: hash ID collisions forced a {}-character ID ({}) instead of {}. \
+                 The database is getting large for its hash space; `mb stats` shows the \
+                 longest hash ID in use.",
+                generated.length, generated.id, generated.initial_length
+            );
+        }
+        Ok(generated.id)
+    }
+
+    /// Pick a hash ID for content created at `timestamp` that collides with
+    /// no issue file, active or archived, nor with any ID in `taken` (IDs
+    /// handed out but not yet written)
+    fn pick_hash_id(
+        &self,
+        prefix: &str,
+        title: &str,
+        description: &str,
+        timestamp: chrono::DateTime<chrono::Utc>,
+        taken: &HashSet<String>,
+    ) -> Result<hash::GeneratedHashId> {
+        // Count existing issues to determine adaptive length
+        let entries = fs::read_dir(&self.issues_dir).context("Failed to read issues directory")?;
         let issue_count = entries.count();
 
         // Get hash encoding from config
         let encoding = self.get_hash_encoding()?;
 
         // Use hash::generate_hash_id_with_collision_check with filesystem checker
-        hash::generate_hash_id_with_collision_check(
+        let generated = hash::generate_hash_id_with_collision_check(
             prefix,
             title,
             description,
             timestamp,
             issue_count,
+            self.hash_min_length()?,
             encoding,
-            |candidate| self.issues_dir.join(format!("{}.md", candidate)).exists(),
-        )
+            |candidate| {
+                taken.contains(candidate)
+                    || self.issues_dir.join(format!("{}.md", candidate)).exists()
+                    || self.archived_path(candidate).exists()
+            },
+        )?;
+
+        if self.verbose {
+            eprintln!(
+                "debug: hash ID {} has length {} (nonce {}, started at length {} for ~{} issues)",
+                generated.id,
+                generated.length,
+                generated.nonce,
+                generated.initial_length,
+                issue_count
+            );
+        }
+
+        Ok(generated)
     }
 
     /// Create a new issue
-    #[allow(clippy::too_many_arguments)]
-    pub fn create_issue(
-        &self,
-        title: String,
-        description: String,
-        design: Option<String>,
-        acceptance: Option<String>,
-        priority: i32,
-        issue_type: IssueType,
-        assignee: Option<String>,
-        labels: Vec<String>,
-        external_ref: Option<String>,
-        id: Option<String>,
-        deps: Vec<(String, DependencyType)>,
-    ) -> Result<Issue> {
+    pub fn create_issue(&self, spec: IssueSpec) -> Result<Issue> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let (issue_path, issue) = self.prepare_new_issue(spec)?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
+
+        Ok(issue)
+    }
+
+    /// The issue `create_issue` would create from the same spec, after the
+    /// same ID generation and validation, without writing anything (used by
+    /// `create --dry-run`). Sequential and hash IDs are only derived from
+    /// the files on disk, so a later real create gets the same sequential ID.
+    pub fn preview_issue(&self, spec: IssueSpec) -> Result<Issue> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
+        let (_, issue) = self.prepare_new_issue(spec)?;
+        Ok(issue)
+    }
+
+    /// Assign an ID to, build, and validate a new issue, returning the path
+    /// it should be written to. Reads only; the caller holds the lock.
+    fn prepare_new_issue(&self, spec: IssueSpec) -> Result<(PathBuf, Issue)> {
         // Generate ID if not provided
-        let issue_id = if let Some(id) = id {
+        let issue_id = if let Some(id) = spec.id {
+            validate_id(&id, self.id_pattern()?.as_ref())?;
             id
         } else {
             let prefix = self.get_prefix()?;
@@ -399,7 +1167,13 @@ impl Storage {
 
             if use_hash_ids {
                 // Use hash-based ID generation
-                self.generate_hash_id(&prefix, &title, &description)?
+                self.generate_hash_id(
+                    &prefix,
+                    &spec.title,
+                    &spec.description,
+                    chrono::Utc::now(),
+                    &HashSet::new(),
+                )?
             } else {
                 // Use sequential numbering
                 let num = self.get_next_number(&prefix)?;
@@ -408,44 +1182,53 @@ impl Storage {
         };
 
         // Create issue
-        let mut issue = Issue::new(issue_id.clone(), title, priority, issue_type);
-        issue.description = if description.is_empty() {
-            String::new()
-        } else {
-            description
-        };
-        issue.design = design.unwrap_or_default();
-        issue.acceptance_criteria = acceptance.unwrap_or_default();
-        issue.assignee = assignee.unwrap_or_default();
-        issue.labels = labels;
-        issue.external_ref = external_ref;
+        let (priority, issue_type) = self.create_defaults(spec.priority, spec.issue_type)?;
+        let mut issue = Issue::new(issue_id.clone(), spec.title, priority, issue_type);
+        if let Some(created_at) = spec.created_at {
+            issue.created_at = created_at;
+            issue.updated_at = created_at;
+        }
+        issue.description = spec.description;
+        issue.design = spec.design;
+        issue.acceptance_criteria = spec.acceptance_criteria;
+        issue.notes = spec.notes;
+        issue.assignee = spec.assignee;
+        issue.labels = spec.labels;
+        issue.external_ref = spec.external_ref;
 
         // Add dependencies (with validation)
-        for (dep_id, dep_type) in deps {
-            // Validate dependency target exists (warn if not)
-            self.validate_dependency_exists(&dep_id);
+        for (dep_id, dep_type) in spec.dependencies {
+            // Validate dependency target exists (warn, or fail in strict mode)
+            self.validate_dependency_exists(&dep_id)?;
             issue.depends_on.insert(dep_id, dep_type);
         }
 
-        // Write to file
+        if self.archived_path(&issue_id).exists() {
+            anyhow::bail!("Issue already exists in the archive: {}", issue_id);
+        }
+
+        // Refuse to clobber an existing issue unless asked to
         let issue_path = self.issues_dir.join(format!("{}.md", issue_id));
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        if issue_path.exists() && !spec.overwrite {
+            anyhow::bail!(
+                "Issue already exists: {} (use --overwrite to replace it)",
+                issue_id
+            );
+        }
 
-        Ok(issue)
+        Ok((issue_path, issue))
     }
 
     /// Get an issue by ID
     pub fn get_issue(&self, id: &str) -> Result<Option<Issue>> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
-        let issue_path = self.issues_dir.join(format!("{}.md", id));
-        if !issue_path.exists() {
+        let Some(issue_path) = self.resolve_issue_path(id) else {
             return Ok(None);
-        }
+        };
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
         // Populate dependents by scanning all issues
         let all_issues = self.list_all_issues_no_dependents()?;
@@ -454,6 +1237,288 @@ impl Storage {
         Ok(Some(issue))
     }
 
+    /// Create a new issue using `source_id` as a template: title (prefixed
+    /// "Copy of "), description, design, acceptance criteria, type, priority,
+    /// and labels are copied. The copy gets a fresh ID and timestamps, is
+    /// open, and has no assignee, dependencies, notes, or external ref.
+    pub fn copy_issue(&self, source_id: &str) -> Result<Issue> {
+        let source = self
+            .get_issue(source_id)?
+            .ok_or_else(|| error::not_found(format!("Issue not found: {}", source_id)))?;
+
+        self.create_issue(IssueSpec {
+            title: format!("Copy of {}", source.title),
+            description: source.description,
+            design: source.design,
+            acceptance_criteria: source.acceptance_criteria,
+            priority: Some(source.priority),
+            issue_type: Some(source.issue_type),
+            labels: source.labels,
+            ..IssueSpec::default()
+        })
+    }
+
+    /// An issue's markdown: the stored file verbatim, or re-rendered from the
+    /// parsed issue when `normalized` is set. `None` if the issue is missing.
+    pub fn issue_markdown(&self, id: &str, normalized: bool) -> Result<Option<String>> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let Some(issue_path) = self.resolve_issue_path(id) else {
+            return Ok(None);
+        };
+
+        let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
+        if !normalized {
+            return Ok(Some(content));
+        }
+        let issue = self.parse_markdown(id, &content)?;
+        self.to_markdown(&issue).map(Some)
+    }
+
+    /// The file holding an issue and whether the repository has any commits
+    /// yet, for the git-backed commands. `None` if the database is not inside
+    /// a git repository.
+    fn issue_git_path(&self, id: &str) -> Result<Option<(PathBuf, bool)>> {
+        let issue_path = self
+            .resolve_issue_path(id)
+            .ok_or_else(|| error::not_found(format!("Issue not found: {}", id)))?;
+        let beads_dir = self
+            .beads_dir
+            .canonicalize()
+            .context("Failed to resolve database directory")?;
+        if !beads_dir.ancestors().any(|dir| dir.join(".git").exists()) {
+            return Ok(None);
+        }
+        // `git log` and `git blame` fail outright in a repository without commits
+        let has_commits = git_command(&issue_path, &["rev-parse", "--verify", "--quiet", "HEAD"])
+            .output()
+            .context("Failed to run git rev-parse")?
+            .status
+            .success();
+        Ok(Some((issue_path, has_commits)))
+    }
+
+    /// Commits touching an issue's file, newest first, from
+    /// `git log --follow`. `None` if the database is not inside a git
+    /// repository; empty if the file was never committed.
+    pub fn issue_history(&self, id: &str) -> Result<Option<Vec<HistoryEntry>>> {
+        let Some((issue_path, has_commits)) = self.issue_git_path(id)? else {
+            return Ok(None);
+        };
+        if !has_commits {
+            return Ok(Some(Vec::new()));
+        }
+        let output = run_git_on_file(
+            &issue_path,
+            &[
+                "log",
+                "--follow",
+                "--date=short",
+                "--format=%h%x1f%ad%x1f%an%x1f%s",
+            ],
+        )?;
+
+        let history = output
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(4, '\x1f');
+                Some(HistoryEntry {
+                    commit: fields.next()?.to_string(),
+                    date: fields.next()?.to_string(),
+                    author: fields.next()?.to_string(),
+                    summary: fields.next()?.to_string(),
+                })
+            })
+            .collect();
+        Ok(Some(history))
+    }
+
+    /// Who last changed a frontmatter field of an issue, from `git blame` on
+    /// its file. `None` if the database is not inside a git repository.
+    pub fn field_blame(&self, id: &str, field: &str) -> Result<Option<FieldBlame>> {
+        let Some((issue_path, has_commits)) = self.issue_git_path(id)? else {
+            return Ok(None);
+        };
+
+        let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
+        let key = format!("{}:", field);
+        let (line, value) = content
+            .lines()
+            .enumerate()
+            .skip(1)
+            .take_while(|(_, line)| *line != "---")
+            .find_map(|(idx, line)| {
+                line.strip_prefix(&key)
+                    .map(|value| (idx + 1, value.trim().to_string()))
+            })
+            .ok_or_else(|| {
+                error::invalid_input(format!(
+                    "Field '{}' is not set in the frontmatter of {}",
+                    field, id
+                ))
+            })?;
+
+        let tracked = has_commits
+            && git_command(&issue_path, &["ls-files", "--error-unmatch"])
+                .output()
+                .context("Failed to run git ls-files")?
+                .status
+                .success();
+        let commit = if tracked {
+            let range = format!("{},{}", line, line);
+            let output =
+                run_git_on_file(&issue_path, &["blame", "--line-porcelain", "-L", &range])?;
+            parse_blame_commit(&output)
+        } else {
+            None
+        };
+
+        Ok(Some(FieldBlame {
+            field: field.to_string(),
+            line,
+            value,
+            commit,
+        }))
+    }
+
+    /// The file holding an issue: the active one, or the archived one when
+    /// `include_archived` is set. `None` if neither exists.
+    pub fn resolve_issue_path(&self, id: &str) -> Option<PathBuf> {
+        let active = self.issue_path(id);
+        if active.exists() {
+            return Some(active);
+        }
+        let archived = self.archived_path(id);
+        (self.include_archived && archived.exists()).then_some(archived)
+    }
+
+    fn archive_dir(&self) -> PathBuf {
+        self.beads_dir.join("archive")
+    }
+
+    fn archived_path(&self, issue_id: &str) -> PathBuf {
+        self.archive_dir().join(format!("{}.md", issue_id))
+    }
+
+    /// File names in the archive directory (empty if nothing was archived)
+    fn archived_file_names(&self) -> Result<Vec<String>> {
+        let archive_dir = self.archive_dir();
+        if !archive_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut names = Vec::new();
+        for entry in fs::read_dir(&archive_dir).context("Failed to read archive directory")? {
+            names.push(entry?.file_name().to_string_lossy().into_owned());
+        }
+        Ok(names)
+    }
+
+    /// IDs of all archived issues
+    pub fn archived_ids(&self) -> Result<BTreeSet<String>> {
+        Ok(self
+            .archived_file_names()?
+            .iter()
+            .filter_map(|name| name.strip_suffix(".md").map(String::from))
+            .collect())
+    }
+
+    /// A blocker index seeded with every archived issue (as closed), so
+    /// dependencies on archived issues count as resolved rather than missing
+    fn new_blocker_statuses(&self) -> Result<BlockerStatuses> {
+        let mut blockers = BlockerStatuses::new(self.ignore_missing_blockers);
+        for id in self.archived_ids()? {
+            blockers.insert_archived(id);
+        }
+        Ok(blockers)
+    }
+
+    /// Copy the database (issues, archive, comments, config) into `dest`,
+    /// which must not exist yet or be empty, leaving out the lock, command
+    /// log and JSONL backup. The copy is taken under the lock, so it is a
+    /// consistent view that other commands can read via `--beads-dir` while
+    /// this database keeps changing. Returns the number of files copied.
+    pub fn snapshot(&self, dest: &Path) -> Result<usize> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        if dest.exists() && fs::read_dir(dest)?.next().is_some() {
+            return Err(error::invalid_input(format!(
+                "Snapshot directory is not empty: {}",
+                dest.display()
+            )));
+        }
+        fs::create_dir_all(dest).with_context(|| format!("Failed to create {}", dest.display()))?;
+        if fs::canonicalize(dest)?.starts_with(fs::canonicalize(&self.beads_dir)?) {
+            let _ = fs::remove_dir(dest);
+            return Err(error::invalid_input(
+                "Snapshot directory must be outside the database directory",
+            ));
+        }
+
+        copy_dir_except(&self.beads_dir, dest, LOCAL_ONLY_FILES)
+    }
+
+    /// Move closed issues whose `closed_at` is before `cutoff` into the
+    /// archive directory, unchanged. Dependency edges pointing at them are
+    /// left alone. Returns the archived IDs in order; `dry_run` only reports
+    /// them.
+    pub fn archive_closed_before(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+        dry_run: bool,
+    ) -> Result<Vec<String>> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let mut ids: Vec<String> = self
+            .read_active_issues()?
+            .into_iter()
+            .filter(|issue| {
+                issue.status == Status::Closed && issue.closed_at.is_some_and(|t| t < cutoff)
+            })
+            .map(|issue| issue.id)
+            .collect();
+        ids.sort();
+
+        if dry_run || ids.is_empty() {
+            return Ok(ids);
+        }
+
+        fs::create_dir_all(self.archive_dir()).context("Failed to create archive directory")?;
+        for id in &ids {
+            let from = self.issues_dir.join(format!("{}.md", id));
+            fs::rename(&from, self.archived_path(id))
+                .with_context(|| format!("Failed to archive {}", id))?;
+        }
+
+        Ok(ids)
+    }
+
+    /// Move archived issues back into the issues directory. Refuses, without
+    /// moving anything, if an ID is not archived or an active issue already
+    /// uses it.
+    pub fn restore_issues(&self, ids: &[String]) -> Result<()> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        for id in ids {
+            if !self.archived_path(id).exists() {
+                return Err(error::not_found(format!(
+                    "Issue not found in archive: {}",
+                    id
+                )));
+            }
+            if self.issues_dir.join(format!("{}.md", id)).exists() {
+                anyhow::bail!("Cannot restore {}: an active issue with that ID exists", id);
+            }
+        }
+
+        for id in ids {
+            let to = self.issues_dir.join(format!("{}.md", id));
+            fs::rename(self.archived_path(id), &to)
+                .with_context(|| format!("Failed to restore {}", id))?;
+        }
+
+        Ok(())
+    }
+
     fn comments_dir(&self) -> PathBuf {
         self.beads_dir.join("comments")
     }
@@ -489,7 +1554,8 @@ impl Storage {
         let content =
             serde_json::to_string_pretty(&sorted).context("Failed to serialize comments")?;
         let path = self.comment_path(issue_id);
-        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        write_atomic(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
         Ok(())
     }
 
@@ -499,7 +1565,7 @@ impl Storage {
 
         let issue_path = self.issues_dir.join(format!("{}.md", issue_id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", issue_id);
+            return Err(error::not_found(format!("Issue not found: {}", issue_id)));
         }
 
         let mut comments = self.read_comments_no_lock(issue_id)?;
@@ -549,12 +1615,12 @@ impl Storage {
             .iter()
             .position(|c| c.id == comment_id)
             .ok_or_else(|| {
-                anyhow::anyhow!(
+                error::not_found(format!(
                     "Comment not found: {} (on issue {}). Run `mb comments list {}` to see valid comment IDs.",
                     comment_id,
                     issue_id,
                     issue_id
-                )
+                ))
             })?;
         let removed = comments.remove(pos);
         self.write_comments_no_lock(issue_id, &comments)?;
@@ -612,25 +1678,256 @@ impl Storage {
 
     /// Helper to load all issues without computing dependents (to avoid recursion)
     fn list_all_issues_no_dependents(&self) -> Result<Vec<Issue>> {
-        let entries = fs::read_dir(&self.issues_dir).context("Failed to read issues directory")?;
+        self.read_all_issues()
+    }
+
+    /// Read and parse every issue file, in directory order, followed by the
+    /// archived issues when `include_archived` is set.
+    ///
+    /// With the `parallel` feature the files are read and parsed on the rayon
+    /// thread pool; the result order is the same either way. Callers hold the
+    /// storage lock.
+    fn read_all_issues(&self) -> Result<Vec<Issue>> {
+        let mut issues = self.read_active_issues()?;
+        if self.include_archived && self.archive_dir().exists() {
+            issues.extend(self.read_issues_in(&self.archive_dir())?);
+        }
+        Ok(issues)
+    }
+
+    /// `read_all_issues` without the archive, for operations that only
+    /// apply to active issues
+    fn read_active_issues(&self) -> Result<Vec<Issue>> {
+        self.read_issues_in(&self.issues_dir)
+    }
+
+    fn read_issues_in(&self, dir: &Path) -> Result<Vec<Issue>> {
+        let entries =
+            fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
 
-        let mut issues = Vec::new();
+        let mut files = Vec::new();
         for entry in entries {
             let entry = entry?;
             let name = entry.file_name();
-            let name_str = name.to_string_lossy();
+            if let Some(issue_id) = name.to_string_lossy().strip_suffix(".md") {
+                files.push((issue_id.to_string(), entry.path()));
+            }
+        }
 
-            if !name_str.ends_with(".md") {
-                continue;
+        let parse = |(issue_id, path): &(String, PathBuf)| -> Result<Issue> {
+            let content = fs::read_to_string(path)?;
+            self.parse_markdown(issue_id, &content)
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            files.par_iter().map(parse).collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            files.iter().map(parse).collect()
+        }
+    }
+
+    /// IDs of active issues whose file would change on a no-op rewrite: the
+    /// canonical form (see [`Self::normalize_all`]) differs from what is on
+    /// disk, or the file does not parse at all. A stable database returns an
+    /// empty list.
+    pub fn check_roundtrip(&self) -> Result<Vec<String>> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        Ok(self
+            .unstable_issue_files()?
+            .into_iter()
+            .map(|(issue_id, _, _)| issue_id)
+            .collect())
+    }
+
+    /// IDs of active issues whose titles match after trimming and
+    /// lowercasing, usually work that was accidentally filed twice. Only
+    /// groups with more than one member are returned; IDs within a group
+    /// are sorted, and groups are ordered by their first ID.
+    pub fn find_duplicate_titles(&self) -> Result<Vec<Vec<String>>> {
+        let mut by_title: HashMap<String, Vec<String>> = HashMap::new();
+        for issue in self.list_issues(&IssueFilter::new(), None)? {
+            by_title
+                .entry(issue.title.trim().to_lowercase())
+                .or_default()
+                .push(issue.id);
+        }
+
+        let mut groups: Vec<Vec<String>> = by_title
+            .into_values()
+            .filter(|ids| ids.len() > 1)
+            .map(|mut ids| {
+                ids.sort();
+                ids
+            })
+            .collect();
+        groups.sort();
+        Ok(groups)
+    }
+
+    /// Open issues whose title is at least `threshold` similar to `title`
+    /// (see [`title_similarity`]), with their similarity, most similar first.
+    /// Closed issues are skipped: recreating finished work is not a duplicate.
+    pub fn find_similar_titles(&self, title: &str, threshold: f64) -> Result<Vec<(Issue, f64)>> {
+        let mut similar: Vec<(Issue, f64)> = self
+            .list_issues(&IssueFilter::new(), None)?
+            .into_iter()
+            .filter(|issue| issue.status != Status::Closed)
+            .filter_map(|issue| {
+                let score = title_similarity(title, &issue.title);
+                (score >= threshold).then_some((issue, score))
+            })
+            .collect();
+        similar.sort_by(|(a, a_score), (b, b_score)| {
+            b_score.total_cmp(a_score).then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(similar)
+    }
+
+    /// Rewrite every active issue whose file is not in canonical form: parsed,
+    /// labels sorted and deduplicated, and re-serialized. `updated_at` is
+    /// left alone, so this only reformats. Fails before writing anything if
+    /// a file does not parse. Returns the IDs rewritten (or, with `dry_run`,
+    /// that would be), the same set [`Self::check_roundtrip`] reports.
+    pub fn normalize_all(&self, dry_run: bool) -> Result<Vec<String>> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let unstable = self.unstable_issue_files()?;
+        let unparsable: Vec<&str> = unstable
+            .iter()
+            .filter(|(_, _, issue)| issue.is_none())
+            .map(|(issue_id, _, _)| issue_id.as_str())
+            .collect();
+        if !unparsable.is_empty() {
+            anyhow::bail!(
+                "Cannot normalize issues that do not parse: {}",
+                unparsable.join(", ")
+            );
+        }
+
+        let mut ids = Vec::new();
+        for (issue_id, path, issue) in unstable {
+            if !dry_run {
+                if let Some(issue) = &issue {
+                    self.write_issue_file(&path, issue)
+                        .context("Failed to write issue file")?;
+                }
             }
+            ids.push(issue_id);
+        }
 
-            let issue_id = &name_str[..name_str.len() - 3];
-            let content = fs::read_to_string(entry.path())?;
-            let issue = markdown_to_issue(issue_id, &content)?;
-            issues.push(issue);
+        Ok(ids)
+    }
+
+    /// Remove debris from the database: issue files that are empty or do not
+    /// parse, and `*.tmp` files left in the issues or database directory by
+    /// an interrupted atomic write (only once they are a minute old, so a
+    /// write in progress is left alone). Files that parse as issues are never
+    /// touched. Returns the removals in file-name order; `dry_run` only
+    /// reports them.
+    pub fn gc(&self, dry_run: bool) -> Result<Vec<Change>> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let mut changes = Vec::new();
+        for dir in [&self.beads_dir, &self.issues_dir] {
+            let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+                .with_context(|| format!("Failed to read {}", dir.display()))?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<std::io::Result<_>>()?;
+            paths.sort();
+
+            for path in paths {
+                if !path.is_file() {
+                    continue;
+                }
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                let file = path
+                    .strip_prefix(&self.beads_dir)
+                    .unwrap_or(&path)
+                    .display()
+                    .to_string();
+
+                let change = if name.ends_with(".tmp") {
+                    // Exports stream into their temp file before taking the
+                    // lock, so a fresh one may still be in use
+                    let age = fs::metadata(&path)?
+                        .modified()?
+                        .elapsed()
+                        .unwrap_or_default();
+                    if age < std::time::Duration::from_secs(60) {
+                        continue;
+                    }
+                    Change::RemoveTempFile { file }
+                } else if let Some(issue_id) =
+                    name.strip_suffix(".md").filter(|_| dir == &self.issues_dir)
+                {
+                    let content = fs::read(&path)
+                        .with_context(|| format!("Failed to read {}", path.display()))?;
+                    if content.iter().all(u8::is_ascii_whitespace) {
+                        Change::RemoveEmptyFile { file }
+                    } else {
+                        let parsed = String::from_utf8(content)
+                            .map_err(anyhow::Error::from)
+                            .and_then(|content| self.parse_markdown(issue_id, &content));
+                        match parsed {
+                            Ok(_) => continue,
+                            Err(e) => Change::RemoveUnparseableFile {
+                                file,
+                                error: e.to_string(),
+                            },
+                        }
+                    }
+                } else {
+                    continue;
+                };
+
+                if !dry_run {
+                    fs::remove_file(&path)
+                        .with_context(|| format!("Failed to remove {}", path.display()))?;
+                }
+                changes.push(change);
+            }
         }
 
-        Ok(issues)
+        Ok(changes)
+    }
+
+    /// Active issue files whose canonical form differs from their content,
+    /// sorted by ID, with the parsed canonical issue (`None` if the file does
+    /// not parse). The caller holds the lock.
+    fn unstable_issue_files(&self) -> Result<Vec<(String, PathBuf, Option<Issue>)>> {
+        let mut unstable = Vec::new();
+        for entry in fs::read_dir(&self.issues_dir).context("Failed to read issues directory")? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let Some(issue_id) = name.to_string_lossy().strip_suffix(".md").map(String::from)
+            else {
+                continue;
+            };
+            let path = entry.path();
+            let content = fs::read_to_string(&path).context("Failed to read issue file")?;
+            let Ok(mut issue) = self.parse_markdown(&issue_id, &content) else {
+                unstable.push((issue_id, path, None));
+                continue;
+            };
+            issue.labels = normalize_labels(std::mem::take(&mut issue.labels));
+            if self.to_markdown(&issue)? != content {
+                unstable.push((issue_id, path, Some(issue)));
+            }
+        }
+        // Numeric IDs in numeric order, then hash-based IDs
+        unstable.sort_by_key(|(issue_id, _, _)| {
+            (
+                numeric_id_suffix(issue_id).unwrap_or(u32::MAX),
+                issue_id.clone(),
+            )
+        });
+
+        Ok(unstable)
     }
 
     /// Populate dependents for a single issue given all issues
@@ -652,17 +1949,20 @@ impl Storage {
         target_issue.dependents = dependents;
     }
 
-    /// Update an issue
+    /// Update an issue. Fields missing from `updates` are left unchanged; a
+    /// field present with an empty value is cleared (`external_ref` becomes
+    /// unset). `append_description`/`append_notes` add a line to the current
+    /// value instead of replacing it, read and written under the same lock.
     pub fn update_issue(&self, id: &str, updates: HashMap<String, String>) -> Result<Issue> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
         let issue_path = self.issues_dir.join(format!("{}.md", id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", id);
+            return Err(error::not_found(format!("Issue not found: {}", id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
         // Apply updates
         for (key, value) in updates {
@@ -672,6 +1972,8 @@ impl Storage {
                 "design" => issue.design = value,
                 "notes" => issue.notes = value,
                 "acceptance_criteria" => issue.acceptance_criteria = value,
+                "append_description" => append_line(&mut issue.description, &value),
+                "append_notes" => append_line(&mut issue.notes, &value),
                 "status" => issue.status = value.parse()?,
                 "priority" => issue.priority = value.parse()?,
                 "issue_type" => issue.issue_type = value.parse()?,
@@ -686,8 +1988,8 @@ impl Storage {
         issue.updated_at = chrono::Utc::now();
 
         // Write back
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
 
         Ok(issue)
     }
@@ -698,11 +2000,11 @@ impl Storage {
 
         let issue_path = self.issues_dir.join(format!("{}.md", id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", id);
+            return Err(error::not_found(format!("Issue not found: {}", id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
         if !issue.labels.iter().any(|existing| existing == label) {
             issue.labels.push(label.to_string());
@@ -710,8 +2012,8 @@ impl Storage {
         }
         issue.updated_at = chrono::Utc::now();
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
 
         Ok(issue)
     }
@@ -722,17 +2024,66 @@ impl Storage {
 
         let issue_path = self.issues_dir.join(format!("{}.md", id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", id);
+            return Err(error::not_found(format!("Issue not found: {}", id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
         issue.labels.retain(|existing| existing != label);
         issue.updated_at = chrono::Utc::now();
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
+
+        Ok(issue)
+    }
+
+    /// Add a watcher to an issue, returning the updated issue. Adding an
+    /// existing watcher leaves the file untouched.
+    pub fn add_watcher(&self, id: &str, user: &str) -> Result<Issue> {
+        self.change_watchers(id, |watchers| {
+            if watchers.iter().any(|existing| existing == user) {
+                return false;
+            }
+            watchers.push(user.to_string());
+            watchers.sort();
+            true
+        })
+    }
+
+    /// Remove a watcher from an issue, returning the updated issue. Removing
+    /// someone who is not watching leaves the file untouched.
+    pub fn remove_watcher(&self, id: &str, user: &str) -> Result<Issue> {
+        self.change_watchers(id, |watchers| {
+            let before = watchers.len();
+            watchers.retain(|existing| existing != user);
+            watchers.len() != before
+        })
+    }
+
+    /// Apply `change` to an issue's watchers under the lock, writing the file
+    /// only if it reports a change
+    fn change_watchers(
+        &self,
+        id: &str,
+        change: impl FnOnce(&mut Vec<String>) -> bool,
+    ) -> Result<Issue> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let issue_path = self.issues_dir.join(format!("{}.md", id));
+        if !issue_path.exists() {
+            return Err(error::not_found(format!("Issue not found: {}", id)));
+        }
+
+        let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
+        let mut issue = self.parse_markdown(id, &content)?;
+
+        if change(&mut issue.watchers) {
+            issue.updated_at = chrono::Utc::now();
+            self.write_issue_file(&issue_path, &issue)
+                .context("Failed to write issue file")?;
+        }
 
         Ok(issue)
     }
@@ -743,24 +2094,24 @@ impl Storage {
 
         let issue_path = self.issues_dir.join(format!("{}.md", id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", id);
+            return Err(error::not_found(format!("Issue not found: {}", id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
         issue.labels = normalize_labels(labels);
         issue.updated_at = chrono::Utc::now();
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
 
         Ok(issue)
     }
 
     /// List all unique labels across issues.
     pub fn list_all_labels(&self) -> Result<Vec<String>> {
-        let issues = self.list_issues(None, None, None, None, None)?;
+        let issues = self.list_issues(&IssueFilter::new(), None)?;
         let labels = issues
             .into_iter()
             .flat_map(|issue| issue.labels)
@@ -799,11 +2150,11 @@ impl Storage {
 
         let issue_path = self.issues_dir.join(format!("{}.md", id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", id);
+            return Err(error::not_found(format!("Issue not found: {}", id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
         let target = issue.text_field_mut(field);
         let occurrences = target.matches(search).count();
@@ -830,8 +2181,8 @@ impl Storage {
 
         issue.updated_at = chrono::Utc::now();
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
 
         Ok(issue)
     }
@@ -851,11 +2202,11 @@ impl Storage {
 
         let issue_path = self.issues_dir.join(format!("{}.md", id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", id);
+            return Err(error::not_found(format!("Issue not found: {}", id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
         let target = issue.text_field_mut(field);
         let existing = target.trim_end();
@@ -867,8 +2218,8 @@ impl Storage {
 
         issue.updated_at = chrono::Utc::now();
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
 
         Ok(issue)
     }
@@ -898,11 +2249,11 @@ impl Storage {
 
         let issue_path = self.issues_dir.join(format!("{}.md", id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", id);
+            return Err(error::not_found(format!("Issue not found: {}", id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
         if issue.status == Status::Closed {
             anyhow::bail!("Cannot claim {}: issue is closed", id);
@@ -934,6 +2285,8 @@ impl Storage {
                 "design" => issue.design = value.clone(),
                 "notes" => issue.notes = value.clone(),
                 "acceptance_criteria" => issue.acceptance_criteria = value.clone(),
+                "append_description" => append_line(&mut issue.description, value),
+                "append_notes" => append_line(&mut issue.notes, value),
                 "priority" => issue.priority = value.parse()?,
                 "issue_type" => issue.issue_type = value.parse()?,
                 "external_ref" => {
@@ -953,8 +2306,8 @@ impl Storage {
         issue.claimed_until = Some(claimed_until);
         issue.updated_at = now;
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
 
         Ok(issue)
     }
@@ -970,11 +2323,11 @@ impl Storage {
 
         let issue_path = self.issues_dir.join(format!("{}.md", id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", id);
+            return Err(error::not_found(format!("Issue not found: {}", id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
         if !issue.assignee.is_empty() && issue.assignee != actor && !force {
             anyhow::bail!(
@@ -993,65 +2346,186 @@ impl Storage {
         }
         issue.updated_at = chrono::Utc::now();
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
 
         Ok(issue)
     }
 
     /// Close an issue
-    pub fn close_issue(&self, id: &str, _reason: &str) -> Result<Issue> {
+    pub fn close_issue(&self, id: &str, reason: &str) -> Result<Issue> {
+        self.close_issue_with_resolution(id, reason, None)
+    }
+
+    /// Close an issue, recording how it was resolved
+    pub fn close_issue_with_resolution(
+        &self,
+        id: &str,
+        _reason: &str,
+        resolution: Option<Resolution>,
+    ) -> Result<Issue> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
         let issue_path = self.issues_dir.join(format!("{}.md", id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", id);
+            return Err(error::not_found(format!("Issue not found: {}", id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
         issue.status = Status::Closed;
         issue.closed_at = Some(chrono::Utc::now());
+        issue.resolution = resolution;
         issue.updated_at = chrono::Utc::now();
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
 
         Ok(issue)
     }
 
-    /// Reopen an issue
-    pub fn reopen_issue(&self, id: &str) -> Result<Issue> {
+    /// Apply a guarded status [`Transition`], appending `note` (if any) to the
+    /// issue's notes. Fails without writing if the issue's current status
+    /// does not allow it.
+    pub fn transition_issue(
+        &self,
+        id: &str,
+        transition: Transition,
+        note: Option<&str>,
+    ) -> Result<Issue> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
         let issue_path = self.issues_dir.join(format!("{}.md", id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", id);
+            return Err(error::not_found(format!("Issue not found: {}", id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(id, &content)?;
+        let mut issue = self.parse_markdown(id, &content)?;
 
-        issue.status = Status::Open;
-        issue.closed_at = None;
+        let Some(status) = transition.target(issue.status, !issue.assignee.is_empty()) else {
+            let hint = if issue.status == Status::Closed {
+                "; reopen it with `mb reopen` first"
+            } else {
+                ""
+            };
+            return Err(error::invalid_input(format!(
+                "Cannot {} {}: it is {}{}",
+                transition.as_str(),
+                id,
+                issue.status,
+                hint
+            )));
+        };
+
+        issue.status = status;
+        if let Some(note) = note {
+            append_line(&mut issue.notes, note);
+        }
         issue.updated_at = chrono::Utc::now();
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
 
         Ok(issue)
     }
 
-    /// Rename an issue ID
-    ///
-    /// This operation:
+    /// Merge two tickets: close `dup` with resolution `duplicate` and give it
+    /// a `related` dependency on `canonical` (an existing dependency of
+    /// another type is kept). With `copy_labels`, labels only `dup` has are
+    /// added to `canonical`. Both files are written under one lock. Returns
+    /// the updated duplicate and canonical issues.
+    pub fn mark_duplicate(
+        &self,
+        dup: &str,
+        canonical: &str,
+        copy_labels: bool,
+    ) -> Result<(Issue, Issue)> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        if dup == canonical {
+            return Err(error::invalid_input(format!(
+                "{} cannot be a duplicate of itself",
+                dup
+            )));
+        }
+        let dup_path = self.issues_dir.join(format!("{}.md", dup));
+        let canonical_path = self.issues_dir.join(format!("{}.md", canonical));
+        for (id, path) in [(dup, &dup_path), (canonical, &canonical_path)] {
+            if !path.exists() {
+                return Err(error::not_found(format!("Issue not found: {}", id)));
+            }
+        }
+
+        let content = fs::read_to_string(&dup_path).context("Failed to read issue file")?;
+        let mut dup_issue = self.parse_markdown(dup, &content)?;
+        let content = fs::read_to_string(&canonical_path).context("Failed to read issue file")?;
+        let mut canonical_issue = self.parse_markdown(canonical, &content)?;
+
+        let now = chrono::Utc::now();
+        if copy_labels {
+            let missing: Vec<String> = dup_issue
+                .labels
+                .iter()
+                .filter(|label| !canonical_issue.labels.contains(label))
+                .cloned()
+                .collect();
+            if !missing.is_empty() {
+                canonical_issue.labels.extend(missing);
+                canonical_issue.labels.sort();
+                canonical_issue.updated_at = now;
+                self.write_issue_file(&canonical_path, &canonical_issue)
+                    .context("Failed to write issue file")?;
+            }
+        }
+
+        dup_issue
+            .depends_on
+            .entry(canonical.to_string())
+            .or_insert(DependencyType::Related);
+        dup_issue.status = Status::Closed;
+        dup_issue.closed_at = Some(now);
+        dup_issue.resolution = Some(Resolution::Duplicate);
+        dup_issue.updated_at = now;
+        self.write_issue_file(&dup_path, &dup_issue)
+            .context("Failed to write issue file")?;
+
+        Ok((dup_issue, canonical_issue))
+    }
+
+    /// Reopen an issue
+    pub fn reopen_issue(&self, id: &str) -> Result<Issue> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let issue_path = self.issues_dir.join(format!("{}.md", id));
+        if !issue_path.exists() {
+            return Err(error::not_found(format!("Issue not found: {}", id)));
+        }
+
+        let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
+        let mut issue = self.parse_markdown(id, &content)?;
+
+        issue.status = Status::Open;
+        issue.closed_at = None;
+        issue.resolution = None;
+        issue.updated_at = chrono::Utc::now();
+
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
+
+        Ok(issue)
+    }
+
+    /// Rename an issue ID
+    ///
+    /// This operation:
     /// - Renames the markdown file
     /// - Updates the ID in the issue frontmatter
     /// - Updates all references in other issues' dependencies
     /// - Updates all text mentions of the old ID in all issues (title, description, design, notes, acceptance_criteria)
     /// - Is atomic (all updates succeed or none)
-    pub fn rename_issue(&self, old_id: &str, new_id: &str, dry_run: bool) -> Result<Vec<String>> {
+    pub fn rename_issue(&self, old_id: &str, new_id: &str, dry_run: bool) -> Result<Vec<Change>> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
         let old_path = self.issues_dir.join(format!("{}.md", old_id));
@@ -1059,7 +2533,7 @@ impl Storage {
 
         // Validate old issue exists
         if !old_path.exists() {
-            anyhow::bail!("Issue not found: {}", old_id);
+            return Err(error::not_found(format!("Issue not found: {}", old_id)));
         }
 
         // Validate new ID doesn't already exist
@@ -1068,20 +2542,22 @@ impl Storage {
         }
 
         // Track all changes for dry-run mode
-        let mut changes = Vec::new();
-        changes.push(format!("Rename file: {}.md -> {}.md", old_id, new_id));
+        let mut changes = vec![Change::RenameFile {
+            from: old_id.to_string(),
+            to: new_id.to_string(),
+        }];
 
         // Load the issue to rename
         let content = fs::read_to_string(&old_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(old_id, &content)?;
+        let mut issue = self.parse_markdown(old_id, &content)?;
 
         // Update the issue's ID
         issue.id = new_id.to_string();
         issue.updated_at = chrono::Utc::now();
-        changes.push(format!(
-            "Update ID in frontmatter: {} -> {}",
-            old_id, new_id
-        ));
+        changes.push(Change::UpdateId {
+            from: old_id.to_string(),
+            to: new_id.to_string(),
+        });
 
         // Build ID mapping for text replacement
         let mut id_mapping = HashMap::new();
@@ -1104,10 +2580,11 @@ impl Storage {
 
             // Check if this issue has explicit dependency on the renamed issue
             if other_issue.depends_on.contains_key(old_id) {
-                changes.push(format!(
-                    "Update dependency in {}: {} -> {}",
-                    other_issue.id, old_id, new_id
-                ));
+                changes.push(Change::UpdateDependency {
+                    issue: other_issue.id.clone(),
+                    from: old_id.to_string(),
+                    to: new_id.to_string(),
+                });
                 has_changes = true;
             }
 
@@ -1127,10 +2604,11 @@ impl Storage {
                 || other_issue.notes != old_notes
                 || other_issue.acceptance_criteria != old_acceptance
             {
-                changes.push(format!(
-                    "Update text references in {}: {} -> {}",
-                    other_issue.id, old_id, new_id
-                ));
+                changes.push(Change::UpdateTextReferences {
+                    issue: other_issue.id.clone(),
+                    from: old_id.to_string(),
+                    to: new_id.to_string(),
+                });
                 has_changes = true;
             }
 
@@ -1155,14 +2633,13 @@ impl Storage {
 
             // Write the updated issue
             let other_path = self.issues_dir.join(format!("{}.md", other_issue.id));
-            let markdown = issue_to_markdown(&other_issue)?;
-            fs::write(&other_path, markdown)
+            self.write_issue_file(&other_path, &other_issue)
                 .context(format!("Failed to update issue: {}", other_issue.id))?;
         }
 
         // Write the renamed issue with new ID
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&new_path, markdown).context("Failed to write renamed issue")?;
+        self.write_issue_file(&new_path, &issue)
+            .context("Failed to write renamed issue")?;
 
         // Remove the old file
         fs::remove_file(&old_path).context("Failed to remove old issue file")?;
@@ -1170,35 +2647,269 @@ impl Storage {
         Ok(changes)
     }
 
+    /// Read issues and their comments for `move-to`, which recreates them in
+    /// another database with `import_moved_issues`
+    pub fn export_subset(&self, ids: &[String]) -> Result<Vec<(Issue, Vec<Comment>)>> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let mut subset = Vec::new();
+        for id in ids {
+            let path = self.issue_path(id);
+            if !path.exists() {
+                return Err(error::not_found(format!("Issue not found: {}", id)));
+            }
+            let content = fs::read_to_string(&path).context("Failed to read issue file")?;
+            let issue = self.parse_markdown(id, &content)?;
+            subset.push((issue, self.read_comments_no_lock(id)?));
+        }
+        Ok(subset)
+    }
+
+    /// Create issues exported from another database by `export_subset`.
+    ///
+    /// With `keep_ids` the IDs are kept (and must be free here); otherwise
+    /// each issue gets the next ID this database would assign (hash IDs are
+    /// derived from each issue's creation time and never collide with each
+    /// other). Dependencies and text mentions of the old IDs are rewritten to
+    /// the new ones, as in `rename_issue`: among the moved issues, and in
+    /// issues already here unless the old ID names an issue of this
+    /// database. Returns the old-to-new ID mapping and the list of changes;
+    /// `dry_run` only computes them.
+    pub fn import_moved_issues(
+        &self,
+        subset: Vec<(Issue, Vec<Comment>)>,
+        keep_ids: bool,
+        dry_run: bool,
+    ) -> Result<(MovedIds, Vec<Change>)> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let mut mapping = Vec::new();
+        if keep_ids {
+            let pattern = self.id_pattern()?;
+            for (issue, _) in &subset {
+                validate_id(&issue.id, pattern.as_ref())?;
+                mapping.push((issue.id.clone(), issue.id.clone()));
+            }
+        } else {
+            let prefix = self.get_prefix()?;
+            let use_hash_ids = self.use_hash_ids()?;
+            let mut next_number = self.get_next_number(&prefix)?;
+            let mut taken = HashSet::new();
+            for (issue, _) in &subset {
+                let new_id = if use_hash_ids {
+                    self.generate_hash_id(
+                        &prefix,
+                        &issue.title,
+                        &issue.description,
+                        issue.created_at,
+                        &taken,
+                    )?
+                } else {
+                    next_number += 1;
+                    format!("{}-{}", prefix, next_number - 1)
+                };
+                taken.insert(new_id.clone());
+                mapping.push((issue.id.clone(), new_id));
+            }
+        }
+
+        for (_, new_id) in &mapping {
+            if self.issue_path(new_id).exists() || self.archived_path(new_id).exists() {
+                anyhow::bail!("Issue already exists in target: {}", new_id);
+            }
+        }
+
+        let id_mapping: HashMap<String, String> = mapping.iter().cloned().collect();
+        let mut changes = Vec::new();
+        let mut moved = Vec::new();
+        for (mut issue, mut comments) in subset {
+            let old_id = issue.id.clone();
+            issue.id = id_mapping[&old_id].clone();
+            changes.push(Change::CreateIssue {
+                id: issue.id.clone(),
+                from: old_id.clone(),
+            });
+
+            issue.depends_on = issue
+                .depends_on
+                .into_iter()
+                .map(|(dep_id, dep_type)| {
+                    (id_mapping.get(&dep_id).cloned().unwrap_or(dep_id), dep_type)
+                })
+                .collect();
+            for dep_id in issue.depends_on.keys() {
+                let moved_too = id_mapping.values().any(|new_id| new_id == dep_id);
+                if !moved_too && !self.issue_path(dep_id).exists() {
+                    changes.push(Change::MissingInTarget {
+                        issue: issue.id.clone(),
+                        depends_on: dep_id.clone(),
+                    });
+                }
+            }
+
+            replace_ids_in_issue_text(&mut issue, &id_mapping);
+            issue.updated_at = chrono::Utc::now();
+            for comment in &mut comments {
+                comment.issue_id = issue.id.clone();
+                comment.body = replace_issue_ids_in_text(&comment.body, &id_mapping);
+            }
+            moved.push((issue, comments));
+        }
+
+        // Issues already here may point at a moved issue by its old ID; an
+        // old ID that names an issue of this database is left alone
+        let renamed: Vec<(&String, &String)> = mapping
+            .iter()
+            .filter(|(old_id, new_id)| {
+                old_id != new_id
+                    && !self.issue_path(old_id).exists()
+                    && !self.archived_path(old_id).exists()
+            })
+            .map(|(old_id, new_id)| (old_id, new_id))
+            .collect();
+        let mut referencing = Vec::new();
+        if !renamed.is_empty() {
+            for mut other in self.list_all_issues_no_dependents()? {
+                let mut has_changes = false;
+                for &(old_id, new_id) in &renamed {
+                    if let Some(dep_type) = other.depends_on.remove(old_id) {
+                        other.depends_on.insert(new_id.clone(), dep_type);
+                        changes.push(Change::UpdateDependency {
+                            issue: other.id.clone(),
+                            from: old_id.clone(),
+                            to: new_id.clone(),
+                        });
+                        has_changes = true;
+                    }
+                    let text = |issue: &Issue| {
+                        [
+                            issue.title.clone(),
+                            issue.description.clone(),
+                            issue.design.clone(),
+                            issue.notes.clone(),
+                            issue.acceptance_criteria.clone(),
+                        ]
+                    };
+                    let before = text(&other);
+                    let single = HashMap::from([(old_id.clone(), new_id.clone())]);
+                    replace_ids_in_issue_text(&mut other, &single);
+                    if text(&other) != before {
+                        changes.push(Change::UpdateTextReferences {
+                            issue: other.id.clone(),
+                            from: old_id.clone(),
+                            to: new_id.clone(),
+                        });
+                        has_changes = true;
+                    }
+                }
+                if has_changes {
+                    other.updated_at = chrono::Utc::now();
+                    referencing.push(other);
+                }
+            }
+        }
+
+        if dry_run {
+            return Ok((mapping, changes));
+        }
+
+        for (issue, comments) in &moved {
+            self.write_issue_file(&self.issue_path(&issue.id), issue)
+                .with_context(|| format!("Failed to write issue: {}", issue.id))?;
+            if !comments.is_empty() {
+                self.write_comments_no_lock(&issue.id, comments)?;
+            }
+        }
+        for other in &referencing {
+            self.write_issue_file(&self.issue_path(&other.id), other)
+                .with_context(|| format!("Failed to update issue: {}", other.id))?;
+        }
+
+        Ok((mapping, changes))
+    }
+
+    /// Delete issues (and their comments) after `move-to` copied them to
+    /// another database. Issues left behind that still depend on a removed
+    /// one are reported, not changed. Returns the list of changes;
+    /// `dry_run` only computes them.
+    pub fn remove_moved_issues(&self, ids: &[String], dry_run: bool) -> Result<Vec<Change>> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let mut changes: Vec<Change> = ids
+            .iter()
+            .map(|id| Change::DeleteIssue { id: id.clone() })
+            .collect();
+        for issue in self.list_all_issues_no_dependents()? {
+            if ids.contains(&issue.id) {
+                continue;
+            }
+            for dep_id in issue.depends_on.keys() {
+                if ids.contains(dep_id) {
+                    changes.push(Change::DependsOnMoved {
+                        issue: issue.id.clone(),
+                        depends_on: dep_id.clone(),
+                    });
+                }
+            }
+        }
+
+        if dry_run {
+            return Ok(changes);
+        }
+
+        for id in ids {
+            fs::remove_file(self.issue_path(id))
+                .with_context(|| format!("Failed to delete {}", id))?;
+            let comments = self.comment_path(id);
+            if comments.exists() {
+                fs::remove_file(&comments)
+                    .with_context(|| format!("Failed to delete comments of {}", id))?;
+            }
+        }
+
+        Ok(changes)
+    }
+
     /// Repair broken references by scanning all issues and fixing stale references
     ///
-    /// This scans all issues and removes references to nonexistent issues
-    pub fn repair_references(&self, dry_run: bool) -> Result<Vec<String>> {
+    /// This scans all issues and removes references to nonexistent issues.
+    /// Returns one change per removed reference (empty if none were broken),
+    /// plus a note for each edge to an archived issue, which is kept.
+    pub fn repair_references(&self, dry_run: bool) -> Result<Vec<Change>> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
         let mut changes = Vec::new();
         let all_issues = self.list_all_issues_no_dependents()?;
 
-        // Build a set of all valid issue IDs
-        let valid_ids: std::collections::HashSet<String> =
-            all_issues.iter().map(|i| i.id.clone()).collect();
+        // Build a set of all valid issue IDs; archived issues still exist
+        let archived_ids = self.archived_ids()?;
+        let valid_ids: std::collections::HashSet<String> = all_issues
+            .iter()
+            .map(|i| i.id.clone())
+            .chain(archived_ids.iter().cloned())
+            .collect();
 
         // Find issues with broken references
         for issue in all_issues {
             let mut broken_refs = Vec::new();
 
             for dep_id in issue.depends_on.keys() {
-                if !valid_ids.contains(dep_id) {
+                if archived_ids.contains(dep_id) {
+                    changes.push(Change::DependsOnArchived {
+                        issue: issue.id.clone(),
+                        depends_on: dep_id.clone(),
+                    });
+                } else if !valid_ids.contains(dep_id) {
                     broken_refs.push(dep_id.clone());
                 }
             }
 
             if !broken_refs.is_empty() {
                 for broken_ref in &broken_refs {
-                    changes.push(format!(
-                        "Remove broken reference in {}: {} (does not exist)",
-                        issue.id, broken_ref
-                    ));
+                    changes.push(Change::RemoveBrokenReference {
+                        issue: issue.id.clone(),
+                        target: broken_ref.clone(),
+                    });
                 }
 
                 // If not dry-run, apply the fix
@@ -1210,61 +2921,189 @@ impl Storage {
                     updated_issue.updated_at = chrono::Utc::now();
 
                     let issue_path = self.issues_dir.join(format!("{}.md", updated_issue.id));
-                    let markdown = issue_to_markdown(&updated_issue)?;
-                    fs::write(&issue_path, markdown)
+                    self.write_issue_file(&issue_path, &updated_issue)
                         .context(format!("Failed to update issue: {}", updated_issue.id))?;
                 }
             }
         }
 
-        if changes.is_empty() {
-            changes.push("No broken references found".to_string());
-        }
-
         Ok(changes)
     }
 
     /// Validate that a dependency target exists (warns if not)
-    fn validate_dependency_exists(&self, dep_id: &str) -> bool {
+    fn validate_dependency_exists(&self, dep_id: &str) -> Result<()> {
         let dep_path = self.issues_dir.join(format!("{}.md", dep_id));
-        let exists = dep_path.exists();
+        if dep_path.exists() || self.archived_path(dep_id).exists() {
+            return Ok(());
+        }
 
-        if !exists {
-            eprintln!("Warning: Dependency target does not exist: {}", dep_id);
-            eprintln!("  This issue will be blocked until {} is created.", dep_id);
+        if self.strict_deps {
+            anyhow::bail!(
+                "Dependency target does not exist: {} (strict dependency checking is enabled)",
+                dep_id
+            );
         }
 
-        exists
+        eprintln!("Warning: Dependency target does not exist: {}", dep_id);
+        eprintln!("  This issue will be blocked until {} is created.", dep_id);
+        Ok(())
     }
 
-    /// Add a dependency between issues
+    /// Add a dependency between issues.
+    ///
+    /// Re-adding an existing edge with the same type is a no-op and returns
+    /// `Ok(false)`. An existing edge with a different type is an error unless
+    /// `force` is set, in which case its type is replaced.
     pub fn add_dependency(
         &self,
         from_id: &str,
         to_id: &str,
         dep_type: DependencyType,
-    ) -> Result<()> {
+        force: bool,
+    ) -> Result<bool> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let Some((issue_path, issue)) = self.prepare_dependency(from_id, to_id, dep_type, force)?
+        else {
+            return Ok(false);
+        };
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
+
+        Ok(true)
+    }
+
+    /// Add a `dep_type` dependency from `from_id` on each of `to_ids` under
+    /// one lock. Each target is checked and written on its own, so a bad
+    /// target is reported without stopping the rest. Returns, per target in
+    /// order, whether the edge was newly added or why it was not.
+    pub fn add_dependencies(
+        &self,
+        from_id: &str,
+        to_ids: &[String],
+        dep_type: DependencyType,
+        force: bool,
+    ) -> Result<Vec<(String, Result<bool>)>> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        Ok(to_ids
+            .iter()
+            .map(|to_id| {
+                let added = self
+                    .prepare_dependency(from_id, to_id, dep_type, force)
+                    .and_then(|prepared| match prepared {
+                        Some((issue_path, issue)) => {
+                            self.write_issue_file(&issue_path, &issue)
+                                .context("Failed to write issue file")?;
+                            Ok(true)
+                        }
+                        None => Ok(false),
+                    });
+                (to_id.clone(), added)
+            })
+            .collect())
+    }
+
+    /// Add a `related` dependency in both directions under one lock. Both
+    /// edges are checked before either file is written, so a failure leaves
+    /// both issues untouched. Returns whether each edge (a -> b, b -> a) was
+    /// newly added.
+    pub fn add_related_dependency(&self, a: &str, b: &str, force: bool) -> Result<(bool, bool)> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
+        let forward = self.prepare_dependency(a, b, DependencyType::Related, force)?;
+        let reverse = self.prepare_dependency(b, a, DependencyType::Related, force)?;
+        let added = (forward.is_some(), reverse.is_some());
+        for (issue_path, issue) in forward.into_iter().chain(reverse) {
+            self.write_issue_file(&issue_path, &issue)
+                .context("Failed to write issue file")?;
+        }
+
+        Ok(added)
+    }
+
+    /// Read `from_id` and return it with the `to_id` edge added, ready to
+    /// write, or `None` when the edge already exists with the same type.
+    /// The caller holds the lock.
+    fn prepare_dependency(
+        &self,
+        from_id: &str,
+        to_id: &str,
+        dep_type: DependencyType,
+        force: bool,
+    ) -> Result<Option<(PathBuf, Issue)>> {
         let issue_path = self.issues_dir.join(format!("{}.md", from_id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", from_id);
+            return Err(error::not_found(format!("Issue not found: {}", from_id)));
+        }
+        if from_id == to_id {
+            return Err(error::invalid_input(format!(
+                "{} cannot depend on itself",
+                from_id
+            )));
         }
 
-        // Validate dependency target exists (warn if not)
-        self.validate_dependency_exists(to_id);
+        // Validate dependency target exists (warn, or fail in strict mode)
+        self.validate_dependency_exists(to_id)?;
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(from_id, &content)?;
+        let mut issue = self.parse_markdown(from_id, &content)?;
+
+        match issue.depends_on.get(to_id) {
+            Some(existing) if *existing == dep_type => return Ok(None),
+            Some(existing) if !force => anyhow::bail!(
+                "{} already has dependency on {} ({}); use --force to change it to {}",
+                from_id,
+                to_id,
+                existing,
+                dep_type
+            ),
+            _ => {}
+        }
 
         // Add dependency
         issue.depends_on.insert(to_id.to_string(), dep_type);
         issue.updated_at = chrono::Utc::now();
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        Ok(Some((issue_path, issue)))
+    }
 
-        Ok(())
+    /// Change the type of an existing dependency, e.g. when a hard blocker
+    /// softens to merely related. Returns the previous type, or `None` (and
+    /// writes nothing) if the edge already has `dep_type`. A missing issue or
+    /// edge is a not-found error.
+    pub fn set_dependency_type(
+        &self,
+        from_id: &str,
+        to_id: &str,
+        dep_type: DependencyType,
+    ) -> Result<Option<DependencyType>> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        let issue_path = self.issues_dir.join(format!("{}.md", from_id));
+        if !issue_path.exists() {
+            return Err(error::not_found(format!("Issue not found: {}", from_id)));
+        }
+
+        let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
+        let mut issue = self.parse_markdown(from_id, &content)?;
+
+        let Some(existing) = issue.depends_on.get_mut(to_id) else {
+            return Err(error::not_found(format!(
+                "Dependency not found: {} -> {}",
+                from_id, to_id
+            )));
+        };
+        if *existing == dep_type {
+            return Ok(None);
+        }
+        let previous = std::mem::replace(existing, dep_type);
+        issue.updated_at = chrono::Utc::now();
+
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
+
+        Ok(Some(previous))
     }
 
     pub fn remove_dependency(&self, from_id: &str, to_id: &str) -> Result<()> {
@@ -1272,35 +3111,49 @@ impl Storage {
 
         let issue_path = self.issues_dir.join(format!("{}.md", from_id));
         if !issue_path.exists() {
-            anyhow::bail!("Issue not found: {}", from_id);
+            return Err(error::not_found(format!("Issue not found: {}", from_id)));
         }
 
         let content = fs::read_to_string(&issue_path).context("Failed to read issue file")?;
-        let mut issue = markdown_to_issue(from_id, &content)?;
+        let mut issue = self.parse_markdown(from_id, &content)?;
 
         // Remove dependency
         if issue.depends_on.remove(to_id).is_none() {
-            anyhow::bail!("Dependency not found: {} -> {}", from_id, to_id);
+            return Err(error::not_found(format!(
+                "Dependency not found: {} -> {}",
+                from_id, to_id
+            )));
         }
         issue.updated_at = chrono::Utc::now();
 
-        let markdown = issue_to_markdown(&issue)?;
-        fs::write(&issue_path, markdown).context("Failed to write issue file")?;
+        self.write_issue_file(&issue_path, &issue)
+            .context("Failed to write issue file")?;
 
         Ok(())
     }
 
     /// Get dependency tree starting from a given issue
+    ///
+    /// A dependency that leads back to an issue already on the current path
+    /// becomes an `is_cycle` leaf, so the tree stays finite for any
+    /// `max_depth`. Diamond dependencies are expanded under every path that
+    /// reaches them; `show_all_paths` is accepted for upstream compatibility
+    /// and no longer disables the cycle check.
+    ///
+    /// `max_children` keeps at most that many dependencies (in ID order) per
+    /// node and counts the rest in `omitted_children`, bounding the tree for
+    /// hub issues with huge fan-outs.
     pub fn get_dependency_tree(
         &self,
         issue_id: &str,
         max_depth: usize,
-        show_all_paths: bool,
+        _show_all_paths: bool,
+        max_children: Option<usize>,
     ) -> Result<crate::types::TreeNode> {
         use std::collections::HashSet;
 
         // Load all issues to build the tree
-        let issues = self.list_issues(None, None, None, None, None)?;
+        let issues = self.list_issues(&IssueFilter::new(), None)?;
         let issues_map: HashMap<String, Issue> = issues
             .into_iter()
             .map(|issue| (issue.id.clone(), issue))
@@ -1309,29 +3162,115 @@ impl Storage {
         // Find the root issue
         let root_issue = issues_map
             .get(issue_id)
-            .ok_or_else(|| anyhow::anyhow!("Issue not found: {}", issue_id))?;
+            .ok_or_else(|| error::not_found(format!("Issue not found: {}", issue_id)))?;
 
-        // Track visited nodes for cycle detection (if not showing all paths)
-        let mut visited = HashSet::new();
+        // Issues on the path from the root to the current node, for cycle detection
+        let mut path = HashSet::new();
 
         // Build tree recursively
         build_tree_node(
             root_issue,
             &issues_map,
-            &mut visited,
+            &mut path,
             0,
             max_depth,
-            show_all_paths,
+            max_children,
             None,
         )
     }
 
+    /// Longest chain of unresolved `blocks` dependencies leading into an issue
+    ///
+    /// Returns issue IDs ordered from the first issue that has to be done to
+    /// `issue_id` itself, so the path is the minimum sequence of work before
+    /// `issue_id` can start. Closed and nonexistent blockers are skipped.
+    /// Issues that block each other in a cycle count as one step and are
+    /// all listed together. Ties go to the lexicographically smallest
+    /// blocker.
+    pub fn get_critical_path(&self, issue_id: &str) -> Result<Vec<String>> {
+        let issues = self.list_issues(&IssueFilter::new(), None)?;
+        let issues_map: HashMap<String, Issue> = issues
+            .into_iter()
+            .map(|issue| (issue.id.clone(), issue))
+            .collect();
+
+        let Some((root, _)) = issues_map.get_key_value(issue_id) else {
+            return Err(error::not_found(format!("Issue not found: {}", issue_id)));
+        };
+
+        let graph = BlockingGraph::new(root, &issues_map);
+        let chain = graph.chain_to(root, &mut HashMap::new());
+        Ok(chain.into_iter().map(str::to_string).collect())
+    }
+
+    /// IDs of `root` and every issue reachable from it along dependency
+    /// edges in `direction`, following all dependency types
+    ///
+    /// Cycles are harmless: each issue is visited once. Edges to issues that
+    /// do not exist are not followed, so every returned ID can be loaded.
+    pub fn dependency_closure(
+        &self,
+        root: &str,
+        direction: ClosureDirection,
+    ) -> Result<HashSet<String>> {
+        let issues = self.list_issues(&IssueFilter::new(), None)?;
+        let issues_map: HashMap<&str, &Issue> = issues
+            .iter()
+            .map(|issue| (issue.id.as_str(), issue))
+            .collect();
+        if !issues_map.contains_key(root) {
+            return Err(error::not_found(format!("Issue not found: {}", root)));
+        }
+
+        let mut dependents: HashMap<&str, Vec<&str>> = HashMap::new();
+        if direction != ClosureDirection::Dependencies {
+            for issue in &issues {
+                for dep_id in issue.depends_on.keys() {
+                    dependents
+                        .entry(dep_id.as_str())
+                        .or_default()
+                        .push(issue.id.as_str());
+                }
+            }
+        }
+
+        // Walk one direction only; `Both` joins the two walks from the root,
+        // so a dependent's other dependencies are not pulled in
+        let walk = |forward: bool| {
+            let mut closure = HashSet::new();
+            let mut stack = vec![root];
+            while let Some(id) = stack.pop() {
+                if !closure.insert(id.to_string()) {
+                    continue;
+                }
+                if forward {
+                    stack.extend(
+                        issues_map[id]
+                            .depends_on
+                            .keys()
+                            .map(String::as_str)
+                            .filter(|dep_id| issues_map.contains_key(dep_id)),
+                    );
+                } else if let Some(ids) = dependents.get(id) {
+                    stack.extend(ids.iter().copied());
+                }
+            }
+            closure
+        };
+
+        let mut closure = walk(direction != ClosureDirection::Dependents);
+        if direction == ClosureDirection::Both {
+            closure.extend(walk(false));
+        }
+        Ok(closure)
+    }
+
     /// Detect dependency cycles in the issue graph
     pub fn detect_dependency_cycles(&self) -> Result<Vec<Vec<String>>> {
         use std::collections::{HashMap, HashSet};
 
         // Load all issues
-        let issues = self.list_issues(None, None, None, None, None)?;
+        let issues = self.list_issues(&IssueFilter::new(), None)?;
         let issues_map: HashMap<String, Issue> = issues
             .into_iter()
             .map(|issue| (issue.id.clone(), issue))
@@ -1358,6 +3297,41 @@ impl Storage {
 
         Ok(cycles)
     }
+
+    /// The dependency graph among the issues `filter` selects. Edges to
+    /// issues outside the selection (or missing entirely) are left out.
+    pub fn dependency_graph(&self, filter: &IssueFilter) -> Result<DependencyGraph> {
+        let issues = self.list_issues_for_export(filter)?;
+        let ids: std::collections::HashSet<&str> =
+            issues.iter().map(|issue| issue.id.as_str()).collect();
+
+        let mut edges: Vec<GraphEdge> = issues
+            .iter()
+            .flat_map(|issue| {
+                issue
+                    .depends_on
+                    .iter()
+                    .filter(|(dep_id, _)| ids.contains(dep_id.as_str()))
+                    .map(|(dep_id, dep_type)| GraphEdge {
+                        from: dep_id.clone(),
+                        to: issue.id.clone(),
+                        dep_type: *dep_type,
+                    })
+            })
+            .collect();
+        edges.sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+
+        let nodes = issues
+            .into_iter()
+            .map(|issue| GraphNode {
+                id: issue.id,
+                title: issue.title,
+                status: issue.status,
+            })
+            .collect();
+
+        Ok(DependencyGraph { nodes, edges })
+    }
 }
 
 fn normalize_config_key(key: &str) -> String {
@@ -1395,14 +3369,72 @@ fn normalize_labels(labels: Vec<String>) -> Vec<String> {
         .collect()
 }
 
+/// Jaccard similarity of two titles' word sets: the number of shared words
+/// over the number of distinct words in either. Words are lowercased runs of
+/// alphanumeric characters, so punctuation and case are ignored. Two titles
+/// with no words at all have similarity 0.
+pub fn title_similarity(a: &str, b: &str) -> f64 {
+    let words = |title: &str| -> HashSet<String> {
+        title
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Recursively copy `from` into the existing directory `to`, skipping entries
+/// named in `skip`. Returns the number of files copied.
+fn copy_dir_except(from: &Path, to: &Path, skip: &[&str]) -> Result<usize> {
+    let mut copied = 0;
+    for entry in fs::read_dir(from).with_context(|| format!("Failed to read {}", from.display()))? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if skip.iter().any(|skipped| name == **skipped) {
+            continue;
+        }
+        let target = to.join(&name);
+        if entry.file_type()?.is_dir() {
+            fs::create_dir(&target)
+                .with_context(|| format!("Failed to create {}", target.display()))?;
+            copied += copy_dir_except(&entry.path(), &target, skip)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .with_context(|| format!("Failed to copy to {}", target.display()))?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// Append `text` to a text field as a new line, so repeated appends build
+/// up a journal without touching the earlier lines. Empty text is a no-op.
+fn append_line(field: &mut String, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let existing = field.trim_end();
+    *field = if existing.is_empty() {
+        text.to_string()
+    } else {
+        format!("{existing}\n{text}")
+    };
+}
+
 /// Recursively build a tree node
 fn build_tree_node(
     issue: &Issue,
     issues_map: &HashMap<String, Issue>,
-    visited: &mut std::collections::HashSet<String>,
+    path: &mut std::collections::HashSet<String>,
     current_depth: usize,
     max_depth: usize,
-    show_all_paths: bool,
+    max_children: Option<usize>,
     dep_type: Option<String>,
 ) -> Result<crate::types::TreeNode> {
     let mut node = crate::types::TreeNode {
@@ -1414,10 +3446,11 @@ fn build_tree_node(
         children: Vec::new(),
         is_cycle: false,
         depth_exceeded: false,
+        omitted_children: 0,
     };
 
-    // Check for cycle
-    if !show_all_paths && visited.contains(&issue.id) {
+    // Check for cycle: this issue is already one of its own ancestors
+    if path.contains(&issue.id) {
         node.is_cycle = true;
         return Ok(node);
     }
@@ -1428,31 +3461,34 @@ fn build_tree_node(
         return Ok(node);
     }
 
-    // Mark as visited (if not showing all paths)
-    if !show_all_paths {
-        visited.insert(issue.id.clone());
+    path.insert(issue.id.clone());
+
+    // Add children (dependencies), in ID order so a cap keeps the same ones
+    let mut deps: Vec<(&String, &DependencyType)> = issue
+        .depends_on
+        .iter()
+        .filter(|(dep_id, _)| issues_map.contains_key(*dep_id))
+        .collect();
+    deps.sort_by_key(|(dep_id, _)| *dep_id);
+    if let Some(max) = max_children {
+        node.omitted_children = deps.len().saturating_sub(max);
+        deps.truncate(max);
     }
-
-    // Add children (dependencies)
-    for (dep_id, dep_type_val) in &issue.depends_on {
-        if let Some(dep_issue) = issues_map.get(dep_id) {
-            let child = build_tree_node(
-                dep_issue,
-                issues_map,
-                visited,
-                current_depth + 1,
-                max_depth,
-                show_all_paths,
-                Some(dep_type_val.to_string()),
-            )?;
-            node.children.push(child);
-        }
+    for (dep_id, dep_type_val) in deps {
+        let child = build_tree_node(
+            &issues_map[dep_id],
+            issues_map,
+            path,
+            current_depth + 1,
+            max_depth,
+            max_children,
+            Some(dep_type_val.to_string()),
+        )?;
+        node.children.push(child);
     }
 
-    // Unmark as visited (for backtracking if not showing all paths)
-    if !show_all_paths {
-        visited.remove(&issue.id);
-    }
+    // Backtrack: siblings may reach this issue again without a cycle
+    path.remove(&issue.id);
 
     Ok(node)
 }
@@ -1496,15 +3532,153 @@ fn find_cycles_dfs(
     path.pop();
 }
 
-/// Check if two cycles are equal (considering rotation)
-fn cycles_equal(cycle1: &[String], cycle2: &[String]) -> bool {
-    if cycle1.len() != cycle2.len() {
-        return false;
+/// Open `blocks` edges of the issue graph for `get_critical_path`, with the
+/// strongly connected components reachable from one root. Issues that block
+/// each other in a cycle share a component, and the components form a DAG,
+/// so chains can be memoized per component.
+struct BlockingGraph<'a> {
+    /// Each issue's open blockers, in ID order
+    blockers: HashMap<&'a str, Vec<&'a str>>,
+    component: HashMap<&'a str, usize>,
+    members: Vec<Vec<&'a str>>,
+}
+
+impl<'a> BlockingGraph<'a> {
+    fn new(root: &'a str, issues_map: &'a HashMap<String, Issue>) -> Self {
+        let blockers = issues_map
+            .values()
+            .map(|issue| {
+                let mut ids: Vec<&str> = issue
+                    .depends_on
+                    .iter()
+                    .filter(|(_, dep_type)| **dep_type == DependencyType::Blocks)
+                    .map(|(id, _)| id.as_str())
+                    .filter(|id| {
+                        issues_map
+                            .get(*id)
+                            .is_some_and(|blocker| blocker.status != Status::Closed)
+                    })
+                    .collect();
+                ids.sort_unstable();
+                (issue.id.as_str(), ids)
+            })
+            .collect();
+        let mut graph = Self {
+            blockers,
+            component: HashMap::new(),
+            members: Vec::new(),
+        };
+        let mut tarjan = Tarjan::default();
+        graph.connect(root, &mut tarjan);
+        graph
     }
 
-    // Check all rotations
-    for i in 0..cycle1.len() {
-        let mut match_found = true;
+    /// Tarjan's algorithm: assign `id` and everything it reaches to components
+    fn connect(&mut self, id: &'a str, tarjan: &mut Tarjan<'a>) {
+        let index = tarjan.index.len();
+        tarjan.index.insert(id, index);
+        tarjan.low.insert(id, index);
+        tarjan.stack.push(id);
+        tarjan.on_stack.insert(id);
+
+        for blocker in self.blockers[id].clone() {
+            if !tarjan.index.contains_key(blocker) {
+                self.connect(blocker, tarjan);
+                let low = tarjan.low[id].min(tarjan.low[blocker]);
+                tarjan.low.insert(id, low);
+            } else if tarjan.on_stack.contains(blocker) {
+                let low = tarjan.low[id].min(tarjan.index[blocker]);
+                tarjan.low.insert(id, low);
+            }
+        }
+
+        if tarjan.low[id] == index {
+            let mut members = Vec::new();
+            while let Some(member) = tarjan.stack.pop() {
+                tarjan.on_stack.remove(member);
+                self.component.insert(member, self.members.len());
+                members.push(member);
+                if member == id {
+                    break;
+                }
+            }
+            members.sort_unstable();
+            self.members.push(members);
+        }
+    }
+
+    /// The longest chain ending at `id`: the longest chain below its
+    /// component, then the component itself in the order work reaches `id`
+    fn chain_to(&self, id: &'a str, memo: &mut HashMap<usize, Vec<&'a str>>) -> Vec<&'a str> {
+        let component = self.component[id];
+        let mut chain = self.chain_below(component, memo);
+
+        // Members of a cycle, walked from `id` along blockers and listed
+        // first-to-do first
+        let mut order = Vec::new();
+        let mut stack = vec![id];
+        let mut seen = HashSet::new();
+        while let Some(member) = stack.pop() {
+            if !seen.insert(member) {
+                continue;
+            }
+            order.push(member);
+            stack.extend(
+                self.blockers[member]
+                    .iter()
+                    .rev()
+                    .filter(|blocker| self.component[*blocker] == component),
+            );
+        }
+        chain.extend(order.into_iter().rev());
+        chain
+    }
+
+    /// The longest chain into any member of `component` from outside it.
+    /// Ties go to the smallest member, then its smallest blocker.
+    fn chain_below(
+        &self,
+        component: usize,
+        memo: &mut HashMap<usize, Vec<&'a str>>,
+    ) -> Vec<&'a str> {
+        if let Some(chain) = memo.get(&component) {
+            return chain.clone();
+        }
+        let mut longest = Vec::new();
+        for member in &self.members[component] {
+            for blocker in &self.blockers[member] {
+                if self.component[blocker] == component {
+                    continue;
+                }
+                let chain = self.chain_to(blocker, memo);
+                if chain.len() > longest.len() {
+                    longest = chain;
+                }
+            }
+        }
+        memo.insert(component, longest.clone());
+        longest
+    }
+}
+
+/// Bookkeeping for `BlockingGraph::connect`
+#[derive(Default)]
+struct Tarjan<'a> {
+    index: HashMap<&'a str, usize>,
+    low: HashMap<&'a str, usize>,
+    stack: Vec<&'a str>,
+    on_stack: HashSet<&'a str>,
+}
+
+/// Check if two cycles are equal (considering rotation)
+fn cycles_equal(cycle1: &[String], cycle2: &[String]) -> bool {
+    if cycle1.len() != cycle2.len() {
+        return false;
+    }
+
+    // Check all rotations
+    for i in 0..cycle1.len() {
+        let mut match_found = true;
         for j in 0..cycle1.len() {
             if cycle1[(i + j) % cycle1.len()] != cycle2[j] {
                 match_found = false;
@@ -1545,62 +3719,68 @@ impl Storage {
         }
     }
 
-    /// List all issues
-    pub fn list_issues(
-        &self,
-        status: Option<Status>,
-        priority: Option<Vec<i32>>,
-        issue_type: Option<IssueType>,
-        assignee: Option<&str>,
-        limit: Option<usize>,
-    ) -> Result<Vec<Issue>> {
-        let _lock = Lock::acquire(&self.beads_dir)?;
-
-        let entries = fs::read_dir(&self.issues_dir).context("Failed to read issues directory")?;
-
-        let mut issues = Vec::new();
-        for entry in entries {
-            let entry = entry?;
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
+    /// List the issues selected by `filter`, in `list` order
+    pub fn list_issues(&self, filter: &IssueFilter, limit: Option<usize>) -> Result<Vec<Issue>> {
+        self.list_issues_with_blockers(filter, limit)
+            .map(|(issues, _)| issues)
+    }
 
-            if !name_str.ends_with(".md") {
-                continue;
-            }
+    /// How many in_progress issues `assignee` holds: their work in progress,
+    /// for `mb ready --capacity`
+    pub fn in_progress_count(&self, assignee: &str) -> Result<usize> {
+        let filter = IssueFilter::new()
+            .status(Status::InProgress)
+            .assignee(assignee);
+        Ok(self.list_issues(&filter, None)?.len())
+    }
 
-            let issue_id = &name_str[..name_str.len() - 3];
-            let content = fs::read_to_string(entry.path())?;
-            let issue = markdown_to_issue(issue_id, &content)?;
+    /// Load every issue once, sorted and with `dependents` populated as by
+    /// `list_issues`, and lend the slice to `f`.
+    ///
+    /// For library users computing several reports from one snapshot instead
+    /// of paying a directory scan per query. The lock is released before `f`
+    /// runs, so `f` may call other storage methods.
+    #[allow(dead_code)] // library API; the CLI uses the dedicated queries
+    pub fn with_all_issues<T>(&self, f: impl FnOnce(&[Issue]) -> T) -> Result<T> {
+        let issues = self.list_issues(&IssueFilter::new(), None)?;
+        Ok(f(&issues))
+    }
 
-            // Apply filters
-            if let Some(s) = status {
-                if issue.status != s {
-                    continue;
-                }
-            }
-            if let Some(ref priorities) = priority {
-                if !priorities.contains(&issue.priority) {
-                    continue;
-                }
-            }
-            if let Some(t) = issue_type {
-                if issue.issue_type != t {
-                    continue;
-                }
-            }
-            if let Some(a) = assignee {
-                if issue.assignee != a {
-                    continue;
-                }
-            }
+    /// `list_issues`, also returning the status index of every issue (not
+    /// just the listed ones) that readiness was decided against
+    fn list_issues_with_blockers(
+        &self,
+        filter: &IssueFilter,
+        limit: Option<usize>,
+    ) -> Result<(Vec<Issue>, BlockerStatuses)> {
+        let _lock = Lock::acquire(&self.beads_dir)?;
 
-            issues.push(issue);
+        let mut issues = self.read_all_issues()?;
+        let mut blockers = self.new_blocker_statuses()?;
+        for issue in &issues {
+            blockers.insert(issue);
         }
 
-        // Sort so that numeric IDs are clustered first, in ascending numeric
+        issues.retain(|issue| filter.matches_with_blockers(issue, &blockers));
+
+        // By default numeric IDs are clustered first, in ascending numeric
         // order (1..N) so the most recent ones appear at the end, followed by
-        // hash-based IDs ordered by creation date (oldest first).
-        issues.sort_by(compare_for_list);
+        // hash-based IDs ordered by creation date (oldest first). Other sort
+        // keys fall back to that order for ties.
+        issues.sort_by(|a, b| {
+            let ordering = match filter.sort {
+                ListSort::Id => compare_for_list(a, b),
+                ListSort::Updated => a
+                    .updated_at
+                    .cmp(&b.updated_at)
+                    .then_with(|| compare_for_list(a, b)),
+            };
+            if filter.reverse {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
 
         // Apply limit
         if let Some(limit) = limit {
@@ -1612,12 +3792,12 @@ impl Storage {
         // Populate dependents
         Self::populate_dependents(&mut issues);
 
-        Ok(issues)
+        Ok((issues, blockers))
     }
 
     /// Get statistics
     pub fn get_stats(&self) -> Result<Stats> {
-        let issues = self.list_issues(None, None, None, None, None)?;
+        let (issues, blockers) = self.list_issues_with_blockers(&IssueFilter::new(), None)?;
 
         let total = issues.len();
         let open = issues.iter().filter(|i| i.status == Status::Open).count();
@@ -1626,18 +3806,33 @@ impl Storage {
             .filter(|i| i.status == Status::InProgress)
             .count();
         let closed = issues.iter().filter(|i| i.status == Status::Closed).count();
+        let mut closed_by_resolution = BTreeMap::new();
+        for issue in issues.iter().filter(|i| i.status == Status::Closed) {
+            if let Some(resolution) = issue.resolution {
+                *closed_by_resolution.entry(resolution).or_insert(0) += 1;
+            }
+        }
 
         // Calculate blocked issues (those with blocking dependencies)
         let blocked = issues
             .iter()
-            .filter(|i| i.status != Status::Closed && i.has_blocking_dependencies())
+            .filter(|i| i.matches_readiness(Readiness::Blocked, &blockers))
             .count();
 
-        // Calculate ready issues
-        let ready = issues
+        // Calculate ready issues, and the unassigned ones an idle agent can take
+        let ready: Vec<&Issue> = issues
             .iter()
-            .filter(|i| i.status == Status::Open && !i.has_blocking_dependencies())
-            .count();
+            .filter(|i| i.matches_readiness(Readiness::Ready, &blockers))
+            .collect();
+        let ready_unassigned = ready.iter().filter(|i| i.assignee.is_empty()).count();
+
+        // Longest hash suffix in use, to anticipate when IDs will need to widen
+        let max_hash_id_length = issues
+            .iter()
+            .filter_map(|i| IssueId::parse(&i.id).ok())
+            .filter(|id| !id.is_numeric())
+            .map(|id| id.suffix().len())
+            .max();
 
         // Calculate average lead time for closed issues
         let mut lead_times = Vec::new();
@@ -1662,55 +3857,275 @@ impl Storage {
             in_progress_issues: in_progress,
             blocked_issues: blocked,
             closed_issues: closed,
-            ready_issues: ready,
+            ready_issues: ready.len(),
+            ready_unassigned_issues: ready_unassigned,
+            max_hash_id_length,
+            closed_by_resolution,
             average_lead_time_hours: avg_lead_time_hours,
         })
     }
 
-    /// Get blocked issues
-    pub fn get_blocked(&self) -> Result<Vec<BlockedIssue>> {
-        let issues = self.list_issues(None, None, None, None, None)?;
+    /// Whether `id` is ready to work on and, if not, why: closed, held up by
+    /// unresolved blockers (with their statuses), or kept out of `ready` by
+    /// its own status. Readiness is decided the same way as for `mb ready`.
+    pub fn readiness(&self, id: &str) -> Result<ReadinessReport> {
+        let (issues, blockers) = self.list_issues_with_blockers(&IssueFilter::new(), None)?;
+        let issue = issues
+            .iter()
+            .find(|issue| issue.id == id)
+            .ok_or_else(|| error::not_found(format!("Issue not found: {}", id)))?;
 
-        let mut blocked = Vec::new();
-        for issue in issues {
-            if issue.status == Status::Closed {
-                continue;
-            }
+        if issue.status == Status::Closed {
+            return Ok(ReadinessReport::Closed);
+        }
+        let mut by: Vec<Blocker> = issue
+            .get_blocking_dependencies(&blockers)
+            .map(|blocker| Blocker {
+                id: blocker.clone(),
+                status: blockers.status(blocker),
+            })
+            .collect();
+        if !by.is_empty() {
+            by.sort_by(|a, b| a.id.cmp(&b.id));
+            return Ok(ReadinessReport::Blocked { by });
+        }
+        Ok(match issue.status {
+            Status::Open => ReadinessReport::Ready,
+            status => ReadinessReport::NotOpen { status },
+        })
+    }
 
-            // Zero-copy: collect blocking dependencies directly without intermediate Vec
-            let blocked_by: Vec<String> = issue.get_blocking_dependencies().cloned().collect();
+    /// Other issues that `issue` mentions by ID in its title, description,
+    /// design, acceptance criteria or notes, in order of first mention.
+    /// Mentions are matched as whole tokens, as when IDs are rewritten on
+    /// rename; IDs that do not resolve to a loaded issue are ignored.
+    pub fn issue_references(&self, issue: &Issue) -> Result<Vec<IssueReference>> {
+        let issues: HashMap<String, Issue> = self
+            .list_issues(&IssueFilter::new(), None)?
+            .into_iter()
+            .filter(|other| other.id != issue.id)
+            .map(|other| (other.id.clone(), other))
+            .collect();
+        let Some(re) = issue_id_regex(issues.keys()) else {
+            return Ok(Vec::new());
+        };
 
-            if !blocked_by.is_empty() {
-                let blocked_by_count = blocked_by.len();
-                blocked.push(BlockedIssue {
-                    issue,
-                    blocked_by,
-                    blocked_by_count,
-                });
+        let mut seen = HashSet::new();
+        let mut references = Vec::new();
+        for text in [
+            &issue.title,
+            &issue.description,
+            &issue.design,
+            &issue.acceptance_criteria,
+            &issue.notes,
+        ] {
+            for caps in re.captures_iter(text) {
+                let id = &caps[1];
+                if seen.insert(id.to_string()) {
+                    let other = &issues[id];
+                    references.push(IssueReference {
+                        id: other.id.clone(),
+                        title: other.title.clone(),
+                        status: other.status,
+                    });
+                }
             }
         }
+        Ok(references)
+    }
+
+    /// Get blocked issues
+    pub fn get_blocked(&self) -> Result<Vec<BlockedIssue>> {
+        // Read everything: blockers' creation times feed `blocked_since`
+        let (issues, blockers) = self.list_issues_with_blockers(&IssueFilter::new(), None)?;
+        let created_at: HashMap<&str, chrono::DateTime<chrono::Utc>> = issues
+            .iter()
+            .map(|issue| (issue.id.as_str(), issue.created_at))
+            .collect();
+
+        let blocked = issues
+            .iter()
+            .filter(|issue| issue.matches_readiness(Readiness::Blocked, &blockers))
+            .map(|issue| {
+                // Only unresolved blockers: closed ones no longer count
+                let blocked_by: Vec<String> = issue
+                    .get_blocking_dependencies(&blockers)
+                    .cloned()
+                    .collect();
+                let blocked_since = if issue.status == Status::Blocked {
+                    Some(issue.updated_at)
+                } else {
+                    blocked_by
+                        .iter()
+                        .filter_map(|id| created_at.get(id.as_str()).copied())
+                        .max()
+                        .map(|latest| latest.max(issue.created_at))
+                };
+                BlockedIssue {
+                    issue: issue.clone(),
+                    blocked_by_count: blocked_by.len(),
+                    blocked_by,
+                    blocked_since,
+                }
+            })
+            .collect();
 
         Ok(blocked)
     }
 
-    /// Get ready work
-    ///
-    /// The caller is responsible for any post-query in-memory filtering (labels,
-    /// title, etc.) and for applying a result limit afterwards, so that limiting
-    /// happens after every filter has run.
-    pub fn get_ready(
-        &self,
-        assignee: Option<&str>,
-        priority: Option<Vec<i32>>,
-        issue_type: Option<IssueType>,
-        sort_policy: &str,
-    ) -> Result<Vec<Issue>> {
-        let issues = self.list_issues(Some(Status::Open), priority, issue_type, assignee, None)?;
+    /// Render a Markdown status report: the stats table, ready work, blocked
+    /// issues with their blockers, and issues closed since `opts.since`
+    pub fn generate_report(&self, opts: &ReportOptions) -> Result<String> {
+        use std::fmt::Write as _;
 
-        let mut ready: Vec<Issue> = issues
+        let stats = self.get_stats()?;
+        let ready = self.get_ready(&IssueFilter::new(), "hybrid")?;
+        let blocked = self.get_blocked()?;
+        let mut closed: Vec<Issue> = self
+            .list_issues(&IssueFilter::new().status(Status::Closed), None)?
             .into_iter()
-            .filter(|i| !i.has_blocking_dependencies())
+            .filter(|issue| issue.closed_at.is_some_and(|t| t >= opts.since))
             .collect();
+        closed.sort_by(|a, b| b.closed_at.cmp(&a.closed_at).then_with(|| a.id.cmp(&b.id)));
+
+        let summary = |issue: &Issue| {
+            format!(
+                "**{}** {} (P{}, {})",
+                issue.id, issue.title, issue.priority, issue.issue_type
+            )
+        };
+        let mut out = String::new();
+
+        writeln!(out, "# Project report: {}\n", self.get_prefix()?)?;
+        writeln!(
+            out,
+            "_Generated {}_\n",
+            chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+        )?;
+
+        writeln!(out, "## Summary\n")?;
+        writeln!(out, "| Status | Issues |")?;
+        writeln!(out, "|---|---:|")?;
+        for (label, count) in [
+            ("Open", stats.open_issues),
+            ("In progress", stats.in_progress_issues),
+            ("Blocked", stats.blocked_issues),
+            ("Ready", stats.ready_issues),
+            ("Closed", stats.closed_issues),
+            ("**Total**", stats.total_issues),
+        ] {
+            writeln!(out, "| {} | {} |", label, count)?;
+        }
+        writeln!(
+            out,
+            "\nAverage lead time: {:.1} hours\n",
+            stats.average_lead_time_hours
+        )?;
+
+        writeln!(out, "## Ready work ({})\n", ready.len())?;
+        for issue in &ready {
+            writeln!(out, "- {}", summary(issue))?;
+        }
+        if ready.is_empty() {
+            writeln!(out, "_None._")?;
+        }
+
+        writeln!(out, "\n## Blocked ({})\n", blocked.len())?;
+        for item in &blocked {
+            writeln!(
+                out,
+                "- {}, blocked by {}",
+                summary(&item.issue),
+                item.blocked_by.join(", ")
+            )?;
+        }
+        if blocked.is_empty() {
+            writeln!(out, "_None._")?;
+        }
+
+        writeln!(
+            out,
+            "\n## Closed since {} ({})\n",
+            opts.since.format("%Y-%m-%d"),
+            closed.len()
+        )?;
+        for issue in &closed {
+            let closed_at = issue.closed_at.expect("filtered on closed_at");
+            writeln!(
+                out,
+                "- {}, closed {}",
+                summary(issue),
+                closed_at.format("%Y-%m-%d")
+            )?;
+        }
+        if closed.is_empty() {
+            writeln!(out, "_None._")?;
+        }
+
+        Ok(out)
+    }
+
+    /// Active issues of any status, most recently updated first, keeping at
+    /// most `limit`: the same order as `mb list --sort updated --reverse`.
+    pub fn recently_updated(&self, limit: Option<usize>) -> Result<Vec<Issue>> {
+        let filter = IssueFilter::new().sort(ListSort::Updated).reversed();
+        self.list_issues(&filter, limit)
+    }
+
+    /// Open-issue count at the end of each of the last `days` days (UTC),
+    /// oldest first, ending today.
+    ///
+    /// Replays every creation and closure, archived issues included, against
+    /// the date axis. A closed issue with no `closed_at` counts as closed at
+    /// its `updated_at`.
+    pub fn burndown(&self, days: u32) -> Result<Vec<(chrono::NaiveDate, usize)>> {
+        if days == 0 {
+            anyhow::bail!("Burndown window must be at least one day");
+        }
+        let today = chrono::Utc::now().date_naive();
+        let start = today
+            .checked_sub_days(chrono::Days::new(u64::from(days) - 1))
+            .ok_or_else(|| {
+                error::invalid_input(format!(
+                    "Burndown window of {} days reaches before the earliest supported date",
+                    days
+                ))
+            })?;
+
+        let _lock = Lock::acquire(&self.beads_dir)?;
+        let mut issues = self.read_active_issues()?;
+        if self.archive_dir().exists() {
+            issues.extend(self.read_issues_in(&self.archive_dir())?);
+        }
+
+        // Net change in open issues per day
+        let mut deltas: std::collections::BTreeMap<chrono::NaiveDate, i64> = Default::default();
+        for issue in &issues {
+            *deltas.entry(issue.created_at.date_naive()).or_default() += 1;
+            if issue.status == Status::Closed {
+                let closed_at = issue.closed_at.unwrap_or(issue.updated_at);
+                *deltas.entry(closed_at.date_naive()).or_default() -= 1;
+            }
+        }
+
+        let mut open: i64 = deltas.range(..start).map(|(_, delta)| delta).sum();
+        let mut series = Vec::with_capacity(days as usize);
+        for day in start.iter_days().take(days as usize) {
+            open += deltas.get(&day).copied().unwrap_or(0);
+            series.push((day, open.max(0) as usize));
+        }
+
+        Ok(series)
+    }
+
+    /// Get ready work: the issues selected by `filter` that are also ready,
+    /// ordered by `sort_policy`
+    ///
+    /// The caller applies any result limit afterwards, so that limiting
+    /// happens after the random shuffle.
+    pub fn get_ready(&self, filter: &IssueFilter, sort_policy: &str) -> Result<Vec<Issue>> {
+        let filter = filter.clone().readiness(Readiness::Ready);
+        let mut ready = self.list_issues(&filter, None)?;
 
         // Apply sorting based on policy
         match sort_policy {
@@ -1731,8 +4146,8 @@ impl Storage {
                 });
             }
             "random" => {
-                // No ordering here; the caller shuffles after post-query
-                // filtering so the randomization spans the whole filtered set.
+                // No ordering here; the caller shuffles the whole filtered
+                // set before applying its limit.
             }
             _ => {
                 // Default to hybrid if invalid (shouldn't happen due to CLI validation)
@@ -1747,114 +4162,455 @@ impl Storage {
         Ok(ready)
     }
 
-    /// Export issues to JSONL format
-    pub fn export_to_jsonl(
+    /// The first `limit` ready issues selected by `filter` under `sort_policy`.
+    ///
+    /// Returns exactly what `get_ready` followed by `truncate(limit)` would,
+    /// but parses issues one at a time and holds at most `limit` of them in a
+    /// bounded max-heap. Apart from those, it keeps only the dependency edges
+    /// of selected issues (needed for `dependents`). The "random" policy has
+    /// no stable order to bound and must go through `get_ready`.
+    pub fn get_ready_limited(
         &self,
-        output_path: &Path,
-        status: Option<Status>,
-        priority: Option<i32>,
-        issue_type: Option<IssueType>,
-        assignee: Option<&str>,
-    ) -> Result<usize> {
-        use std::io::Write;
-
-        // Convert single priority to vector for list_issues
-        let priority_list = priority.map(|p| vec![p]);
-
-        // Get issues with filters (list_issues acquires its own lock)
-        let issues = self.list_issues(status, priority_list, issue_type, assignee, None)?;
-
-        // Open output file
-        let mut file = fs::File::create(output_path)
-            .with_context(|| format!("Failed to create output file: {}", output_path.display()))?;
+        filter: &IssueFilter,
+        sort_policy: &str,
+        limit: usize,
+    ) -> Result<Vec<Issue>> {
+        use crate::types::Dependency;
+        use std::collections::BinaryHeap;
 
-        // Write each issue as a JSON line
-        for issue in &issues {
-            let json =
-                serde_json::to_string(&issue).context("Failed to serialize issue to JSON")?;
-            writeln!(file, "{}", json).context("Failed to write to output file")?;
+        /// Heap entry ordered by its key alone
+        struct Ranked<K: Ord> {
+            key: K,
+            issue: Issue,
+        }
+        impl<K: Ord> PartialEq for Ranked<K> {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+        impl<K: Ord> Eq for Ranked<K> {}
+        impl<K: Ord> PartialOrd for Ranked<K> {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl<K: Ord> Ord for Ranked<K> {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.key.cmp(&other.key)
+            }
         }
 
-        Ok(issues.len())
-    }
-
-    /// Import issues from JSONL format
-    ///
-    /// Returns: (imported_count, skipped_count, errors)
-    #[allow(dead_code)] // Used by sync command (not yet implemented)
-    pub fn import_from_jsonl(
-        &self,
-        input_path: &Path,
-        overwrite: bool,
-    ) -> Result<(usize, usize, Vec<String>)> {
-        use std::io::{BufRead, BufReader};
+        anyhow::ensure!(
+            sort_policy != "random",
+            "get_ready_limited does not support the random sort policy"
+        );
 
         let _lock = Lock::acquire(&self.beads_dir)?;
 
-        // Open input file
-        let file = fs::File::open(input_path)
-            .with_context(|| format!("Failed to open input file: {}", input_path.display()))?;
-        let reader = BufReader::new(file);
+        let mut heap = BinaryHeap::with_capacity(limit + 1);
+        let mut reverse_deps: HashMap<String, Vec<_>> = HashMap::new();
+
+        // Rank one selected ready issue: record its edges for `dependents`
+        // and offer it to the heap
+        let mut consider = |index: usize, issue: Issue| {
+            // Position in `list_issues` order (compare_for_list, then the
+            // directory order a stable sort preserves)
+            let numeric = numeric_id_suffix(&issue.id);
+            let list_key = (
+                numeric.is_none(),
+                numeric,
+                numeric.is_none().then_some(issue.created_at),
+                index,
+            );
 
-        let mut imported = 0;
-        let mut skipped = 0;
-        let mut errors = Vec::new();
+            for (dep_id, dep_type) in &issue.depends_on {
+                reverse_deps.entry(dep_id.clone()).or_default().push((
+                    list_key,
+                    Dependency {
+                        id: issue.id.clone(),
+                        dep_type: dep_type.to_string(),
+                    },
+                ));
+            }
 
-        // Read and parse each line
-        for (line_num, line_result) in reader.lines().enumerate() {
-            let line = match line_result {
-                Ok(l) => l,
-                Err(e) => {
-                    errors.push(format!("Line {}: Failed to read line: {}", line_num + 1, e));
-                    continue;
-                }
+            let key = match sort_policy {
+                "priority" => (Some(issue.priority), None, list_key),
+                "oldest" => (None, Some(issue.created_at), list_key),
+                _ => (Some(issue.priority), Some(issue.created_at), list_key),
             };
-
-            // Skip empty lines
-            if line.trim().is_empty() {
-                continue;
+            heap.push(Ranked { key, issue });
+            if heap.len() > limit {
+                heap.pop();
             }
+        };
 
-            // Parse JSON
-            let issue: Issue = match serde_json::from_str(&line) {
-                Ok(i) => i,
-                Err(e) => {
-                    errors.push(format!(
-                        "Line {}: Failed to parse JSON: {}",
-                        line_num + 1,
-                        e
-                    ));
-                    continue;
-                }
+        // Candidates with `blocks` edges can only be judged once every
+        // blocker's status is known, so only their paths are kept and they
+        // are re-read after the scan
+        let mut blockers = self.new_blocker_statuses()?;
+        let mut deferred = Vec::new();
+
+        let entries = fs::read_dir(&self.issues_dir).context("Failed to read issues directory")?;
+        for (index, entry) in entries.enumerate() {
+            let entry = entry?;
+            let name = entry.file_name();
+            let Some(issue_id) = name.to_string_lossy().strip_suffix(".md").map(String::from)
+            else {
+                continue;
             };
+            let content = fs::read_to_string(entry.path())?;
+            let issue = self.parse_markdown(&issue_id, &content)?;
+            blockers.insert(&issue);
 
-            // Check if markdown file already exists
-            let issue_path = self.issues_dir.join(format!("{}.md", issue.id));
-            if issue_path.exists() && !overwrite {
-                skipped += 1;
+            if issue.status != Status::Open || !filter.matches(&issue) {
                 continue;
             }
 
-            // Convert to markdown and write
-            match issue_to_markdown(&issue) {
-                Ok(markdown) => {
-                    if let Err(e) = fs::write(&issue_path, &markdown) {
-                        errors.push(format!(
-                            "Issue {}: Failed to write markdown file: {}",
-                            issue.id, e
-                        ));
-                        continue;
-                    }
+            if issue.has_blocks_edges() {
+                deferred.push((index, issue_id, entry.path()));
+            } else {
+                consider(index, issue);
+            }
+        }
 
-                    // Set file mtime to match issue's updated_at timestamp (preserve timestamp)
-                    if let Err(e) = set_file_mtime_from_issue(&issue_path, &issue) {
-                        // Non-fatal: log warning but don't fail the import
-                        eprintln!("Warning: Failed to set mtime for {}: {}", issue.id, e);
-                    }
+        for (index, issue_id, path) in deferred {
+            let content = fs::read_to_string(&path)?;
+            let issue = self.parse_markdown(&issue_id, &content)?;
+            if issue.matches_readiness(Readiness::Ready, &blockers) {
+                consider(index, issue);
+            }
+        }
 
-                    imported += 1;
-                }
-                Err(e) => {
+        let mut ready: Vec<Issue> = heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|ranked| ranked.issue)
+            .collect();
+        for issue in &mut ready {
+            let mut dependents = reverse_deps.remove(&issue.id).unwrap_or_default();
+            dependents.sort_by_key(|(key, _)| *key);
+            issue.dependents = dependents.into_iter().map(|(_, dep)| dep).collect();
+        }
+
+        Ok(ready)
+    }
+
+    /// Issues selected for export, sorted by ID
+    ///
+    /// Sorting by ID (rather than the `created_at` order of `list_issues`) keeps
+    /// re-exports byte-identical and matches the order `mb sync` writes, so
+    /// exported files produce clean diffs.
+    pub fn list_issues_for_export(&self, filter: &IssueFilter) -> Result<Vec<Issue>> {
+        // list_issues acquires its own lock
+        let mut issues = self.list_issues(filter, None)?;
+        issues.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(issues)
+    }
+
+    /// Export issues to JSONL format
+    ///
+    /// With `with_header`, the first line is a [`JsonlHeader`] recording the
+    /// schema version. The file is streamed through
+    /// [`Storage::export_jsonl_to_writer`] and renamed into place.
+    pub fn export_to_jsonl(
+        &self,
+        output_path: &Path,
+        filter: &IssueFilter,
+        flavor: JsonlFlavor,
+        with_header: bool,
+        with_dependents: bool,
+    ) -> Result<usize> {
+        write_atomic_with(output_path, |writer| {
+            self.export_jsonl_to_writer(writer, filter, flavor, with_header, with_dependents)
+        })
+        .with_context(|| format!("Failed to write output file: {}", output_path.display()))
+    }
+
+    /// Stream issues as JSONL to `writer` in ID order, one issue at a time.
+    ///
+    /// Only the issue IDs are held in memory, never the full set of issues.
+    /// Dependents are derived from a first pass that keeps just the
+    /// dependency edges; `with_dependents = false` skips that pass and
+    /// exports every issue with an empty `dependents` list. A `readiness`
+    /// filter also needs the first pass, to learn every blocker's status.
+    /// The [`JsonlFlavor::Upstream`] schema has no `dependents`, so
+    /// `with_dependents` only applies to [`JsonlFlavor::Native`].
+    pub fn export_jsonl_to_writer(
+        &self,
+        writer: &mut dyn std::io::Write,
+        filter: &IssueFilter,
+        flavor: JsonlFlavor,
+        with_header: bool,
+        with_dependents: bool,
+    ) -> Result<usize> {
+        use crate::types::Dependency;
+
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        // Each ID is paired with the file it lives in, so archived issues can
+        // be exported alongside active ones
+        let mut ids = Vec::new();
+        for entry in fs::read_dir(&self.issues_dir).context("Failed to read issues directory")? {
+            let name = entry?.file_name();
+            if let Some(id) = name.to_string_lossy().strip_suffix(".md") {
+                ids.push((id.to_string(), self.issues_dir.join(name.clone())));
+            }
+        }
+        if self.include_archived {
+            for id in self.archived_ids()? {
+                let path = self.archived_path(&id);
+                ids.push((id, path));
+            }
+        }
+        ids.sort();
+
+        let read_issue = |id: &str, path: &Path| -> Result<Issue> {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            self.parse_markdown(id, &content)
+        };
+
+        let with_dependents = with_dependents && flavor == JsonlFlavor::Native;
+
+        // Dependents are listed in `list` order (see compare_for_list), so keep
+        // the sort key alongside each edge
+        let mut reverse_deps: HashMap<String, Vec<_>> = HashMap::new();
+        let mut blockers = self.new_blocker_statuses()?;
+        if with_dependents || filter.readiness.is_some() {
+            for (id, path) in &ids {
+                let issue = read_issue(id, path)?;
+                blockers.insert(&issue);
+                if !with_dependents {
+                    continue;
+                }
+                let key = (
+                    numeric_id_suffix(id).is_none(),
+                    numeric_id_suffix(id),
+                    issue.created_at,
+                );
+                for (dep_id, dep_type) in issue.depends_on {
+                    reverse_deps.entry(dep_id).or_default().push((
+                        key,
+                        Dependency {
+                            id: id.clone(),
+                            dep_type: dep_type.to_string(),
+                        },
+                    ));
+                }
+            }
+        }
+
+        if with_header {
+            serde_json::to_writer(&mut *writer, &JsonlHeader::current())
+                .context("Failed to serialize JSONL header")?;
+            writer.write_all(b"\n")?;
+        }
+
+        let mut count = 0;
+        for (id, path) in &ids {
+            let mut issue = read_issue(id, path)?;
+            if !filter.matches_with_blockers(&issue, &blockers) {
+                continue;
+            }
+            let mut dependents = reverse_deps.remove(id).unwrap_or_default();
+            dependents.sort_by_key(|(key, _)| *key);
+            issue.dependents = dependents.into_iter().map(|(_, dep)| dep).collect();
+
+            match flavor {
+                JsonlFlavor::Upstream => {
+                    serde_json::to_writer(&mut *writer, &UpstreamIssue::from(&issue))
+                }
+                JsonlFlavor::Native => serde_json::to_writer(&mut *writer, &issue),
+            }
+            .context("Failed to serialize issue to JSON")?;
+            writer.write_all(b"\n")?;
+            count += 1;
+        }
+        writer.flush()?;
+
+        Ok(count)
+    }
+
+    /// Export issues as a single pretty-printed JSON array
+    ///
+    /// A human-readable companion to [`Storage::export_to_jsonl`]; tooling
+    /// should keep using the compact JSONL form.
+    pub fn export_to_json_array(&self, output_path: &Path, filter: &IssueFilter) -> Result<usize> {
+        let issues = self.list_issues_for_export(filter)?;
+
+        let mut content =
+            serde_json::to_string_pretty(&issues).context("Failed to serialize issues to JSON")?;
+        content.push('\n');
+
+        write_atomic(output_path, content)
+            .with_context(|| format!("Failed to write output file: {}", output_path.display()))?;
+
+        Ok(issues.len())
+    }
+
+    /// Export the dependency graph among the issues `filter` selects as one
+    /// pretty-printed JSON object, `{nodes: [{id, title, status}], edges:
+    /// [{from, to, type}]}`, the adjacency form graph front-ends such as d3
+    /// or cytoscape load directly. Edges follow [`Storage::dependency_graph`]:
+    /// from the issue depended on to its dependent. Returns the node count.
+    pub fn export_graph_json(&self, output_path: &Path, filter: &IssueFilter) -> Result<usize> {
+        let graph = self.dependency_graph(filter)?;
+
+        let mut content = serde_json::to_string_pretty(&graph)
+            .context("Failed to serialize dependency graph to JSON")?;
+        content.push('\n');
+
+        write_atomic(output_path, content)
+            .with_context(|| format!("Failed to write output file: {}", output_path.display()))?;
+
+        Ok(graph.nodes.len())
+    }
+
+    /// Import issues from JSONL format
+    ///
+    /// `mode` decides what happens to issues that already have a markdown
+    /// file (see [`ImportMode`]); kept files count as skipped. A line that is
+    /// not a valid issue, or whose ID is not safe as a file name, is reported
+    /// in the errors and the rest still import. Missing `created_at` or
+    /// `updated_at` default to now, with a warning.
+    ///
+    /// Returns: (imported_count, skipped_count, errors)
+    pub fn import_from_jsonl(
+        &self,
+        input_path: &Path,
+        mode: ImportMode,
+    ) -> Result<(usize, usize, Vec<String>)> {
+        use std::io::{BufRead, BufReader};
+
+        let _lock = Lock::acquire(&self.beads_dir)?;
+
+        // Open input file
+        let file = fs::File::open(input_path)
+            .with_context(|| format!("Failed to open input file: {}", input_path.display()))?;
+        let reader = BufReader::new(file);
+
+        let mut imported = 0;
+        let mut skipped = 0;
+        let mut errors = Vec::new();
+        let now = chrono::Utc::now();
+        let id_pattern = self.id_pattern()?;
+
+        // Read and parse each line
+        for (line_num, line_result) in reader.lines().enumerate() {
+            let line = match line_result {
+                Ok(l) => l,
+                Err(e) => {
+                    errors.push(format!("Line {}: Failed to read line: {}", line_num + 1, e));
+                    continue;
+                }
+            };
+
+            // Skip empty lines
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            // Schema header (written by `export --with-header`): warn on mismatch
+            if let Some(header) = JsonlHeader::from_line(&line) {
+                if let Some(problem) = header.mismatch() {
+                    eprintln!(
+                        "Warning: {}: {}; importing anyway",
+                        input_path.display(),
+                        problem
+                    );
+                }
+                continue;
+            }
+
+            // Parse JSON
+            let mut value: serde_json::Value = match serde_json::from_str(&line) {
+                Ok(v) => v,
+                Err(e) => {
+                    errors.push(format!(
+                        "Line {}: Failed to parse JSON: {}",
+                        line_num + 1,
+                        e
+                    ));
+                    continue;
+                }
+            };
+
+            // Missing timestamps default to now rather than failing the line
+            let mut missing = Vec::new();
+            if let Some(object) = value.as_object_mut() {
+                for field in ["created_at", "updated_at"] {
+                    if object.get(field).is_none_or(serde_json::Value::is_null) {
+                        object.insert(
+                            field.to_string(),
+                            serde_json::Value::String(now.to_rfc3339()),
+                        );
+                        missing.push(field);
+                    }
+                }
+            }
+
+            let issue: Issue = match serde_json::from_value(value) {
+                Ok(i) => i,
+                Err(e) => {
+                    errors.push(format!("Line {}: Invalid issue: {}", line_num + 1, e));
+                    continue;
+                }
+            };
+
+            // IDs become file names, so apply the same rules as `create --id`
+            if let Err(e) = validate_id(&issue.id, id_pattern.as_ref()) {
+                errors.push(format!("Line {}: {}", line_num + 1, e));
+                continue;
+            }
+            if !missing.is_empty() {
+                eprintln!(
+                    "Warning: Line {}: {} has no {}; using the current time",
+                    line_num + 1,
+                    issue.id,
+                    missing.join(" or ")
+                );
+            }
+
+            // Check if markdown file already exists
+            let issue_path = self.issues_dir.join(format!("{}.md", issue.id));
+            if issue_path.exists() {
+                let keep_existing = match mode {
+                    ImportMode::Skip => true,
+                    ImportMode::Overwrite => false,
+                    ImportMode::Merge => match get_file_mtime(&issue_path) {
+                        Ok(mtime) => issue.updated_at <= mtime,
+                        Err(e) => {
+                            errors.push(format!("Issue {}: {}", issue.id, e));
+                            continue;
+                        }
+                    },
+                };
+                if keep_existing {
+                    skipped += 1;
+                    continue;
+                }
+            }
+
+            // Convert to markdown and write
+            match self.to_markdown(&issue) {
+                Ok(markdown) => {
+                    if let Err(e) = write_atomic(&issue_path, &markdown) {
+                        errors.push(format!(
+                            "Issue {}: Failed to write markdown file: {}",
+                            issue.id, e
+                        ));
+                        continue;
+                    }
+
+                    // Set file mtime to match issue's updated_at timestamp (preserve timestamp)
+                    if let Err(e) = set_file_mtime_from_issue(&issue_path, &issue) {
+                        // Non-fatal: log warning but don't fail the import
+                        eprintln!("Warning: Failed to set mtime for {}: {}", issue.id, e);
+                    }
+
+                    imported += 1;
+                }
+                Err(e) => {
                     errors.push(format!(
                         "Issue {}: Failed to convert to markdown: {}",
                         issue.id, e
@@ -1880,7 +4636,7 @@ impl Storage {
         new_prefix: &str,
         dry_run: bool,
         force: bool,
-    ) -> Result<Vec<String>> {
+    ) -> Result<Vec<Change>> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
         // Get current prefix
@@ -1906,12 +4662,9 @@ impl Storage {
         let mut id_mapping = HashMap::new();
         for issue in &all_issues {
             // Check if this issue uses the current prefix
-            if let Some(pos) = issue.id.rfind('-') {
-                let issue_prefix = &issue.id[..pos];
-                let issue_number = &issue.id[pos + 1..];
-
-                if issue_prefix == old_prefix {
-                    let new_id = format!("{}-{}", new_prefix, issue_number);
+            if let Ok(issue_id) = IssueId::parse(&issue.id) {
+                if issue_id.prefix() == old_prefix {
+                    let new_id = format!("{}-{}", new_prefix, issue_id.suffix());
 
                     // Check if new ID would conflict with existing issue
                     if !force {
@@ -1934,30 +4687,33 @@ impl Storage {
         }
 
         // Track all changes for dry-run mode
-        let mut changes = Vec::new();
-        changes.push(format!(
-            "Update config.yaml: issue-prefix: {} -> {}",
-            old_prefix, new_prefix
-        ));
+        let mut changes = vec![Change::UpdateConfig {
+            file: "config.yaml".to_string(),
+            key: "issue-prefix".to_string(),
+            from: old_prefix.clone(),
+            to: new_prefix.to_string(),
+        }];
 
         // Plan all file renames and content updates
         for issue in &all_issues {
             if let Some(new_id) = id_mapping.get(&issue.id) {
-                changes.push(format!("Rename file: {}.md -> {}.md", issue.id, new_id));
-                changes.push(format!(
-                    "Update ID in frontmatter: {} -> {}",
-                    issue.id, new_id
-                ));
+                changes.push(Change::RenameFile {
+                    from: issue.id.clone(),
+                    to: new_id.clone(),
+                });
+                changes.push(Change::UpdateId {
+                    from: issue.id.clone(),
+                    to: new_id.clone(),
+                });
 
                 // Check if this issue has dependencies that will be renamed
                 for dep_id in issue.depends_on.keys() {
-                    if id_mapping.contains_key(dep_id) {
-                        changes.push(format!(
-                            "Update dependency in {}: {} -> {}",
-                            new_id,
-                            dep_id,
-                            id_mapping.get(dep_id).unwrap()
-                        ));
+                    if let Some(new_dep_id) = id_mapping.get(dep_id) {
+                        changes.push(Change::UpdateDependency {
+                            issue: new_id.clone(),
+                            from: dep_id.clone(),
+                            to: new_dep_id.clone(),
+                        });
                     }
                 }
             }
@@ -2016,11 +4772,11 @@ impl Storage {
 
                 // Write to new file (or overwrite if ID didn't change)
                 let new_path = self.issues_dir.join(format!("{}.md", updated_issue.id));
-                let markdown = issue_to_markdown(&updated_issue)?;
-                fs::write(&new_path, markdown).context(format!(
-                    "Failed to write renamed issue: {}",
-                    updated_issue.id
-                ))?;
+                self.write_issue_file(&new_path, &updated_issue)
+                    .context(format!(
+                        "Failed to write renamed issue: {}",
+                        updated_issue.id
+                    ))?;
 
                 // Remove old file if ID changed
                 if updated_issue.id != issue.id {
@@ -2054,7 +4810,7 @@ impl Storage {
         &self,
         dry_run: bool,
         update_config: bool,
-    ) -> Result<(Vec<String>, HashMap<String, String>)> {
+    ) -> Result<(Vec<Change>, HashMap<String, String>)> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
         // Check if already using hash IDs
@@ -2068,19 +4824,33 @@ impl Storage {
         // Load all issues
         let all_issues = self.list_all_issues_no_dependents()?;
 
-        // Build mapping from old numeric ID to new hash ID
+        // Build mapping from old numeric ID to new hash ID, counting the IDs
+        // whose first candidate collided as a gauge of hash-space pressure
         let mut id_mapping = HashMap::new();
+        let mut taken = HashSet::new();
+        let mut retried = 0;
+        let mut max_length = 0;
         for issue in &all_issues {
             // Check if this issue has a numeric ID (prefix-N pattern)
-            if let Some(pos) = issue.id.rfind('-') {
-                let issue_prefix = &issue.id[..pos];
-                let issue_suffix = &issue.id[pos + 1..];
-
+            if let Ok(issue_id) = IssueId::parse(&issue.id) {
                 // Only migrate if it's a numeric ID
-                if issue_prefix == prefix && issue_suffix.parse::<u32>().is_ok() {
-                    // Generate hash-based ID
-                    let hash_id =
-                        self.generate_hash_id(&prefix, &issue.title, &issue.description)?;
+                if issue_id.prefix() == prefix && issue_id.is_numeric() {
+                    // Generate hash-based ID from the issue's own creation
+                    // time, so a dry run predicts the real mapping, avoiding
+                    // the ones already handed out
+                    let generated = self.pick_hash_id(
+                        &prefix,
+                        &issue.title,
+                        &issue.description,
+                        issue.created_at,
+                        &taken,
+                    )?;
+                    if generated.collided() {
+                        retried += 1;
+                    }
+                    max_length = max_length.max(generated.length);
+                    let hash_id = generated.id;
+                    taken.insert(hash_id.clone());
 
                     // Check if new ID would conflict with existing issue
                     let new_path = self.issues_dir.join(format!("{}.md", hash_id));
@@ -2105,32 +4875,45 @@ impl Storage {
         // Track all changes for dry-run mode
         let mut changes = Vec::new();
         if update_config {
-            changes.push("Update config-minibeads.yaml: mb-hash-ids: false -> true".to_string());
+            changes.push(Change::UpdateConfig {
+                file: "config-minibeads.yaml".to_string(),
+                key: "mb-hash-ids".to_string(),
+                from: "false".to_string(),
+                to: "true".to_string(),
+            });
         }
 
         // Plan all file renames and content updates
         for issue in &all_issues {
             if let Some(new_id) = id_mapping.get(&issue.id) {
-                changes.push(format!("Rename file: {}.md -> {}.md", issue.id, new_id));
-                changes.push(format!(
-                    "Update ID in frontmatter: {} -> {}",
-                    issue.id, new_id
-                ));
+                changes.push(Change::RenameFile {
+                    from: issue.id.clone(),
+                    to: new_id.clone(),
+                });
+                changes.push(Change::UpdateId {
+                    from: issue.id.clone(),
+                    to: new_id.clone(),
+                });
 
                 // Check if this issue has dependencies that will be renamed
                 for dep_id in issue.depends_on.keys() {
-                    if id_mapping.contains_key(dep_id) {
-                        changes.push(format!(
-                            "Update dependency in {}: {} -> {}",
-                            new_id,
-                            dep_id,
-                            id_mapping.get(dep_id).unwrap()
-                        ));
+                    if let Some(new_dep_id) = id_mapping.get(dep_id) {
+                        changes.push(Change::UpdateDependency {
+                            issue: new_id.clone(),
+                            from: dep_id.clone(),
+                            to: new_dep_id.clone(),
+                        });
                     }
                 }
             }
         }
 
+        changes.push(Change::HashIdCollisions {
+            migrated: id_mapping.len(),
+            retried,
+            max_length,
+        });
+
         // If dry-run, return changes without applying (return empty mapping for dry-run)
         if dry_run {
             return Ok((changes, HashMap::new()));
@@ -2184,11 +4967,11 @@ impl Storage {
 
                 // Write to new file (or overwrite if ID didn't change)
                 let new_path = self.issues_dir.join(format!("{}.md", updated_issue.id));
-                let markdown = issue_to_markdown(&updated_issue)?;
-                fs::write(&new_path, markdown).context(format!(
-                    "Failed to write renamed issue: {}",
-                    updated_issue.id
-                ))?;
+                self.write_issue_file(&new_path, &updated_issue)
+                    .context(format!(
+                        "Failed to write renamed issue: {}",
+                        updated_issue.id
+                    ))?;
 
                 // Remove old file if ID changed
                 if updated_issue.id != issue.id {
@@ -2222,7 +5005,7 @@ impl Storage {
         &self,
         dry_run: bool,
         update_config: bool,
-    ) -> Result<(Vec<String>, HashMap<String, String>)> {
+    ) -> Result<(Vec<Change>, HashMap<String, String>)> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
         // Get current prefix
@@ -2240,13 +5023,10 @@ impl Storage {
 
         // First pass: collect all numeric IDs with matching prefix
         for issue in &all_issues {
-            if let Some(pos) = issue.id.rfind('-') {
-                let issue_prefix = &issue.id[..pos];
-                let issue_suffix = &issue.id[pos + 1..];
-
-                if issue_prefix == prefix {
+            if let Ok(issue_id) = IssueId::parse(&issue.id) {
+                if issue_id.prefix() == prefix {
                     // Check if it's numeric
-                    if let Ok(num) = issue_suffix.parse::<u32>() {
+                    if let Some(num) = issue_id.number() {
                         numeric_ids.push(num);
                         numeric_id_to_issue.insert(num, issue.clone());
                     } else {
@@ -2255,7 +5035,7 @@ impl Storage {
                     }
                 } else {
                     // Different prefix, check suffix length >= 4
-                    if issue_suffix.len() >= 4 {
+                    if issue_id.suffix().len() >= 4 {
                         hash_issues.push(issue.clone());
                     }
                 }
@@ -2312,27 +5092,34 @@ impl Storage {
         // Track all changes for dry-run mode
         let mut changes = Vec::new();
         if update_config {
-            changes.push("Update config-minibeads.yaml: mb-hash-ids: true -> false".to_string());
+            changes.push(Change::UpdateConfig {
+                file: "config-minibeads.yaml".to_string(),
+                key: "mb-hash-ids".to_string(),
+                from: "true".to_string(),
+                to: "false".to_string(),
+            });
         }
 
         // Plan all file renames and content updates
         for issue in &all_issues {
             if let Some(new_id) = id_mapping.get(&issue.id) {
-                changes.push(format!("Rename file: {}.md -> {}.md", issue.id, new_id));
-                changes.push(format!(
-                    "Update ID in frontmatter: {} -> {}",
-                    issue.id, new_id
-                ));
+                changes.push(Change::RenameFile {
+                    from: issue.id.clone(),
+                    to: new_id.clone(),
+                });
+                changes.push(Change::UpdateId {
+                    from: issue.id.clone(),
+                    to: new_id.clone(),
+                });
 
                 // Check if this issue has dependencies that will be renamed
                 for dep_id in issue.depends_on.keys() {
-                    if id_mapping.contains_key(dep_id) {
-                        changes.push(format!(
-                            "Update dependency in {}: {} -> {}",
-                            new_id,
-                            dep_id,
-                            id_mapping.get(dep_id).unwrap()
-                        ));
+                    if let Some(new_dep_id) = id_mapping.get(dep_id) {
+                        changes.push(Change::UpdateDependency {
+                            issue: new_id.clone(),
+                            from: dep_id.clone(),
+                            to: new_dep_id.clone(),
+                        });
                     }
                 }
             }
@@ -2391,11 +5178,11 @@ impl Storage {
 
                 // Write to new file (or overwrite if ID didn't change)
                 let new_path = self.issues_dir.join(format!("{}.md", updated_issue.id));
-                let markdown = issue_to_markdown(&updated_issue)?;
-                fs::write(&new_path, markdown).context(format!(
-                    "Failed to write renamed issue: {}",
-                    updated_issue.id
-                ))?;
+                self.write_issue_file(&new_path, &updated_issue)
+                    .context(format!(
+                        "Failed to write renamed issue: {}",
+                        updated_issue.id
+                    ))?;
 
                 // Remove old file if ID changed
                 if updated_issue.id != issue.id {
@@ -2436,7 +5223,7 @@ impl Storage {
         &self,
         dry_run: bool,
         closed_issue_start: Option<u32>,
-    ) -> Result<(Vec<String>, HashMap<String, String>)> {
+    ) -> Result<(Vec<Change>, HashMap<String, String>)> {
         let _lock = Lock::acquire(&self.beads_dir)?;
 
         // Get current prefix
@@ -2448,13 +5235,10 @@ impl Storage {
         // Collect all numeric IDs with matching prefix
         let mut numeric_issues = Vec::new();
         for issue in &all_issues {
-            if let Some(pos) = issue.id.rfind('-') {
-                let issue_prefix = &issue.id[..pos];
-                let issue_suffix = &issue.id[pos + 1..];
-
-                if issue_prefix == prefix {
+            if let Ok(issue_id) = IssueId::parse(&issue.id) {
+                if issue_id.prefix() == prefix {
                     // Check if it's numeric
-                    if issue_suffix.parse::<u32>().is_ok() {
+                    if issue_id.is_numeric() {
                         numeric_issues.push(issue.clone());
                     }
                 }
@@ -2530,37 +5314,32 @@ impl Storage {
 
         if id_mapping.is_empty() {
             eprintln!("No gaps found - IDs are already contiguous (1, 2, 3, ...)");
-            return Ok((
-                vec!["No changes needed - IDs already contiguous".to_string()],
-                HashMap::new(),
-            ));
+            return Ok((Vec::new(), HashMap::new()));
         }
 
         // Track all changes for dry-run mode
         let mut changes = Vec::new();
-        changes.push(format!(
-            "Repacking {} numeric IDs to fill gaps",
-            id_mapping.len()
-        ));
 
         // Plan all file renames and content updates
         for issue in &all_issues {
             if let Some(new_id) = id_mapping.get(&issue.id) {
-                changes.push(format!("Rename file: {}.md -> {}.md", issue.id, new_id));
-                changes.push(format!(
-                    "Update ID in frontmatter: {} -> {}",
-                    issue.id, new_id
-                ));
+                changes.push(Change::RenameFile {
+                    from: issue.id.clone(),
+                    to: new_id.clone(),
+                });
+                changes.push(Change::UpdateId {
+                    from: issue.id.clone(),
+                    to: new_id.clone(),
+                });
 
                 // Check if this issue has dependencies that will be renamed
                 for dep_id in issue.depends_on.keys() {
-                    if id_mapping.contains_key(dep_id) {
-                        changes.push(format!(
-                            "Update dependency in {}: {} -> {}",
-                            new_id,
-                            dep_id,
-                            id_mapping.get(dep_id).unwrap()
-                        ));
+                    if let Some(new_dep_id) = id_mapping.get(dep_id) {
+                        changes.push(Change::UpdateDependency {
+                            issue: new_id.clone(),
+                            from: dep_id.clone(),
+                            to: new_dep_id.clone(),
+                        });
                     }
                 }
             }
@@ -2619,11 +5398,11 @@ impl Storage {
 
                 // Write to new file (or overwrite if ID didn't change)
                 let new_path = self.issues_dir.join(format!("{}.md", updated_issue.id));
-                let markdown = issue_to_markdown(&updated_issue)?;
-                fs::write(&new_path, markdown).context(format!(
-                    "Failed to write repacked issue: {}",
-                    updated_issue.id
-                ))?;
+                self.write_issue_file(&new_path, &updated_issue)
+                    .context(format!(
+                        "Failed to write repacked issue: {}",
+                        updated_issue.id
+                    ))?;
 
                 // Remove old file if ID changed
                 if updated_issue.id != issue.id {
@@ -2700,41 +5479,170 @@ fn create_minibeads_config(beads_dir: &Path, mb_hash_ids: bool) -> Result<()> {
     )?;
     writeln!(file, "# Default: base36")?;
     writeln!(file, "hash-encoding: base36")?;
+    writeln!(file)?;
 
-    Ok(())
-}
+    // Starting hash length
+    writeln!(
+        file,
+        "# Hash length new hash-based IDs start at, instead of one chosen from the"
+    )?;
+    writeln!(
+        file,
+        "# database size; collisions still lengthen IDs. 3..=8 for base36, 4..=8 for hex"
+    )?;
+    writeln!(file, "# Default: adaptive")?;
+    writeln!(file, "# mb-hash-min-length: 6")?;
+    writeln!(file)?;
 
-/// Ensure .gitignore exists and contains required entries
-fn ensure_gitignore(beads_dir: &Path) -> Result<()> {
-    use std::io::{BufRead, BufReader, Write};
+    // ID pattern
+    writeln!(
+        file,
+        "# Regex that IDs given with `mb create --id` or imported from JSONL must match"
+    )?;
+    writeln!(
+        file,
+        "# Default: unset (any ID that is safe as a file name)"
+    )?;
+    writeln!(
+        file,
+        "# mb-id-pattern: ^[a-z0-9]+(-[a-z0-9]+(\\.[0-9]+)*)+$"
+    )?;
+    writeln!(file)?;
 
-    let gitignore_path = beads_dir.join(".gitignore");
-    let required_entries = ["minibeads.lock", "command_history.log"];
+    // Create defaults
+    writeln!(
+        file,
+        "# Priority (0-4) and type `mb create` use when --priority / --type are not given"
+    )?;
+    writeln!(file, "# Default: 2 and task")?;
+    writeln!(file, "# mb-default-priority: 1")?;
+    writeln!(file, "# mb-default-type: bug")?;
+    writeln!(file)?;
 
-    // Read existing content if file exists
-    let mut existing_lines = Vec::new();
-    if gitignore_path.exists() {
-        let file = fs::File::open(&gitignore_path).context("Failed to read .gitignore")?;
-        let reader = BufReader::new(file);
-        for line in reader.lines() {
-            existing_lines.push(line?);
-        }
-    }
+    // Markdown section headers
+    writeln!(
+        file,
+        "# Header text for the markdown body sections of issue files"
+    )?;
+    writeln!(
+        file,
+        "# Files using the default English headers are still parsed after a change"
+    )?;
+    writeln!(file, "# mb-sections.description: Description")?;
+    writeln!(file, "# mb-sections.design: Design")?;
+    writeln!(
+        file,
+        "# mb-sections.acceptance_criteria: Acceptance Criteria"
+    )?;
+    writeln!(file, "# mb-sections.notes: Notes")?;
+    writeln!(file)?;
 
-    // Check which entries are missing
-    let mut missing_entries = Vec::new();
-    for entry in &required_entries {
-        if !existing_lines.iter().any(|line| line.trim() == *entry) {
-            missing_entries.push(*entry);
-        }
-    }
+    // Dependency validation
+    writeln!(
+        file,
+        "# Refuse to add dependencies on issues that do not exist yet"
+    )?;
+    writeln!(
+        file,
+        "# When false, a missing target only prints a warning (the issue stays blocked until it is created)"
+    )?;
+    writeln!(file, "# Default: false")?;
+    writeln!(file, "# mb-strict-deps: true")?;
+    writeln!(file)?;
 
-    // Append missing entries if any
-    if !missing_entries.is_empty() {
-        let mut file = fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&gitignore_path)
+    // Default actor
+    writeln!(
+        file,
+        "# Name recorded as the actor (comment author, timestamped notes) when --actor is not given"
+    )?;
+    writeln!(file, "# Set with `mb whoami --set NAME`; default: $USER")?;
+    writeln!(file, "# mb-default-actor: alice")?;
+    writeln!(file)?;
+
+    // Sync timestamp tolerance
+    writeln!(
+        file,
+        "# How far apart (ms) a markdown file's mtime and its JSONL updated_at may be"
+    )?;
+    writeln!(
+        file,
+        "# and still count as unchanged in `mb sync`. Raise it on filesystems with coarse"
+    )?;
+    writeln!(
+        file,
+        "# mtimes (FAT keeps 2s, some network mounts 1s); lower it on local SSDs"
+    )?;
+    writeln!(
+        file,
+        "# Default: 1000 (overridden by `mb sync --tolerance-ms`)"
+    )?;
+    writeln!(file, "# mb-sync-tolerance-ms: 2000")?;
+
+    Ok(())
+}
+
+/// Read config-minibeads.yaml as key/value pairs; a missing file is empty
+fn load_minibeads_config(beads_dir: &Path) -> Result<HashMap<String, String>> {
+    let config_path = beads_dir.join("config-minibeads.yaml");
+
+    if !config_path.exists() {
+        return Ok(HashMap::new());
+    }
+
+    let content =
+        fs::read_to_string(&config_path).context("Failed to read config-minibeads.yaml")?;
+    serde_yaml::from_str(&content).context("Failed to parse config-minibeads.yaml")
+}
+
+/// Read `mb-strict-deps` from config-minibeads.yaml (default: false)
+fn load_strict_deps(beads_dir: &Path) -> Result<bool> {
+    let config = load_minibeads_config(beads_dir)?;
+
+    Ok(config.get("mb-strict-deps").is_some_and(|v| v == "true"))
+}
+
+/// Load the `mb-sections.*` header mapping from config-minibeads.yaml
+pub fn load_section_headers(beads_dir: &Path) -> Result<SectionHeaders> {
+    let config = load_minibeads_config(beads_dir)?;
+
+    SectionHeaders::from_config(&config)
+}
+
+/// Per-checkout files in the database directory: kept out of git by
+/// `ensure_gitignore`, and out of snapshots
+const LOCAL_ONLY_FILES: &[&str] = &["minibeads.lock", "command_history.log", "issues.jsonl.bak"];
+
+/// Ensure .gitignore exists and contains required entries
+fn ensure_gitignore(beads_dir: &Path) -> Result<()> {
+    use std::io::{BufRead, BufReader, Write};
+
+    let gitignore_path = beads_dir.join(".gitignore");
+    let required_entries = LOCAL_ONLY_FILES;
+
+    // Read existing content if file exists
+    let mut existing_lines = Vec::new();
+    if gitignore_path.exists() {
+        let file = fs::File::open(&gitignore_path).context("Failed to read .gitignore")?;
+        let reader = BufReader::new(file);
+        for line in reader.lines() {
+            existing_lines.push(line?);
+        }
+    }
+
+    // Check which entries are missing
+    let mut missing_entries = Vec::new();
+    for entry in required_entries {
+        if !existing_lines.iter().any(|line| line.trim() == *entry) {
+            missing_entries.push(*entry);
+        }
+    }
+
+    // Append missing entries if any
+    if !missing_entries.is_empty() {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&gitignore_path)
             .context("Failed to open .gitignore for writing")?;
 
         // Add a newline before our entries if file already had content
@@ -2751,6 +5659,43 @@ fn ensure_gitignore(beads_dir: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Write `content` to `path` without ever exposing a partially written file.
+///
+/// The data goes to a sibling `<name>.tmp` file, is synced to disk, and is then
+/// renamed over `path`. A rename within one directory is atomic, so readers see
+/// either the old file or the new one. The temp file is removed on failure.
+pub fn write_atomic(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+    use std::io::Write;
+
+    write_atomic_with(path, |writer| Ok(writer.write_all(content.as_ref())?))
+}
+
+/// Like [`write_atomic`], but hands `write` a buffered writer so large
+/// outputs can be streamed into the temporary file instead of built in memory.
+pub fn write_atomic_with<T>(
+    path: &Path,
+    write: impl FnOnce(&mut std::io::BufWriter<fs::File>) -> Result<T>,
+) -> Result<T> {
+    use std::io::Write;
+
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let result = (|| {
+        let mut writer = std::io::BufWriter::new(fs::File::create(&tmp_path)?);
+        let value = write(&mut writer)?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        fs::rename(&tmp_path, path)?;
+        Ok(value)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
 /// Set file mtime to match issue's updated_at timestamp
 /// This preserves timestamps when importing from JSONL
 #[allow(dead_code)] // Used by import and sync (not yet fully wired up)
@@ -2772,8 +5717,7 @@ fn set_file_mtime_from_issue(file_path: &Path, issue: &Issue) -> Result<()> {
 }
 
 /// Get file mtime as DateTime<Utc>
-/// This is used for comparison during sync
-#[allow(dead_code)] // Used by sync command (not yet implemented)
+/// This is used for comparison during sync and `import --mode merge`
 pub fn get_file_mtime(file_path: &Path) -> Result<chrono::DateTime<chrono::Utc>> {
     use chrono::{DateTime, Utc};
     use std::time::SystemTime;
@@ -2940,6 +5884,47 @@ fn upsert_yaml_key_value(file_path: &Path, key: &str, new_value: &str) -> Result
     Ok(())
 }
 
+/// Helpers shared by the test modules here and in other files
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+
+    /// Create an issue with `title` and every other field left at its default
+    pub(crate) fn new_issue(storage: &Storage, title: impl Into<String>) -> Issue {
+        storage
+            .create_issue(IssueSpec::new(title))
+            .expect("create issue")
+    }
+
+    /// A fresh, empty `demo` database under a temporary directory. Keep the
+    /// `TempDir` alive for as long as the storage is used.
+    pub(crate) fn empty_storage() -> (tempfile::TempDir, Storage) {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = Storage::init(tmp.path().join(".beads"), Some("demo".to_string()), false)
+            .expect("init storage");
+        (tmp, storage)
+    }
+
+    /// A fresh `demo` database holding one issue per title, `demo-1` upward
+    pub(crate) fn storage_with_titles(titles: &[&str]) -> (tempfile::TempDir, Storage) {
+        let (tmp, storage) = empty_storage();
+        for title in titles {
+            new_issue(&storage, *title);
+        }
+        (tmp, storage)
+    }
+
+    /// A fresh `demo` database holding `count` issues, `demo-1` ("Issue 1")
+    /// upward
+    pub(crate) fn storage_with_issues(count: usize) -> (tempfile::TempDir, Storage) {
+        let (tmp, storage) = empty_storage();
+        for i in 0..count {
+            new_issue(&storage, format!("Issue {}", i + 1));
+        }
+        (tmp, storage)
+    }
+}
+
 #[cfg(test)]
 mod list_order_tests {
     use super::*;
@@ -2960,6 +5945,14 @@ mod list_order_tests {
         assert_eq!(numeric_id_suffix("foo-bar-7"), Some(7));
     }
 
+    #[test]
+    fn numeric_suffix_without_a_hyphen_is_the_whole_id() {
+        assert_eq!(numeric_id_suffix("42"), Some(42));
+        assert_eq!(numeric_id_suffix("a3f9"), None);
+        assert_eq!(numeric_id_suffix("+3"), None);
+        assert_eq!(numeric_id_suffix("minibeads-+3"), None);
+    }
+
     #[test]
     fn numeric_cluster_first_then_hash() {
         let base = chrono::Utc::now();
@@ -2988,6 +5981,7 @@ mod list_order_tests {
 
 #[cfg(test)]
 mod ready_tests {
+    use super::test_support::{empty_storage, new_issue};
     use super::*;
 
     fn storage_with_open_issues(count: usize) -> (tempfile::TempDir, Storage) {
@@ -2996,29 +5990,88 @@ mod ready_tests {
         let storage =
             Storage::init(beads_dir, Some("demo".to_string()), false).expect("init storage");
         for i in 0..count {
-            storage
-                .create_issue(
-                    format!("Issue {i}"),
-                    String::new(),
-                    None,
-                    None,
-                    2,
-                    IssueType::Task,
-                    None,
-                    Vec::new(),
-                    None,
-                    None,
-                    Vec::new(),
-                )
-                .expect("create issue");
+            new_issue(&storage, format!("Issue {i}"));
         }
         (tmp, storage)
     }
 
+    #[test]
+    fn closing_a_blocker_makes_dependents_ready() {
+        let (_tmp, storage) = storage_with_open_issues(2);
+        storage
+            .add_dependency("demo-1", "demo-2", DependencyType::Blocks, false)
+            .unwrap();
+
+        let ready_ids = |storage: &Storage| -> Vec<String> {
+            let ready = storage.get_ready(&IssueFilter::new(), "hybrid").unwrap();
+            ready.into_iter().map(|i| i.id).collect()
+        };
+        assert_eq!(ready_ids(&storage), vec!["demo-2"]);
+        assert_eq!(storage.get_blocked().unwrap()[0].blocked_by, vec!["demo-2"]);
+
+        storage.close_issue("demo-2", "done").unwrap();
+        assert_eq!(ready_ids(&storage), vec!["demo-1"]);
+        assert!(storage.get_blocked().unwrap().is_empty());
+        let limited = storage
+            .get_ready_limited(&IssueFilter::new(), "hybrid", 5)
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+        assert_eq!(storage.get_stats().unwrap().ready_issues, 1);
+
+        // Reopening the blocker blocks the dependent again
+        storage.reopen_issue("demo-2").unwrap();
+        assert_eq!(ready_ids(&storage), vec!["demo-2"]);
+    }
+
+    #[test]
+    fn stats_count_ready_unassigned_issues() {
+        let (_tmp, storage) = storage_with_open_issues(3);
+        storage
+            .update_issue(
+                "demo-1",
+                HashMap::from([("assignee".to_string(), "alice".to_string())]),
+            )
+            .unwrap();
+        storage
+            .add_dependency("demo-2", "demo-3", DependencyType::Blocks, false)
+            .unwrap();
+
+        // demo-1 is taken and demo-2 is blocked, leaving only demo-3
+        let stats = storage.get_stats().unwrap();
+        assert_eq!(stats.ready_issues, 2);
+        assert_eq!(stats.ready_unassigned_issues, 1);
+    }
+
+    #[test]
+    fn dangling_blocker_blocks_unless_ignored() {
+        let (_tmp, mut storage) = storage_with_open_issues(2);
+        storage
+            .add_dependency("demo-1", "demo-404", DependencyType::Blocks, false)
+            .unwrap();
+
+        let ready_ids = |storage: &Storage| -> Vec<String> {
+            let ready = storage.get_ready(&IssueFilter::new(), "hybrid").unwrap();
+            ready.into_iter().map(|i| i.id).collect()
+        };
+        assert_eq!(ready_ids(&storage), vec!["demo-2"]);
+        assert_eq!(
+            storage.get_blocked().unwrap()[0].blocked_by,
+            vec!["demo-404"]
+        );
+
+        storage.set_ignore_missing_blockers(true);
+        assert_eq!(ready_ids(&storage), vec!["demo-1", "demo-2"]);
+        assert!(storage.get_blocked().unwrap().is_empty());
+        let limited = storage
+            .get_ready_limited(&IssueFilter::new(), "hybrid", 5)
+            .unwrap();
+        assert_eq!(limited.len(), 2);
+    }
+
     #[test]
     fn no_limit_returns_all_ready_issues() {
         let (_tmp, storage) = storage_with_open_issues(15);
-        let ready = storage.get_ready(None, None, None, "hybrid").unwrap();
+        let ready = storage.get_ready(&IssueFilter::new(), "hybrid").unwrap();
         assert_eq!(ready.len(), 15);
     }
 
@@ -3026,14 +6079,90 @@ mod ready_tests {
     fn explicit_limit_truncates() {
         let (_tmp, storage) = storage_with_open_issues(15);
         // Limiting is now the caller's responsibility (applied after filtering).
-        let mut ready = storage.get_ready(None, None, None, "hybrid").unwrap();
+        let mut ready = storage.get_ready(&IssueFilter::new(), "hybrid").unwrap();
         ready.truncate(5);
         assert_eq!(ready.len(), 5);
     }
+
+    #[test]
+    fn bounded_ready_matches_full_sort_then_truncate() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(124);
+        let (_tmp, storage) = empty_storage();
+
+        let shared_times: Vec<chrono::DateTime<chrono::Utc>> = (0..3)
+            .map(|d| chrono::Utc::now() - chrono::Duration::days(d))
+            .collect();
+        let mut ids: Vec<String> = Vec::new();
+        for i in 0..60 {
+            // Mix sequential and hash-style IDs
+            let id = if rng.gen_bool(0.25) {
+                format!("demo-h{:03x}", 0xa00 + i)
+            } else {
+                format!("demo-{}", i + 1)
+            };
+            let mut deps = Vec::new();
+            if !ids.is_empty() && rng.gen_bool(0.4) {
+                let target = ids[rng.gen_range(0..ids.len())].clone();
+                let dep_type = if rng.gen_bool(0.3) {
+                    DependencyType::Blocks
+                } else {
+                    DependencyType::Related
+                };
+                deps.push((target, dep_type));
+            }
+            let labels = if rng.gen_bool(0.5) {
+                vec!["x".to_string()]
+            } else {
+                Vec::new()
+            };
+            let mut issue = storage
+                .create_issue(IssueSpec {
+                    priority: Some(rng.gen_range(0..5)),
+                    labels,
+                    id: Some(id.clone()),
+                    dependencies: deps,
+                    ..IssueSpec::new(format!("Issue {i}"))
+                })
+                .unwrap();
+            if rng.gen_bool(0.15) {
+                issue.status = Status::Closed;
+            }
+            // Force created_at ties so the tie-breakers are exercised
+            if rng.gen_bool(0.5) {
+                issue.created_at = shared_times[rng.gen_range(0..shared_times.len())];
+            }
+            let path = storage.issues_dir.join(format!("{}.md", id));
+            write_atomic(&path, storage.to_markdown(&issue).unwrap()).unwrap();
+            ids.push(id);
+        }
+
+        for policy in ["priority", "oldest", "hybrid"] {
+            for limit in [1, 3, 7, 20, 100] {
+                for filtered in [false, true] {
+                    let filter = if filtered {
+                        IssueFilter::new().label("x")
+                    } else {
+                        IssueFilter::new()
+                    };
+                    let mut expected = storage.get_ready(&filter, policy).unwrap();
+                    expected.truncate(limit);
+                    let actual = storage.get_ready_limited(&filter, policy, limit).unwrap();
+                    assert_eq!(
+                        serde_json::to_value(&actual).unwrap(),
+                        serde_json::to_value(&expected).unwrap(),
+                        "policy={policy} limit={limit} filtered={filtered}"
+                    );
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod config_compat_tests {
+    use super::test_support::empty_storage;
     use super::*;
 
     /// Upstream bd leaves `issue-prefix` commented out in config.yaml (the
@@ -3060,10 +6189,23 @@ mod config_compat_tests {
         let storage = Storage::open(beads_dir).expect("open must tolerate commented prefix");
         assert_eq!(storage.get_prefix().unwrap(), "acme");
     }
+
+    #[test]
+    fn default_actor_is_saved_in_minibeads_config() {
+        let (_tmp, storage) = empty_storage();
+        assert_eq!(storage.default_actor().unwrap(), None);
+
+        storage.set_default_actor("alice").unwrap();
+        storage.set_default_actor("bob").unwrap();
+        assert_eq!(storage.default_actor().unwrap().as_deref(), Some("bob"));
+        // Other settings survive the edit
+        assert!(!storage.use_hash_ids().unwrap());
+    }
 }
 
 #[cfg(test)]
 mod claim_tests {
+    use super::test_support::new_issue;
     use super::*;
     use chrono::{Duration, Utc};
 
@@ -3073,21 +6215,7 @@ mod claim_tests {
         let beads_dir = tmp.path().join(".beads");
         let storage =
             Storage::init(beads_dir, Some("demo".to_string()), false).expect("init storage");
-        let issue = storage
-            .create_issue(
-                "A task".to_string(),
-                String::new(),
-                None,
-                None,
-                2,
-                IssueType::Task,
-                None,
-                Vec::new(),
-                None,
-                None,
-                Vec::new(),
-            )
-            .expect("create issue");
+        let issue = new_issue(&storage, "A task");
         (tmp, storage, issue.id)
     }
 
@@ -3209,6 +6337,7 @@ mod claim_tests {
 
 #[cfg(test)]
 mod github_metadata_tests {
+    use super::test_support::new_issue;
     use super::*;
 
     fn storage() -> (tempfile::TempDir, Storage) {
@@ -3224,17 +6353,9 @@ mod github_metadata_tests {
         let (_tmp, storage) = storage();
         let issue = storage
             .create_issue(
-                "linked".to_string(),
-                String::new(),
-                None,
-                None,
-                2,
-                IssueType::Task,
-                None,
-                vec!["bug".to_string()],
-                Some("https://github.com/owner/repo/issues/123".to_string()),
-                None,
-                Vec::new(),
+                IssueSpec::new("linked")
+                    .label("bug".to_string())
+                    .external_ref("https://github.com/owner/repo/issues/123".to_string()),
             )
             .unwrap();
 
@@ -3244,21 +6365,7 @@ mod github_metadata_tests {
     #[test]
     fn github_external_ref_can_be_detected_without_labels() {
         let (_tmp, storage) = storage();
-        let issue = storage
-            .create_issue(
-                "local".to_string(),
-                String::new(),
-                None,
-                None,
-                2,
-                IssueType::Task,
-                None,
-                Vec::new(),
-                None,
-                None,
-                Vec::new(),
-            )
-            .unwrap();
+        let issue = new_issue(&storage, "local");
 
         storage
             .update_issue(
@@ -3270,7 +6377,7 @@ mod github_metadata_tests {
             )
             .unwrap();
 
-        let issues = storage.list_issues(None, None, None, None, None).unwrap();
+        let issues = storage.list_issues(&IssueFilter::new(), None).unwrap();
         let github_ids: Vec<_> = issues
             .iter()
             .filter(|issue| {
@@ -3287,21 +6394,7 @@ mod github_metadata_tests {
     #[test]
     fn comments_round_trip_in_created_order() {
         let (_tmp, storage) = storage();
-        let issue = storage
-            .create_issue(
-                "commented".to_string(),
-                String::new(),
-                None,
-                None,
-                2,
-                IssueType::Task,
-                None,
-                Vec::new(),
-                None,
-                None,
-                Vec::new(),
-            )
-            .unwrap();
+        let issue = new_issue(&storage, "commented");
 
         let first = storage
             .add_comment(&issue.id, "alice", "first comment")
@@ -3321,21 +6414,7 @@ mod github_metadata_tests {
     #[test]
     fn delete_comment_removes_only_the_targeted_comment() {
         let (_tmp, storage) = storage();
-        let issue = storage
-            .create_issue(
-                "commented".to_string(),
-                String::new(),
-                None,
-                None,
-                2,
-                IssueType::Task,
-                None,
-                Vec::new(),
-                None,
-                None,
-                Vec::new(),
-            )
-            .unwrap();
+        let issue = new_issue(&storage, "commented");
 
         let first = storage.add_comment(&issue.id, "alice", "first").unwrap();
         let second = storage.add_comment(&issue.id, "bob", "second").unwrap();
@@ -3351,21 +6430,7 @@ mod github_metadata_tests {
     #[test]
     fn delete_missing_comment_errors_and_leaves_others() {
         let (_tmp, storage) = storage();
-        let issue = storage
-            .create_issue(
-                "commented".to_string(),
-                String::new(),
-                None,
-                None,
-                2,
-                IssueType::Task,
-                None,
-                Vec::new(),
-                None,
-                None,
-                Vec::new(),
-            )
-            .unwrap();
+        let issue = new_issue(&storage, "commented");
         let only = storage.add_comment(&issue.id, "alice", "keep me").unwrap();
 
         let err = storage
@@ -3391,19 +6456,7 @@ mod search_replace_tests {
         let storage =
             Storage::init(beads_dir, Some("demo".to_string()), false).expect("init storage");
         let issue = storage
-            .create_issue(
-                "A task".to_string(),
-                desc.to_string(),
-                None,
-                None,
-                2,
-                IssueType::Task,
-                None,
-                Vec::new(),
-                None,
-                None,
-                Vec::new(),
-            )
+            .create_issue(IssueSpec::new("A task").description(desc.to_string()))
             .expect("create issue");
         (tmp, storage, issue.id)
     }
@@ -3527,3 +6580,2629 @@ mod search_replace_tests {
         assert_eq!(issue.description, "body");
     }
 }
+
+#[cfg(test)]
+mod jsonl_header_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    fn storage_with_issue() -> (tempfile::TempDir, Storage) {
+        let (tmp, storage) = empty_storage();
+        storage
+            .create_issue(IssueSpec::new("A task").description("body".to_string()))
+            .expect("create issue");
+        (tmp, storage)
+    }
+
+    #[test]
+    fn header_is_written_first_and_skipped_on_import() {
+        let (tmp, storage) = storage_with_issue();
+        let path = tmp.path().join("export.jsonl");
+        let count = storage
+            .export_to_jsonl(&path, &IssueFilter::new(), JsonlFlavor::Native, true, true)
+            .unwrap();
+        assert_eq!(count, 1);
+
+        let content = fs::read_to_string(&path).unwrap();
+        let first = content.lines().next().unwrap();
+        assert_eq!(JsonlHeader::from_line(first), Some(JsonlHeader::current()));
+
+        let other = Storage::init(tmp.path().join("other"), Some("demo".to_string()), false)
+            .expect("init storage");
+        let (imported, skipped, errors) = other.import_from_jsonl(&path, ImportMode::Skip).unwrap();
+        assert_eq!((imported, skipped), (1, 0));
+        assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+    }
+
+    #[test]
+    fn mismatched_header_still_imports() {
+        let (tmp, storage) = storage_with_issue();
+        let path = tmp.path().join("export.jsonl");
+        storage
+            .export_to_jsonl(&path, &IssueFilter::new(), JsonlFlavor::Native, false, true)
+            .unwrap();
+        let body = fs::read_to_string(&path).unwrap();
+        fs::write(
+            &path,
+            format!(
+                "{{\"_schema\":\"minibeads-jsonl\",\"version\":99}}\n{}",
+                body
+            ),
+        )
+        .unwrap();
+
+        let other = Storage::init(tmp.path().join("other"), Some("demo".to_string()), false)
+            .expect("init storage");
+        let (imported, _, errors) = other.import_from_jsonl(&path, ImportMode::Skip).unwrap();
+        assert_eq!(imported, 1);
+        assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+    }
+}
+
+#[cfg(test)]
+mod write_atomic_tests {
+    use super::*;
+
+    #[test]
+    fn replaces_content_and_leaves_no_temp_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let path = tmp.path().join("demo-1.md");
+        fs::write(&path, "old content").unwrap();
+
+        write_atomic(&path, "new content").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new content");
+        let names: Vec<_> = fs::read_dir(tmp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(names, vec![std::ffi::OsString::from("demo-1.md")]);
+    }
+
+    #[test]
+    fn failed_write_cleans_up_temp_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        // Renaming a file over a non-empty directory fails after the temp file
+        // has been written.
+        let path = tmp.path().join("target");
+        fs::create_dir(&path).unwrap();
+        fs::write(path.join("keep"), "x").unwrap();
+
+        assert!(write_atomic(&path, "content").is_err());
+        assert!(!tmp.path().join("target.tmp").exists());
+    }
+}
+
+#[cfg(test)]
+mod export_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    fn create_with_id(storage: &Storage, id: &str) {
+        storage
+            .create_issue(IssueSpec::new(format!("Issue {}", id)).id(id.to_string()))
+            .expect("create issue");
+    }
+
+    #[test]
+    fn export_applies_the_same_filters_as_list() {
+        let (_tmp, storage) = empty_storage();
+        for id in ["demo-a", "demo-b", "demo-c"] {
+            create_with_id(&storage, id);
+        }
+        storage.add_label("demo-a", "ui").unwrap();
+        storage.add_label("demo-b", "ui").unwrap();
+        storage
+            .add_dependency("demo-b", "demo-c", DependencyType::Blocks, false)
+            .unwrap();
+
+        let filter = IssueFilter::new().label("ui").readiness(Readiness::Ready);
+        let listed: Vec<String> = storage
+            .list_issues(&filter, None)
+            .unwrap()
+            .into_iter()
+            .map(|issue| issue.id)
+            .collect();
+        assert_eq!(listed, vec!["demo-a"]);
+
+        for with_dependents in [true, false] {
+            let mut out = Vec::new();
+            storage
+                .export_jsonl_to_writer(
+                    &mut out,
+                    &filter,
+                    JsonlFlavor::Native,
+                    false,
+                    with_dependents,
+                )
+                .unwrap();
+            let exported: Vec<String> = String::from_utf8(out)
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str::<Issue>(line).unwrap().id)
+                .collect();
+            assert_eq!(exported, listed);
+        }
+    }
+
+    #[test]
+    fn export_is_sorted_by_id_not_creation_order() {
+        let (tmp, storage) = empty_storage();
+        for id in ["demo-c", "demo-a", "demo-b"] {
+            create_with_id(&storage, id);
+        }
+
+        let path = tmp.path().join("export.jsonl");
+        storage
+            .export_to_jsonl(&path, &IssueFilter::new(), JsonlFlavor::Native, false, true)
+            .unwrap();
+        let ids: Vec<String> = fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<Issue>(line).unwrap().id)
+            .collect();
+        assert_eq!(ids, vec!["demo-a", "demo-b", "demo-c"]);
+    }
+
+    #[test]
+    fn json_array_export_is_pretty_and_parses_back() {
+        let (tmp, storage) = empty_storage();
+        for id in ["demo-b", "demo-a"] {
+            create_with_id(&storage, id);
+        }
+
+        let path = tmp.path().join("export.json");
+        let count = storage
+            .export_to_json_array(&path, &IssueFilter::new())
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("[\n"), "not pretty-printed: {content}");
+        let issues: Vec<Issue> = serde_json::from_str(&content).unwrap();
+        let ids: Vec<&str> = issues.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["demo-a", "demo-b"]);
+    }
+
+    fn streamed_dependents(storage: &Storage, with_dependents: bool) -> Vec<usize> {
+        let mut out = Vec::new();
+        let count = storage
+            .export_jsonl_to_writer(
+                &mut out,
+                &IssueFilter::new(),
+                JsonlFlavor::Native,
+                false,
+                with_dependents,
+            )
+            .unwrap();
+        assert_eq!(count, 2);
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| {
+                let value: serde_json::Value = serde_json::from_str(line).unwrap();
+                value["dependents"].as_array().unwrap().len()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn streaming_export_fills_dependents_unless_skipped() {
+        let (_tmp, storage) = empty_storage();
+        create_with_id(&storage, "demo-a");
+        storage
+            .create_issue(
+                IssueSpec::new("Depends on a")
+                    .id("demo-b".to_string())
+                    .depends_on("demo-a".to_string(), DependencyType::Blocks),
+            )
+            .unwrap();
+
+        assert_eq!(streamed_dependents(&storage, true), vec![1, 0]);
+        assert_eq!(streamed_dependents(&storage, false), vec![0, 0]);
+    }
+
+    #[test]
+    fn upstream_export_uses_bd_dependency_schema_and_reimports() {
+        let (tmp, storage) = empty_storage();
+        create_with_id(&storage, "demo-a");
+        create_with_id(&storage, "demo-b");
+        storage
+            .add_dependency("demo-b", "demo-a", DependencyType::Related, false)
+            .unwrap();
+
+        let path = tmp.path().join("export.jsonl");
+        storage
+            .export_to_jsonl(
+                &path,
+                &IssueFilter::new(),
+                JsonlFlavor::Upstream,
+                false,
+                true,
+            )
+            .unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines[1]["dependencies"],
+            serde_json::json!([{
+                "issue_id": "demo-b",
+                "depends_on_id": "demo-a",
+                "type": "related",
+                "created_at": lines[1]["created_at"],
+            }])
+        );
+        for line in &lines {
+            let object = line.as_object().unwrap();
+            for key in ["dependents", "assignee", "design", "external_ref"] {
+                assert!(!object.contains_key(key), "unexpected {key}: {line}");
+            }
+        }
+
+        let other = Storage::init(tmp.path().join("other"), Some("demo".to_string()), false)
+            .expect("init storage");
+        let (imported, _, errors) = other.import_from_jsonl(&path, ImportMode::Skip).unwrap();
+        assert_eq!(imported, 2);
+        assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+        let reimported = other.get_issue("demo-b").unwrap().unwrap();
+        assert_eq!(
+            reimported.depends_on.get("demo-a"),
+            Some(&DependencyType::Related)
+        );
+    }
+}
+
+#[cfg(test)]
+mod explicit_id_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    fn create_with_id(storage: &Storage, id: &str) -> Result<Issue> {
+        create_titled(storage, id, "A task", false)
+    }
+
+    fn create_titled(storage: &Storage, id: &str, title: &str, overwrite: bool) -> Result<Issue> {
+        storage.create_issue(IssueSpec {
+            id: Some(id.to_string()),
+            overwrite,
+            ..IssueSpec::new(title)
+        })
+    }
+
+    #[test]
+    fn path_traversal_id_is_rejected() {
+        let (tmp, storage) = empty_storage();
+
+        let err = create_with_id(&storage, "../escape").unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid issue ID"),
+            "unexpected: {err}"
+        );
+        assert!(!tmp.path().join(".beads").join("escape.md").exists());
+    }
+
+    #[test]
+    fn id_with_path_separator_is_rejected() {
+        let (_tmp, storage) = empty_storage();
+
+        let err = create_with_id(&storage, "a/b").unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid issue ID"),
+            "unexpected: {err}"
+        );
+        assert!(!storage.issues_dir.join("a").exists());
+    }
+
+    #[test]
+    fn any_id_the_database_can_generate_is_accepted_by_default() {
+        let tmp = tempfile::tempdir().unwrap();
+        let storage = Storage::init(
+            tmp.path().join(".beads"),
+            Some("My_Proj".to_string()),
+            false,
+        )
+        .expect("init storage");
+
+        for id in ["My_Proj-7", "My_Proj-7.2", "bd-1.2"] {
+            assert_eq!(create_with_id(&storage, id).unwrap().id, id);
+        }
+
+        // The database's own export imports cleanly
+        let path = tmp.path().join("export.jsonl");
+        storage
+            .export_to_jsonl(&path, &IssueFilter::new(), JsonlFlavor::Native, false, true)
+            .unwrap();
+        let other = Storage::init(tmp.path().join("other"), Some("My_Proj".to_string()), false)
+            .expect("init storage");
+        let (imported, _, errors) = other.import_from_jsonl(&path, ImportMode::Skip).unwrap();
+        assert_eq!(imported, 3);
+        assert!(errors.is_empty(), "unexpected errors: {errors:?}");
+
+        // ...and so do moved issues that keep their IDs
+        let target = Storage::init(tmp.path().join("target"), Some("tgt".to_string()), false)
+            .expect("init storage");
+        let subset = storage.export_subset(&["My_Proj-7.2".to_string()]).unwrap();
+        let (mapping, _) = target.import_moved_issues(subset, true, false).unwrap();
+        assert_eq!(mapping[0].1, "My_Proj-7.2");
+    }
+
+    #[test]
+    fn id_not_matching_pattern_is_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        let storage = Storage::init(beads_dir.clone(), Some("demo".to_string()), false)
+            .expect("init storage");
+        let config_path = beads_dir.join("config-minibeads.yaml");
+        let config = fs::read_to_string(&config_path).unwrap();
+        fs::write(
+            &config_path,
+            format!("{config}mb-id-pattern: '^[a-z0-9]+(-[a-z0-9]+)+$'\n"),
+        )
+        .unwrap();
+
+        for id in ["Demo-1", "demo_1", "demo", "demo-1-"] {
+            let err = create_with_id(&storage, id).unwrap_err();
+            assert!(
+                err.to_string().contains("does not match the ID pattern"),
+                "unexpected for {id}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn configured_id_pattern_is_enforced() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        let storage = Storage::init(beads_dir.clone(), Some("demo".to_string()), false)
+            .expect("init storage");
+        let config_path = beads_dir.join("config-minibeads.yaml");
+        let config = fs::read_to_string(&config_path).unwrap();
+
+        fs::write(
+            &config_path,
+            format!("{config}mb-id-pattern: '^[A-Z]+_[0-9]+$'\n"),
+        )
+        .unwrap();
+        assert!(create_with_id(&storage, "DEMO_1").is_ok());
+        assert!(create_with_id(&storage, "demo-2").is_err());
+        // The pattern cannot relax the file-name safety checks
+        fs::write(&config_path, format!("{config}mb-id-pattern: '.*'\n")).unwrap();
+        assert!(create_with_id(&storage, "a/b").is_err());
+
+        // An invalid regex is ignored
+        fs::write(&config_path, format!("{config}mb-id-pattern: '(['\n")).unwrap();
+        assert!(storage.id_pattern().unwrap().is_none());
+        assert!(create_with_id(&storage, "demo_3").is_ok());
+    }
+
+    #[test]
+    fn ordinary_explicit_id_is_accepted() {
+        let (_tmp, storage) = empty_storage();
+
+        let issue = create_with_id(&storage, "demo-42").unwrap();
+        assert_eq!(issue.id, "demo-42");
+    }
+
+    #[test]
+    fn existing_id_is_not_overwritten() {
+        let (_tmp, storage) = empty_storage();
+        create_titled(&storage, "demo-1", "Original", false).unwrap();
+
+        let err = create_titled(&storage, "demo-1", "Clobber", false).unwrap_err();
+        assert!(
+            err.to_string().contains("already exists"),
+            "unexpected: {err}"
+        );
+        let issue = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(issue.title, "Original");
+    }
+
+    #[test]
+    fn overwrite_replaces_existing_id() {
+        let (_tmp, storage) = empty_storage();
+        create_titled(&storage, "demo-1", "Original", false).unwrap();
+
+        create_titled(&storage, "demo-1", "Replacement", true).unwrap();
+        let issue = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(issue.title, "Replacement");
+    }
+}
+
+#[cfg(test)]
+mod section_headers_tests {
+    use super::*;
+
+    #[test]
+    fn configured_headers_are_used_for_write_and_read() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        Storage::init(beads_dir.clone(), Some("demo".to_string()), false).expect("init storage");
+
+        let config_path = beads_dir.join("config-minibeads.yaml");
+        let mut config = fs::read_to_string(&config_path).unwrap();
+        config.push_str("mb-sections.description: Summary\nmb-sections.design: Approach\n");
+        fs::write(&config_path, config).unwrap();
+
+        let storage = Storage::open(beads_dir.clone()).expect("open storage");
+        let issue = storage
+            .create_issue(
+                IssueSpec::new("Sections")
+                    .description("What to do".to_string())
+                    .design("How to do it".to_string()),
+            )
+            .unwrap();
+
+        let content =
+            fs::read_to_string(beads_dir.join(format!("issues/{}.md", issue.id))).unwrap();
+        assert!(content.contains("\n# Summary\n"), "content: {content}");
+        assert!(content.contains("\n# Approach\n"), "content: {content}");
+        assert!(!content.contains("# Description"), "content: {content}");
+
+        let reread = storage.get_issue(&issue.id).unwrap().unwrap();
+        assert_eq!(reread.description, "What to do");
+        assert_eq!(reread.design, "How to do it");
+    }
+}
+
+#[cfg(test)]
+mod strict_deps_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    fn create_with_deps(
+        storage: &Storage,
+        title: &str,
+        deps: Vec<(String, DependencyType)>,
+    ) -> Result<Issue> {
+        storage.create_issue(IssueSpec {
+            dependencies: deps,
+            ..IssueSpec::new(title)
+        })
+    }
+
+    #[test]
+    fn missing_target_only_warns_by_default() {
+        let (_tmp, storage) = empty_storage();
+
+        let issue = create_with_deps(
+            &storage,
+            "Waits on future work",
+            vec![("demo-99".to_string(), DependencyType::Blocks)],
+        )
+        .expect("missing target is allowed");
+        assert!(issue.depends_on.contains_key("demo-99"));
+    }
+
+    #[test]
+    fn config_makes_missing_targets_an_error() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        Storage::init(beads_dir.clone(), Some("demo".to_string()), false).expect("init storage");
+
+        let config_path = beads_dir.join("config-minibeads.yaml");
+        let mut config = fs::read_to_string(&config_path).unwrap();
+        config.push_str("mb-strict-deps: true\n");
+        fs::write(&config_path, config).unwrap();
+
+        let storage = Storage::open(beads_dir.clone()).expect("open storage");
+        let err = create_with_deps(
+            &storage,
+            "Typo'd blocker",
+            vec![("demo-99".to_string(), DependencyType::Blocks)],
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("demo-99"), "error: {err}");
+        assert!(
+            !beads_dir.join("issues/demo-1.md").exists(),
+            "nothing should be written on failure"
+        );
+
+        let blocker = create_with_deps(&storage, "Blocker", Vec::new()).unwrap();
+        let blocked = create_with_deps(
+            &storage,
+            "Blocked",
+            vec![(blocker.id.clone(), DependencyType::Blocks)],
+        )
+        .expect("existing target is accepted");
+
+        assert!(storage
+            .add_dependency(&blocked.id, "demo-99", DependencyType::Related, false)
+            .is_err());
+    }
+
+    #[test]
+    fn flag_overrides_config() {
+        let (_tmp, mut storage) = empty_storage();
+        let issue = create_with_deps(&storage, "Lonely", Vec::new()).unwrap();
+
+        storage.set_strict_deps(true);
+        assert!(storage
+            .add_dependency(&issue.id, "demo-42", DependencyType::Blocks, false)
+            .is_err());
+        assert!(storage
+            .get_issue(&issue.id)
+            .unwrap()
+            .unwrap()
+            .depends_on
+            .is_empty());
+    }
+}
+
+#[cfg(test)]
+mod critical_path_tests {
+    use super::test_support::storage_with_issues;
+    use super::*;
+
+    fn blocks(storage: &Storage, from: &str, to: &str) {
+        storage
+            .add_dependency(from, to, DependencyType::Blocks, false)
+            .unwrap();
+    }
+
+    #[test]
+    fn picks_the_longest_chain_of_blockers() {
+        let (_tmp, storage) = storage_with_issues(6);
+        // demo-1 <- demo-2 <- demo-3 <- demo-4, and a shortcut demo-1 <- demo-5
+        blocks(&storage, "demo-1", "demo-2");
+        blocks(&storage, "demo-2", "demo-3");
+        blocks(&storage, "demo-3", "demo-4");
+        blocks(&storage, "demo-1", "demo-5");
+        // Non-blocking edges are not part of the path
+        storage
+            .add_dependency("demo-4", "demo-6", DependencyType::Related, false)
+            .unwrap();
+
+        assert_eq!(
+            storage.get_critical_path("demo-1").unwrap(),
+            vec!["demo-4", "demo-3", "demo-2", "demo-1"]
+        );
+        assert_eq!(storage.get_critical_path("demo-6").unwrap(), vec!["demo-6"]);
+    }
+
+    #[test]
+    fn closed_and_missing_blockers_are_skipped() {
+        let (_tmp, storage) = storage_with_issues(3);
+        blocks(&storage, "demo-1", "demo-2");
+        blocks(&storage, "demo-2", "demo-3");
+        blocks(&storage, "demo-1", "demo-404");
+        storage.close_issue("demo-3", "done").unwrap();
+
+        assert_eq!(
+            storage.get_critical_path("demo-1").unwrap(),
+            vec!["demo-2", "demo-1"]
+        );
+        assert!(storage.get_critical_path("demo-404").is_err());
+    }
+
+    #[test]
+    fn cycles_terminate() {
+        let (_tmp, storage) = storage_with_issues(3);
+        blocks(&storage, "demo-1", "demo-2");
+        blocks(&storage, "demo-2", "demo-3");
+        blocks(&storage, "demo-3", "demo-1");
+
+        assert_eq!(
+            storage.get_critical_path("demo-1").unwrap(),
+            vec!["demo-3", "demo-2", "demo-1"]
+        );
+    }
+
+    #[test]
+    fn diamond_into_a_cycle_finds_the_longest_chain() {
+        let (_tmp, storage) = storage_with_issues(7);
+        // demo-1 <- demo-2 <- demo-4 and demo-1 <- demo-3 <- demo-5, where
+        // demo-4 and demo-5 block each other and demo-4 also waits on
+        // demo-6 <- demo-7. The cycle is one step with demo-7 <- demo-6 below
+        // it whichever member it is entered at, so both branches are equally
+        // long and the tie goes to demo-2.
+        blocks(&storage, "demo-1", "demo-2");
+        blocks(&storage, "demo-1", "demo-3");
+        blocks(&storage, "demo-2", "demo-4");
+        blocks(&storage, "demo-3", "demo-5");
+        blocks(&storage, "demo-4", "demo-5");
+        blocks(&storage, "demo-5", "demo-4");
+        blocks(&storage, "demo-4", "demo-6");
+        blocks(&storage, "demo-6", "demo-7");
+
+        assert_eq!(
+            storage.get_critical_path("demo-1").unwrap(),
+            vec!["demo-7", "demo-6", "demo-5", "demo-4", "demo-2", "demo-1"]
+        );
+        assert_eq!(
+            storage.get_critical_path("demo-3").unwrap(),
+            vec!["demo-7", "demo-6", "demo-4", "demo-5", "demo-3"]
+        );
+    }
+
+    #[test]
+    fn dense_cyclic_graphs_stay_fast() {
+        // Every issue blocks every other one: the simple paths number 20!,
+        // but the whole graph is one cycle and is listed once
+        let (_tmp, storage) = storage_with_issues(20);
+        for from in 1..=20 {
+            for to in 1..=20 {
+                if from != to {
+                    blocks(&storage, &format!("demo-{from}"), &format!("demo-{to}"));
+                }
+            }
+        }
+
+        let path = storage.get_critical_path("demo-1").unwrap();
+        assert_eq!(path.len(), 20);
+        assert_eq!(path.last().unwrap(), "demo-1");
+        let unique: HashSet<&String> = path.iter().collect();
+        assert_eq!(unique.len(), 20);
+    }
+}
+
+#[cfg(test)]
+mod issue_filter_tests {
+    use super::*;
+
+    fn issue(id: &str) -> Issue {
+        let mut issue = Issue::new(id.to_string(), format!("Issue {id}"), 2, IssueType::Task);
+        issue.labels = vec!["ui".to_string(), "bug".to_string()];
+        issue.assignee = "alice".to_string();
+        issue
+            .depends_on
+            .insert("demo-9".to_string(), DependencyType::ParentChild);
+        issue
+    }
+
+    #[test]
+    fn default_filter_matches_everything() {
+        assert!(IssueFilter::new().matches(&issue("demo-1")));
+    }
+
+    #[test]
+    fn builder_narrows_on_every_field() {
+        let issue = issue("demo-1");
+        let matching = IssueFilter::new()
+            .status(Status::Open)
+            .priorities([1, 2])
+            .issue_types([IssueType::Bug, IssueType::Task])
+            .assignee("alice")
+            .label("ui")
+            .label("bug")
+            .ids(["demo-1", "demo-2"])
+            .title("ISSUE")
+            .parent("demo-9");
+        assert!(matching.matches(&issue));
+
+        for filter in [
+            IssueFilter::new().status(Status::Closed),
+            IssueFilter::new().priorities([0]),
+            IssueFilter::new().issue_type(IssueType::Epic),
+            IssueFilter::new().issue_types([IssueType::Bug, IssueType::Feature]),
+            IssueFilter::new().assignee("bob"),
+            IssueFilter::new().label("ui").label("docs"),
+            IssueFilter::new().github_only(),
+            IssueFilter::new().ids(["demo-2"]),
+            IssueFilter::new().title("other"),
+            IssueFilter::new().parent("demo-8"),
+            IssueFilter::new().unassigned(),
+        ] {
+            assert!(
+                !filter.matches(&issue),
+                "{filter:?} should reject the issue"
+            );
+        }
+    }
+
+    #[test]
+    fn unassigned_matches_only_empty_assignee() {
+        let mut issue = issue("demo-1");
+        issue.assignee.clear();
+        assert!(IssueFilter::new().unassigned().matches(&issue));
+    }
+}
+
+#[cfg(test)]
+mod archive_tests {
+    use super::test_support::{new_issue, storage_with_issues};
+    use super::*;
+
+    fn tomorrow() -> chrono::DateTime<chrono::Utc> {
+        chrono::Utc::now() + chrono::Duration::days(1)
+    }
+
+    fn list_ids(storage: &Storage) -> Vec<String> {
+        let mut ids: Vec<String> = storage
+            .list_issues(&IssueFilter::new(), None)
+            .unwrap()
+            .into_iter()
+            .map(|issue| issue.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn archives_closed_issues_and_keeps_their_edges_resolved() {
+        let (_tmp, mut storage) = storage_with_issues(3);
+        storage
+            .add_dependency("demo-3", "demo-1", DependencyType::Blocks, false)
+            .unwrap();
+        storage.close_issue("demo-1", "done").unwrap();
+        storage.close_issue("demo-2", "done").unwrap();
+
+        let archived = storage.archive_closed_before(tomorrow(), false).unwrap();
+        assert_eq!(archived, vec!["demo-1", "demo-2"]);
+        assert_eq!(list_ids(&storage), vec!["demo-3"]);
+        assert!(storage.get_issue("demo-1").unwrap().is_none());
+
+        // The edge is untouched and the archived blocker counts as closed
+        let dependent = storage.get_issue("demo-3").unwrap().unwrap();
+        assert!(dependent.depends_on.contains_key("demo-1"));
+        let ready: Vec<String> = storage
+            .get_ready(&IssueFilter::new(), "hybrid")
+            .unwrap()
+            .into_iter()
+            .map(|issue| issue.id)
+            .collect();
+        assert_eq!(ready, vec!["demo-3"]);
+
+        // Archived numbers are not reused
+        let next = new_issue(&storage, "Next");
+        assert_eq!(next.id, "demo-4");
+
+        storage.set_include_archived(true);
+        assert_eq!(
+            list_ids(&storage),
+            vec!["demo-1", "demo-2", "demo-3", "demo-4"]
+        );
+    }
+
+    #[test]
+    fn repair_keeps_edges_to_archived_issues() {
+        let (_tmp, storage) = storage_with_issues(2);
+        storage
+            .add_dependency("demo-2", "demo-1", DependencyType::Blocks, false)
+            .unwrap();
+        storage
+            .add_dependency("demo-2", "demo-9", DependencyType::Related, true)
+            .unwrap();
+        storage.close_issue("demo-1", "done").unwrap();
+        storage.archive_closed_before(tomorrow(), false).unwrap();
+
+        let changes = storage.repair_references(false).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                Change::DependsOnArchived {
+                    issue: "demo-2".to_string(),
+                    depends_on: "demo-1".to_string(),
+                },
+                Change::RemoveBrokenReference {
+                    issue: "demo-2".to_string(),
+                    target: "demo-9".to_string(),
+                },
+            ]
+        );
+        assert!(changes[0].is_note());
+        let dependent = storage.get_issue("demo-2").unwrap().unwrap();
+        assert!(dependent.depends_on.contains_key("demo-1"));
+        assert!(!dependent.depends_on.contains_key("demo-9"));
+    }
+
+    #[test]
+    fn restore_moves_issues_back_unless_the_id_is_taken() {
+        let (_tmp, mut storage) = storage_with_issues(2);
+        storage.close_issue("demo-1", "done").unwrap();
+        storage.close_issue("demo-2", "done").unwrap();
+        storage.archive_closed_before(tomorrow(), false).unwrap();
+
+        assert!(storage.get_issue("demo-1").unwrap().is_none());
+        storage.set_include_archived(true);
+        assert_eq!(
+            storage.get_issue("demo-1").unwrap().unwrap().title,
+            "Issue 1"
+        );
+        storage.set_include_archived(false);
+
+        // An active file with the same ID blocks the whole restore
+        fs::copy(
+            storage.archived_path("demo-2"),
+            storage.issues_dir.join("demo-2.md"),
+        )
+        .unwrap();
+        let ids = vec!["demo-1".to_string(), "demo-2".to_string()];
+        let err = storage.restore_issues(&ids).unwrap_err();
+        assert!(err.to_string().contains("demo-2"), "{}", err);
+        assert!(storage.get_issue("demo-1").unwrap().is_none());
+
+        storage.restore_issues(&ids[..1]).unwrap();
+        assert_eq!(list_ids(&storage), vec!["demo-1", "demo-2"]);
+        assert_eq!(storage.archived_ids().unwrap().len(), 1);
+        assert!(storage.restore_issues(&ids[..1]).is_err());
+    }
+
+    #[test]
+    fn dry_run_and_later_closures_leave_issues_in_place() {
+        let (_tmp, storage) = storage_with_issues(2);
+        storage.close_issue("demo-1", "done").unwrap();
+
+        let past = chrono::Utc::now() - chrono::Duration::days(1);
+        assert!(storage
+            .archive_closed_before(past, false)
+            .unwrap()
+            .is_empty());
+
+        let would = storage.archive_closed_before(tomorrow(), true).unwrap();
+        assert_eq!(would, vec!["demo-1"]);
+        assert_eq!(list_ids(&storage), vec!["demo-1", "demo-2"]);
+        assert!(storage.archived_ids().unwrap().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod dep_add_tests {
+    use super::test_support::storage_with_issues;
+    use super::*;
+
+    #[test]
+    fn several_targets_are_reported_one_by_one() {
+        let (_tmp, storage) = storage_with_issues(3);
+        storage
+            .add_dependency("demo-1", "demo-2", DependencyType::Blocks, false)
+            .unwrap();
+
+        let targets = ["demo-2", "demo-1", "demo-3"].map(String::from);
+        let results = storage
+            .add_dependencies("demo-1", &targets, DependencyType::Blocks, false)
+            .unwrap();
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["demo-2", "demo-1", "demo-3"]);
+        assert!(!*results[0].1.as_ref().unwrap(), "already there");
+        let self_dep = results[1].1.as_ref().unwrap_err().to_string();
+        assert!(self_dep.contains("cannot depend on itself"), "{self_dep}");
+        assert!(*results[2].1.as_ref().unwrap());
+
+        let issue = storage.get_issue("demo-1").unwrap().unwrap();
+        let mut deps: Vec<&String> = issue.depends_on.keys().collect();
+        deps.sort();
+        assert_eq!(deps, ["demo-2", "demo-3"]);
+    }
+
+    #[test]
+    fn re_adding_the_same_edge_is_a_no_op() {
+        let (_tmp, storage) = storage_with_issues(2);
+        assert!(storage
+            .add_dependency("demo-1", "demo-2", DependencyType::Blocks, false)
+            .unwrap());
+        let before = storage.get_issue("demo-1").unwrap().unwrap().updated_at;
+
+        assert!(!storage
+            .add_dependency("demo-1", "demo-2", DependencyType::Blocks, false)
+            .unwrap());
+        let issue = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(issue.updated_at, before);
+        assert_eq!(issue.depends_on["demo-2"], DependencyType::Blocks);
+    }
+
+    #[test]
+    fn changing_the_type_requires_force() {
+        let (_tmp, storage) = storage_with_issues(2);
+        storage
+            .add_dependency("demo-1", "demo-2", DependencyType::Blocks, false)
+            .unwrap();
+
+        let err = storage
+            .add_dependency("demo-1", "demo-2", DependencyType::Related, false)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("already has dependency"),
+            "{}",
+            err
+        );
+        let issue = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(issue.depends_on["demo-2"], DependencyType::Blocks);
+
+        assert!(storage
+            .add_dependency("demo-1", "demo-2", DependencyType::Related, true)
+            .unwrap());
+        let issue = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(issue.depends_on["demo-2"], DependencyType::Related);
+    }
+
+    #[test]
+    fn related_pair_adds_both_edges_or_neither() {
+        let (_tmp, storage) = storage_with_issues(3);
+        assert_eq!(
+            storage
+                .add_related_dependency("demo-1", "demo-2", false)
+                .unwrap(),
+            (true, true)
+        );
+        let first = storage.get_issue("demo-1").unwrap().unwrap();
+        let second = storage.get_issue("demo-2").unwrap().unwrap();
+        assert_eq!(first.depends_on["demo-2"], DependencyType::Related);
+        assert_eq!(second.depends_on["demo-1"], DependencyType::Related);
+
+        // Only the missing reverse edge is added
+        storage
+            .add_dependency("demo-1", "demo-3", DependencyType::Related, false)
+            .unwrap();
+        assert_eq!(
+            storage
+                .add_related_dependency("demo-1", "demo-3", false)
+                .unwrap(),
+            (false, true)
+        );
+
+        // A conflicting reverse edge fails before the forward edge is written
+        storage
+            .add_dependency("demo-3", "demo-2", DependencyType::Blocks, false)
+            .unwrap();
+        assert!(storage
+            .add_related_dependency("demo-2", "demo-3", false)
+            .is_err());
+        let second = storage.get_issue("demo-2").unwrap().unwrap();
+        assert!(!second.depends_on.contains_key("demo-3"));
+    }
+
+    #[test]
+    fn dep_type_changes_only_the_edge_type() {
+        let (_tmp, storage) = storage_with_issues(3);
+        storage
+            .add_dependency("demo-1", "demo-2", DependencyType::Blocks, false)
+            .unwrap();
+        storage
+            .add_dependency("demo-1", "demo-3", DependencyType::Blocks, false)
+            .unwrap();
+
+        assert_eq!(
+            storage
+                .set_dependency_type("demo-1", "demo-2", DependencyType::Related)
+                .unwrap(),
+            Some(DependencyType::Blocks)
+        );
+        let issue = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(issue.depends_on["demo-2"], DependencyType::Related);
+        assert_eq!(issue.depends_on["demo-3"], DependencyType::Blocks);
+
+        // Already that type: nothing is written
+        assert_eq!(
+            storage
+                .set_dependency_type("demo-1", "demo-2", DependencyType::Related)
+                .unwrap(),
+            None
+        );
+        let unchanged = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(unchanged.updated_at, issue.updated_at);
+
+        let missing = storage
+            .set_dependency_type("demo-2", "demo-1", DependencyType::Related)
+            .unwrap_err();
+        assert!(missing.to_string().contains("Dependency not found"));
+    }
+}
+
+#[cfg(test)]
+mod move_tests {
+    use super::test_support::new_issue;
+    use super::*;
+
+    fn storage_with_titles(tmp: &tempfile::TempDir, prefix: &str, titles: &[&str]) -> Storage {
+        let storage = Storage::init(tmp.path().join(prefix), Some(prefix.to_string()), false)
+            .expect("init storage");
+        for title in titles {
+            new_issue(&storage, *title);
+        }
+        storage
+    }
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn renumbers_and_rewrites_references_among_moved_issues() {
+        let tmp = tempfile::tempdir().unwrap();
+        let source = storage_with_titles(&tmp, "demo", &["Schema", "Fix demo-1 first", "Stays"]);
+        let target = storage_with_titles(&tmp, "other", &["Existing"]);
+        source
+            .add_dependency("demo-2", "demo-1", DependencyType::Blocks, false)
+            .unwrap();
+        source
+            .add_dependency("demo-3", "demo-1", DependencyType::Related, false)
+            .unwrap();
+        source.add_comment("demo-1", "alice", "see demo-2").unwrap();
+
+        let subset = source.export_subset(&ids(&["demo-1", "demo-2"])).unwrap();
+        let (mapping, changes) = target.import_moved_issues(subset, false, true).unwrap();
+        assert_eq!(
+            mapping,
+            vec![
+                ("demo-1".to_string(), "other-2".to_string()),
+                ("demo-2".to_string(), "other-3".to_string()),
+            ]
+        );
+        assert_eq!(changes[0].to_string(), "Create other-2 (from demo-1)");
+        assert!(target.get_issue("other-2").unwrap().is_none(), "dry run");
+
+        let subset = source.export_subset(&ids(&["demo-1", "demo-2"])).unwrap();
+        target.import_moved_issues(subset, false, false).unwrap();
+        let moved = target.get_issue("other-3").unwrap().unwrap();
+        assert_eq!(moved.title, "Fix other-2 first");
+        assert_eq!(moved.depends_on["other-2"], DependencyType::Blocks);
+        let comments = target.list_comments("other-2").unwrap();
+        assert_eq!(comments[0].body, "see other-3");
+        assert_eq!(comments[0].issue_id, "other-2");
+
+        let removed = source
+            .remove_moved_issues(&ids(&["demo-1", "demo-2"]), false)
+            .unwrap();
+        assert!(removed.contains(&Change::DependsOnMoved {
+            issue: "demo-3".to_string(),
+            depends_on: "demo-1".to_string(),
+        }));
+        assert!(source.get_issue("demo-1").unwrap().is_none());
+        assert!(source.list_comments("demo-1").unwrap().is_empty());
+    }
+
+    #[test]
+    fn keep_ids_refuses_ids_taken_in_the_target() {
+        let tmp = tempfile::tempdir().unwrap();
+        let source = storage_with_titles(&tmp, "demo", &["One", "Two"]);
+        let target = storage_with_titles(&tmp, "other", &[]);
+        target
+            .create_issue(IssueSpec::new("Taken").id("demo-2".to_string()))
+            .unwrap();
+
+        let subset = source.export_subset(&ids(&["demo-1"])).unwrap();
+        let (mapping, _) = target.import_moved_issues(subset, true, false).unwrap();
+        assert_eq!(mapping, vec![("demo-1".to_string(), "demo-1".to_string())]);
+
+        let subset = source.export_subset(&ids(&["demo-2"])).unwrap();
+        let err = target.import_moved_issues(subset, true, false).unwrap_err();
+        assert!(
+            err.to_string().contains("already exists in target: demo-2"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn rewrites_target_references_to_old_ids_it_does_not_own() {
+        let tmp = tempfile::tempdir().unwrap();
+        let source = storage_with_titles(&tmp, "demo", &["Moved", "Also moved"]);
+        let target = storage_with_titles(&tmp, "other", &["Waits on demo-1"]);
+        // demo-2 names no issue of the target, so other-1's edge points at
+        // the source issue
+        target
+            .add_dependency("other-1", "demo-2", DependencyType::Blocks, false)
+            .unwrap();
+        target
+            .create_issue(IssueSpec::new("Own issue").id("demo-1".to_string()))
+            .unwrap();
+
+        let subset = source.export_subset(&ids(&["demo-1", "demo-2"])).unwrap();
+        let (mapping, changes) = target.import_moved_issues(subset, false, false).unwrap();
+        assert_eq!(mapping[1], ("demo-2".to_string(), "other-3".to_string()));
+        assert!(changes.contains(&Change::UpdateDependency {
+            issue: "other-1".to_string(),
+            from: "demo-2".to_string(),
+            to: "other-3".to_string(),
+        }));
+
+        let other = target.get_issue("other-1").unwrap().unwrap();
+        assert_eq!(other.depends_on["other-3"], DependencyType::Blocks);
+        assert!(!other.depends_on.contains_key("demo-2"));
+        // demo-1 is the target's own issue, so mentions of it stay
+        assert_eq!(other.title, "Waits on demo-1");
+    }
+
+    #[test]
+    fn moved_issues_with_the_same_hash_get_distinct_ids() {
+        let tmp = tempfile::tempdir().unwrap();
+        let source = storage_with_titles(&tmp, "demo", &[]);
+        let target = storage_with_titles(&tmp, "other", &[]);
+        let config_path = tmp.path().join("other").join("config-minibeads.yaml");
+        let config = fs::read_to_string(&config_path).unwrap();
+        fs::write(
+            &config_path,
+            format!("{config}mb-hash-ids: true\nmb-hash-min-length: 3\n"),
+        )
+        .unwrap();
+        // Same content and creation time hash to the same first candidate
+        let created_at = "2026-01-01T00:00:00Z".parse().unwrap();
+        for _ in 0..2 {
+            source
+                .create_issue(IssueSpec {
+                    created_at: Some(created_at),
+                    ..IssueSpec::new("Twin")
+                })
+                .unwrap();
+        }
+
+        let subset = source.export_subset(&ids(&["demo-1", "demo-2"])).unwrap();
+        let (mapping, _) = target.import_moved_issues(subset, false, false).unwrap();
+        assert_ne!(mapping[0].1, mapping[1].1);
+        for (_, new_id) in &mapping {
+            assert!(target.get_issue(new_id).unwrap().is_some());
+        }
+    }
+}
+
+#[cfg(test)]
+mod report_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    fn create(storage: &Storage, title: &str, deps: Vec<(String, DependencyType)>) -> Issue {
+        storage
+            .create_issue(IssueSpec {
+                dependencies: deps,
+                ..IssueSpec::new(title)
+            })
+            .expect("create issue")
+    }
+
+    #[test]
+    fn report_has_a_section_per_query() {
+        let (_tmp, storage) = empty_storage();
+        create(&storage, "Base", Vec::new());
+        create(
+            &storage,
+            "Top",
+            vec![("demo-1".to_string(), DependencyType::Blocks)],
+        );
+        create(&storage, "Done", Vec::new());
+        storage.close_issue("demo-3", "done").unwrap();
+
+        let week_ago = chrono::Utc::now() - chrono::Duration::days(7);
+        let report = storage
+            .generate_report(&ReportOptions { since: week_ago })
+            .unwrap();
+        assert!(report.starts_with("# Project report: demo\n"));
+        assert!(report.contains("| **Total** | 3 |"), "{}", report);
+        assert!(report.contains("## Ready work (1)\n\n- **demo-1** Base (P2, task)\n"));
+        assert!(report.contains("- **demo-2** Top (P2, task), blocked by demo-1\n"));
+        assert!(report.contains("- **demo-3** Done (P2, task), closed "));
+
+        // Closures before the window are left out
+        let tomorrow = chrono::Utc::now() + chrono::Duration::days(1);
+        let report = storage
+            .generate_report(&ReportOptions { since: tomorrow })
+            .unwrap();
+        assert!(report.ends_with("(0)\n\n_None._\n"), "{}", report);
+    }
+}
+
+#[cfg(test)]
+mod burndown_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    #[test]
+    fn replays_creations_and_closures_per_day() {
+        let (_tmp, storage) = empty_storage();
+        let now = chrono::Utc::now();
+        let days_ago = |n: i64| now - chrono::Duration::days(n);
+
+        let write = |id: &str, created: i64, closed: Option<i64>| {
+            let mut issue = Issue::new(id.to_string(), id.to_string(), 2, IssueType::Task);
+            issue.created_at = days_ago(created);
+            issue.updated_at = days_ago(created);
+            if let Some(closed) = closed {
+                issue.status = Status::Closed;
+                issue.closed_at = Some(days_ago(closed));
+            }
+            let markdown = storage.to_markdown(&issue).unwrap();
+            write_atomic(&storage.issue_path(id), markdown).unwrap();
+        };
+        write("demo-1", 5, None);
+        write("demo-2", 2, Some(1));
+        write("demo-3", 0, None);
+        write("demo-4", 9, Some(8));
+
+        let series = storage.burndown(3).unwrap();
+        let counts: Vec<usize> = series.iter().map(|(_, open)| *open).collect();
+        assert_eq!(counts, vec![2, 1, 2]);
+        assert_eq!(series[2].0, now.date_naive());
+
+        assert!(storage.burndown(0).is_err());
+        let err = storage.burndown(4_000_000_000).unwrap_err();
+        assert!(err.to_string().contains("earliest supported date"), "{err}");
+    }
+
+    #[test]
+    fn created_at_override_backdates_the_issue_and_its_file() {
+        let (_tmp, storage) = empty_storage();
+        let created_at = chrono::Utc::now() - chrono::Duration::days(2);
+
+        let issue = storage
+            .create_issue(IssueSpec::new("Backfilled").created_at(created_at))
+            .unwrap();
+        assert_eq!(issue.created_at, created_at);
+
+        let stored = storage.get_issue(&issue.id).unwrap().unwrap();
+        assert_eq!(stored.created_at.timestamp(), created_at.timestamp());
+        let mtime: chrono::DateTime<chrono::Utc> = fs::metadata(storage.issue_path(&issue.id))
+            .unwrap()
+            .modified()
+            .unwrap()
+            .into();
+        assert_eq!(mtime.timestamp(), created_at.timestamp());
+
+        let counts: Vec<usize> = storage
+            .burndown(3)
+            .unwrap()
+            .into_iter()
+            .map(|(_, open)| open)
+            .collect();
+        assert_eq!(counts, vec![1, 1, 1]);
+    }
+}
+
+#[cfg(test)]
+mod mtime_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    fn mtime(storage: &Storage, id: &str) -> chrono::DateTime<chrono::Utc> {
+        fs::metadata(storage.issue_path(id))
+            .unwrap()
+            .modified()
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn issue_writes_set_mtime_to_updated_at() {
+        let (_tmp, storage) = empty_storage();
+        let backdated = chrono::Utc::now() - chrono::Duration::days(30);
+
+        let issue = storage
+            .create_issue(IssueSpec::new("Backfilled").created_at(backdated))
+            .unwrap();
+        assert_eq!(
+            mtime(&storage, &issue.id).timestamp_millis(),
+            backdated.timestamp_millis()
+        );
+
+        let updated = storage
+            .update_issue(
+                &issue.id,
+                HashMap::from([("title".to_string(), "Renamed".to_string())]),
+            )
+            .unwrap();
+        assert_eq!(
+            mtime(&storage, &issue.id).timestamp_millis(),
+            updated.updated_at.timestamp_millis()
+        );
+
+        let closed = storage.close_issue(&issue.id, "done").unwrap();
+        assert_eq!(
+            mtime(&storage, &issue.id).timestamp_millis(),
+            closed.updated_at.timestamp_millis()
+        );
+    }
+}
+
+#[cfg(test)]
+mod blocked_since_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    #[test]
+    fn blocked_since_uses_latest_open_blocker_or_updated_at() {
+        let (_tmp, storage) = empty_storage();
+        let now = chrono::Utc::now();
+        let days_ago = |n: i64| now - chrono::Duration::days(n);
+
+        let write = |id: &str, created: i64, status: Status, deps: &[&str]| {
+            let mut issue = Issue::new(id.to_string(), id.to_string(), 2, IssueType::Task);
+            issue.created_at = days_ago(created);
+            issue.updated_at = days_ago(1);
+            issue.status = status;
+            if status == Status::Closed {
+                issue.closed_at = Some(days_ago(1));
+            }
+            for dep in deps {
+                issue
+                    .depends_on
+                    .insert(dep.to_string(), DependencyType::Blocks);
+            }
+            let markdown = storage.to_markdown(&issue).unwrap();
+            write_atomic(&storage.issue_path(id), markdown).unwrap();
+        };
+        write("demo-1", 10, Status::Open, &[]);
+        write("demo-2", 4, Status::Open, &[]);
+        write("demo-3", 2, Status::Closed, &[]);
+        // Blocked by demo-1 and demo-2; closed demo-3 no longer counts
+        write("demo-4", 8, Status::Open, &["demo-1", "demo-2", "demo-3"]);
+        // Created after its blocker, so blocked from its own creation
+        write("demo-5", 6, Status::Open, &["demo-1"]);
+        // Explicitly marked blocked: the last write stands in for the start
+        write("demo-6", 9, Status::Blocked, &["demo-1"]);
+
+        let blocked = storage.get_blocked().unwrap();
+        let since: HashMap<&str, _> = blocked
+            .iter()
+            .map(|item| (item.issue.id.as_str(), item.blocked_since))
+            .collect();
+        assert_eq!(since.len(), 3);
+        assert_eq!(since["demo-4"], Some(days_ago(4)));
+        assert_eq!(since["demo-5"], Some(days_ago(6)));
+        assert_eq!(since["demo-6"], Some(days_ago(1)));
+    }
+}
+
+#[cfg(test)]
+mod update_field_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    #[test]
+    fn empty_value_clears_and_absent_key_leaves_field() {
+        let (_tmp, storage) = empty_storage();
+        let issue = storage
+            .create_issue(
+                IssueSpec::new("A task")
+                    .description("Some description".to_string())
+                    .design("Some design".to_string())
+                    .acceptance_criteria("Done when done".to_string())
+                    .external_ref("gh-1".to_string()),
+            )
+            .expect("create issue");
+
+        storage
+            .update_issue(
+                &issue.id,
+                HashMap::from([
+                    ("description".to_string(), String::new()),
+                    ("external_ref".to_string(), String::new()),
+                ]),
+            )
+            .unwrap();
+
+        // Re-read from disk so the cleared fields survive the markdown round trip
+        let issue = storage.get_issue(&issue.id).unwrap().unwrap();
+        assert_eq!(issue.description, "");
+        assert_eq!(issue.external_ref, None);
+        assert_eq!(issue.design, "Some design");
+        assert_eq!(issue.acceptance_criteria, "Done when done");
+    }
+
+    #[test]
+    fn append_keys_add_lines_to_the_current_value() {
+        let (_tmp, storage) = empty_storage();
+        let issue = storage
+            .create_issue(IssueSpec::new("A task").description("First line\n".to_string()))
+            .expect("create issue");
+
+        for entry in ["started", "tests pass"] {
+            storage
+                .update_issue(
+                    &issue.id,
+                    HashMap::from([
+                        ("append_notes".to_string(), entry.to_string()),
+                        ("append_description".to_string(), entry.to_string()),
+                    ]),
+                )
+                .unwrap();
+        }
+
+        let issue = storage.get_issue(&issue.id).unwrap().unwrap();
+        assert_eq!(issue.notes, "started\ntests pass");
+        assert_eq!(issue.description, "First line\nstarted\ntests pass");
+    }
+}
+
+#[cfg(test)]
+mod roundtrip_check_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    #[test]
+    fn reports_files_that_change_on_rewrite() {
+        let (_tmp, storage) = empty_storage();
+        storage
+            .create_issue(IssueSpec::new("Written by mb").description("Body".to_string()))
+            .expect("create issue");
+        assert!(storage.check_roundtrip().unwrap().is_empty());
+
+        // Hand-written: same data, but `Z` timestamps are re-rendered as `+00:00`
+        let hand_written = "---\ntitle: Hand written\nstatus: open\npriority: 2\nissue_type: task\ncreated_at: 2025-01-02T10:00:00Z\nupdated_at: 2025-01-02T10:00:00Z\n---\n";
+        fs::write(storage.issue_path("demo-10"), hand_written).unwrap();
+        fs::write(storage.issue_path("demo-2"), "not markdown").unwrap();
+
+        assert_eq!(
+            storage.check_roundtrip().unwrap(),
+            vec!["demo-2", "demo-10"]
+        );
+    }
+
+    #[test]
+    fn normalize_rewrites_unstable_files_without_touching_updated_at() {
+        let (_tmp, storage) = empty_storage();
+        let hand_written = "---\ntitle: Hand written\nstatus: open\npriority: 2\nissue_type: task\nlabels:\n- ui\n- api\n- ui\ncreated_at: 2025-01-02T10:00:00Z\nupdated_at: 2025-01-03T10:00:00Z\n---\n";
+        let path = storage.issue_path("demo-1");
+        fs::write(&path, hand_written).unwrap();
+
+        assert_eq!(storage.normalize_all(true).unwrap(), vec!["demo-1"]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), hand_written);
+
+        assert_eq!(storage.normalize_all(false).unwrap(), vec!["demo-1"]);
+        assert!(storage.check_roundtrip().unwrap().is_empty());
+        let issue = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(issue.labels, vec!["api", "ui"]);
+        assert_eq!(issue.updated_at.to_rfc3339(), "2025-01-03T10:00:00+00:00");
+
+        // A file that does not parse blocks normalization entirely
+        fs::write(storage.issue_path("demo-2"), "not markdown").unwrap();
+        assert!(storage.normalize_all(false).is_err());
+    }
+}
+
+#[cfg(test)]
+mod copy_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    #[test]
+    fn copy_keeps_content_and_drops_state() {
+        let (_tmp, storage) = empty_storage();
+        for title in ["Blocker", "Weekly release"] {
+            storage
+                .create_issue(
+                    IssueSpec::new(title)
+                        .description("Steps".to_string())
+                        .design("Design".to_string())
+                        .acceptance_criteria("Shipped".to_string())
+                        .priority(1)
+                        .issue_type(IssueType::Chore)
+                        .assignee("alice".to_string())
+                        .label("release".to_string())
+                        .external_ref("gh-7".to_string()),
+                )
+                .expect("create issue");
+        }
+        storage
+            .add_dependency("demo-2", "demo-1", DependencyType::Blocks, false)
+            .unwrap();
+        storage.close_issue("demo-2", "done").unwrap();
+
+        let copy = storage.copy_issue("demo-2").unwrap();
+        assert_eq!(copy.id, "demo-3");
+        assert_eq!(copy.title, "Copy of Weekly release");
+        assert_eq!(copy.description, "Steps");
+        assert_eq!(copy.design, "Design");
+        assert_eq!(copy.acceptance_criteria, "Shipped");
+        assert_eq!(copy.priority, 1);
+        assert_eq!(copy.issue_type, IssueType::Chore);
+        assert_eq!(copy.labels, vec!["release"]);
+        assert_eq!(copy.status, Status::Open);
+        assert!(copy.assignee.is_empty());
+        assert!(copy.depends_on.is_empty());
+        assert_eq!(copy.external_ref, None);
+        assert_eq!(copy.closed_at, None);
+
+        assert!(storage.copy_issue("demo-404").is_err());
+    }
+}
+
+#[cfg(test)]
+mod preview_tests {
+    use super::test_support::{empty_storage, new_issue};
+    use super::*;
+
+    fn preview(
+        storage: &Storage,
+        title: &str,
+        deps: Vec<(String, DependencyType)>,
+    ) -> Result<Issue> {
+        storage.preview_issue(IssueSpec {
+            dependencies: deps,
+            ..IssueSpec::new(title)
+        })
+    }
+
+    #[test]
+    fn preview_writes_nothing_and_matches_the_next_create() {
+        let (_tmp, mut storage) = empty_storage();
+
+        let issue = preview(&storage, "Planned", Vec::new()).unwrap();
+        assert_eq!(issue.id, "demo-1");
+        assert_eq!(issue.title, "Planned");
+        assert!(!storage.issue_path("demo-1").exists());
+
+        storage.set_strict_deps(true);
+        let missing = vec![("demo-9".to_string(), DependencyType::Blocks)];
+        assert!(preview(&storage, "Blocked", missing).is_err());
+
+        let created = new_issue(&storage, "Planned");
+        assert_eq!(created.id, issue.id);
+    }
+}
+
+#[cfg(test)]
+mod sync_tolerance_tests {
+    use super::*;
+
+    #[test]
+    fn tolerance_is_read_from_config() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        let storage = Storage::init(beads_dir.clone(), Some("demo".to_string()), false)
+            .expect("init storage");
+        assert_eq!(storage.sync_tolerance_ms().unwrap(), None);
+
+        let config_path = beads_dir.join("config-minibeads.yaml");
+        let config = fs::read_to_string(&config_path).unwrap();
+        fs::write(
+            &config_path,
+            format!("{config}mb-sync-tolerance-ms: 2500\n"),
+        )
+        .unwrap();
+        assert_eq!(storage.sync_tolerance_ms().unwrap(), Some(2500));
+
+        fs::write(
+            &config_path,
+            format!("{config}mb-sync-tolerance-ms: soon\n"),
+        )
+        .unwrap();
+        assert_eq!(storage.sync_tolerance_ms().unwrap(), None);
+    }
+}
+
+#[cfg(test)]
+mod change_tests {
+    use super::test_support::storage_with_titles;
+    use super::*;
+
+    #[test]
+    fn rename_changes_render_as_text_and_json() {
+        let (_tmp, storage) = storage_with_titles(&["Schema", "Migrate"]);
+        storage
+            .add_dependency("demo-2", "demo-1", DependencyType::Blocks, false)
+            .unwrap();
+
+        let changes = storage.rename_issue("demo-1", "demo-9", true).unwrap();
+        let text: Vec<String> = changes.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            text,
+            vec![
+                "Rename file: demo-1.md -> demo-9.md",
+                "Update ID in frontmatter: demo-1 -> demo-9",
+                "Update dependency in demo-2: demo-1 -> demo-9",
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(&changes[2]).unwrap(),
+            serde_json::json!({
+                "kind": "update_dependency",
+                "issue": "demo-2",
+                "from": "demo-1",
+                "to": "demo-9",
+            })
+        );
+        assert!(storage.issue_path("demo-1").exists(), "dry run");
+        assert!(storage.repair_references(true).unwrap().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod graph_tests {
+    use super::test_support::storage_with_titles;
+    use super::*;
+
+    #[test]
+    fn graph_keeps_edges_between_selected_issues_in_both_formats() {
+        let (tmp, storage) = storage_with_titles(&["Schema", "Migrate", "Docs"]);
+        storage
+            .add_dependency("demo-2", "demo-1", DependencyType::Blocks, false)
+            .unwrap();
+        storage
+            .add_dependency("demo-3", "demo-2", DependencyType::Related, false)
+            .unwrap();
+        storage.close_issue("demo-1", "done").unwrap();
+
+        let graph = storage.dependency_graph(&IssueFilter::default()).unwrap();
+        assert_eq!(graph.nodes.len(), 3);
+        let dot = graph.to_dot();
+        assert!(dot.contains("\"demo-1\" -> \"demo-2\" [label=\"blocks\"];"));
+        assert!(dot.contains("\"demo-1\" [label=\"demo-1: Schema\", style=filled"));
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.starts_with("flowchart TD\n"));
+        assert!(mermaid.contains("    demo_2 -->|related| demo_3\n"));
+        assert!(mermaid.contains("    class demo_1 closed\n"));
+
+        let open = IssueFilter {
+            status: Some(Status::Open),
+            ..Default::default()
+        };
+        let graph = storage.dependency_graph(&open).unwrap();
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 1, "edge to closed demo-1 is dropped");
+        assert!(!graph.to_mermaid().contains("classDef"));
+
+        let path = tmp.path().join("graph.json");
+        assert_eq!(
+            storage
+                .export_graph_json(&path, &IssueFilter::default())
+                .unwrap(),
+            3
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(json["nodes"][0]["status"], "closed");
+        assert_eq!(
+            json["edges"],
+            serde_json::json!([
+                {"from": "demo-1", "to": "demo-2", "type": "blocks"},
+                {"from": "demo-2", "to": "demo-3", "type": "related"},
+            ])
+        );
+    }
+}
+
+#[cfg(test)]
+mod closure_tests {
+    use super::test_support::{storage_with_issues, storage_with_titles};
+    use super::*;
+
+    fn ids(closure: HashSet<String>) -> Vec<String> {
+        let mut ids: Vec<String> = closure.into_iter().collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn closure_follows_dependencies_through_cycles() {
+        let (_tmp, storage) =
+            storage_with_titles(&["Schema", "Migrate", "Backfill", "Docs", "Unrelated"]);
+        // demo-4 -> demo-3 -> demo-2 -> demo-1, and demo-1 relates back to demo-3
+        for (from, to, dep_type) in [
+            ("demo-2", "demo-1", DependencyType::Blocks),
+            ("demo-3", "demo-2", DependencyType::Blocks),
+            ("demo-4", "demo-3", DependencyType::Blocks),
+            ("demo-1", "demo-3", DependencyType::Related),
+        ] {
+            storage.add_dependency(from, to, dep_type, false).unwrap();
+        }
+
+        assert_eq!(
+            ids(storage
+                .dependency_closure("demo-2", ClosureDirection::Dependencies)
+                .unwrap()),
+            vec!["demo-1", "demo-2", "demo-3"]
+        );
+        assert_eq!(
+            ids(storage
+                .dependency_closure("demo-3", ClosureDirection::Dependents)
+                .unwrap()),
+            vec!["demo-1", "demo-2", "demo-3", "demo-4"]
+        );
+        assert_eq!(
+            ids(storage
+                .dependency_closure("demo-4", ClosureDirection::Both)
+                .unwrap()),
+            vec!["demo-1", "demo-2", "demo-3", "demo-4"]
+        );
+        assert!(storage
+            .dependency_closure("demo-9", ClosureDirection::Both)
+            .is_err());
+    }
+
+    #[test]
+    fn both_directions_leave_out_a_dependents_other_dependencies() {
+        let (_tmp, storage) = storage_with_issues(4);
+        // demo-3 depends on demo-1 and on demo-4; demo-1 depends on demo-2
+        for (from, to) in [
+            ("demo-1", "demo-2"),
+            ("demo-3", "demo-1"),
+            ("demo-3", "demo-4"),
+        ] {
+            storage
+                .add_dependency(from, to, DependencyType::Blocks, false)
+                .unwrap();
+        }
+
+        assert_eq!(
+            ids(storage
+                .dependency_closure("demo-1", ClosureDirection::Both)
+                .unwrap()),
+            vec!["demo-1", "demo-2", "demo-3"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod hash_min_length_tests {
+    use super::test_support::new_issue;
+    use super::*;
+
+    #[test]
+    fn configured_length_sets_where_hash_ids_start() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        let storage =
+            Storage::init(beads_dir.clone(), Some("demo".to_string()), true).expect("init storage");
+        assert_eq!(storage.hash_min_length().unwrap(), None);
+
+        let config_path = beads_dir.join("config-minibeads.yaml");
+        let config = fs::read_to_string(&config_path).unwrap();
+        fs::write(&config_path, format!("{config}mb-hash-min-length: 6\n")).unwrap();
+        assert_eq!(storage.hash_min_length().unwrap(), Some(6));
+        let issue = new_issue(&storage, "Long");
+        assert_eq!(IssueId::parse(&issue.id).unwrap().suffix().len(), 6);
+
+        // Out of range for the encoding: warn and fall back to adaptive
+        fs::write(&config_path, format!("{config}mb-hash-min-length: 9\n")).unwrap();
+        assert_eq!(storage.hash_min_length().unwrap(), None);
+        let hex = config.replace("hash-encoding: base36", "hash-encoding: hex");
+        fs::write(&config_path, format!("{hex}mb-hash-min-length: 3\n")).unwrap();
+        assert_eq!(storage.hash_min_length().unwrap(), None);
+    }
+}
+
+#[cfg(test)]
+mod hash_migration_tests {
+    use super::*;
+
+    #[test]
+    fn migration_reports_collision_statistics() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        let storage = Storage::init(beads_dir.clone(), Some("demo".to_string()), false)
+            .expect("init storage");
+        let config_path = beads_dir.join("config-minibeads.yaml");
+        let config = fs::read_to_string(&config_path).unwrap();
+        fs::write(&config_path, format!("{config}mb-hash-min-length: 3\n")).unwrap();
+        // A fixed creation time makes the hashes deterministic; the two
+        // twins hash to the same first candidate, so exactly one is retried
+        let created_at = "2026-01-01T00:00:00Z".parse().unwrap();
+        for title in ["Issue 1", "Issue 2", "Twin", "Twin"] {
+            storage
+                .create_issue(IssueSpec {
+                    created_at: Some(created_at),
+                    ..IssueSpec::new(title)
+                })
+                .unwrap();
+        }
+
+        let (changes, mapping) = storage.migrate_to_hash_ids(false, true).unwrap();
+        let Some(Change::HashIdCollisions {
+            migrated,
+            retried,
+            max_length,
+        }) = changes.last()
+        else {
+            panic!("expected collision statistics, got {:?}", changes.last());
+        };
+        assert_eq!(*migrated, 4);
+        assert_eq!(*retried, 1);
+        assert_eq!(*max_length, 3);
+        assert!(changes.last().unwrap().is_note());
+
+        // IDs handed out within one migration never collide with each other
+        let new_ids: HashSet<&String> = mapping.values().collect();
+        assert_eq!(new_ids.len(), 4);
+        assert_eq!(fs::read_dir(beads_dir.join("issues")).unwrap().count(), 4);
+    }
+}
+
+#[cfg(test)]
+mod dependency_tree_tests {
+    use super::test_support::{storage_with_issues, storage_with_titles};
+    use super::*;
+
+    #[test]
+    fn cycles_are_cut_even_when_showing_all_paths() {
+        let (_tmp, storage) = storage_with_titles(&["A", "B"]);
+        storage
+            .add_dependency("demo-1", "demo-2", DependencyType::Blocks, false)
+            .unwrap();
+        storage
+            .add_dependency("demo-2", "demo-1", DependencyType::Blocks, false)
+            .unwrap();
+
+        for show_all_paths in [false, true] {
+            let tree = storage
+                .get_dependency_tree("demo-1", 10, show_all_paths, None)
+                .unwrap();
+            assert_eq!(tree.children.len(), 1);
+            let b = &tree.children[0];
+            assert_eq!(b.id, "demo-2");
+            assert!(!b.is_cycle);
+            assert_eq!(b.children.len(), 1);
+            let back_to_a = &b.children[0];
+            assert_eq!(back_to_a.id, "demo-1");
+            assert!(back_to_a.is_cycle, "show_all_paths={show_all_paths}");
+            assert!(back_to_a.children.is_empty());
+        }
+    }
+
+    #[test]
+    fn max_children_caps_each_node_and_counts_the_rest() {
+        let (_tmp, storage) = storage_with_issues(5);
+        for dep in ["demo-5", "demo-3", "demo-2", "demo-4"] {
+            storage
+                .add_dependency("demo-1", dep, DependencyType::Blocks, false)
+                .unwrap();
+        }
+
+        let tree = storage
+            .get_dependency_tree("demo-1", 10, false, Some(2))
+            .unwrap();
+        let ids: Vec<&str> = tree.children.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["demo-2", "demo-3"]);
+        assert_eq!(tree.omitted_children, 2);
+
+        let full = storage
+            .get_dependency_tree("demo-1", 10, false, None)
+            .unwrap();
+        assert_eq!(full.children.len(), 4);
+        assert_eq!(full.omitted_children, 0);
+    }
+}
+
+#[cfg(test)]
+mod duplicate_title_tests {
+    use super::test_support::storage_with_titles;
+
+    #[test]
+    fn titles_are_grouped_after_trimming_and_lowercasing() {
+        let (_tmp, storage) = storage_with_titles(&[
+            "Fix login",
+            "Write docs",
+            "  fix LOGIN ",
+            "Other",
+            "write docs",
+        ]);
+
+        assert_eq!(
+            storage.find_duplicate_titles().unwrap(),
+            vec![vec!["demo-1", "demo-3"], vec!["demo-2", "demo-5"]]
+        );
+    }
+}
+
+#[cfg(test)]
+mod similar_title_tests {
+    use super::test_support::storage_with_titles;
+    use super::*;
+
+    #[test]
+    fn similarity_is_jaccard_over_lowercased_words() {
+        assert_eq!(title_similarity("Fix login bug", "fix LOGIN bug!"), 1.0);
+        assert_eq!(title_similarity("Fix login bug", "Fix signup bug"), 0.5);
+        assert_eq!(title_similarity("Fix login", "Write docs"), 0.0);
+        assert_eq!(title_similarity("", "..."), 0.0);
+    }
+
+    #[test]
+    fn only_open_issues_above_threshold_are_reported() {
+        let (_tmp, storage) = storage_with_titles(&[
+            "Fix the login bug",
+            "Fix login bug",
+            "Write docs",
+            "Fix login bug on mobile",
+        ]);
+        storage.close_issue("demo-2", "done").unwrap();
+
+        let similar: Vec<(String, f64)> = storage
+            .find_similar_titles("fix login bug", 0.6)
+            .unwrap()
+            .into_iter()
+            .map(|(issue, score)| (issue.id, score))
+            .collect();
+        assert_eq!(
+            similar,
+            vec![("demo-1".to_string(), 0.75), ("demo-4".to_string(), 0.6)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod recently_updated_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    #[test]
+    fn newest_update_comes_first_and_limit_applies() {
+        let (_tmp, storage) = empty_storage();
+        for (title, created) in [
+            ("A", "2024-01-01T00:00:00Z"),
+            ("B", "2024-01-02T00:00:00Z"),
+            ("C", "2024-01-03T00:00:00Z"),
+        ] {
+            storage
+                .create_issue(IssueSpec::new(title).created_at(created.parse().unwrap()))
+                .unwrap();
+        }
+        storage.close_issue("demo-1", "done").unwrap();
+
+        let ids = |issues: Vec<Issue>| issues.into_iter().map(|i| i.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(storage.recently_updated(None).unwrap()),
+            ["demo-1", "demo-3", "demo-2"]
+        );
+        assert_eq!(
+            ids(storage.recently_updated(Some(2)).unwrap()),
+            ["demo-1", "demo-3"]
+        );
+        // `mb list --sort updated` without --reverse
+        let by_updated = IssueFilter::new().sort(ListSort::Updated);
+        assert_eq!(
+            ids(storage.list_issues(&by_updated, None).unwrap()),
+            ["demo-2", "demo-3", "demo-1"]
+        );
+    }
+}
+
+#[cfg(test)]
+mod create_defaults_tests {
+    use super::*;
+
+    #[test]
+    fn configured_defaults_are_validated() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        let storage = Storage::init(beads_dir.clone(), Some("demo".to_string()), false)
+            .expect("init storage");
+        assert_eq!(storage.default_priority().unwrap(), None);
+        assert_eq!(storage.default_issue_type().unwrap(), None);
+
+        let config_path = beads_dir.join("config-minibeads.yaml");
+        let config = fs::read_to_string(&config_path).unwrap();
+        fs::write(
+            &config_path,
+            format!("{config}mb-default-priority: 1\nmb-default-type: bug\n"),
+        )
+        .unwrap();
+        assert_eq!(storage.default_priority().unwrap(), Some(1));
+        assert_eq!(storage.default_issue_type().unwrap(), Some(IssueType::Bug));
+        assert_eq!(
+            storage.create_defaults(None, None).unwrap(),
+            (1, IssueType::Bug)
+        );
+        assert_eq!(
+            storage
+                .create_defaults(Some(0), Some(IssueType::Chore))
+                .unwrap(),
+            (0, IssueType::Chore)
+        );
+
+        // Invalid values warn and fall back
+        fs::write(
+            &config_path,
+            format!("{config}mb-default-priority: 7\nmb-default-type: story\n"),
+        )
+        .unwrap();
+        assert_eq!(storage.default_priority().unwrap(), None);
+        assert_eq!(storage.default_issue_type().unwrap(), None);
+        assert_eq!(
+            storage.create_defaults(None, None).unwrap(),
+            (2, IssueType::Task)
+        );
+    }
+}
+
+#[cfg(test)]
+mod readiness_report_tests {
+    use super::test_support::storage_with_titles;
+    use super::*;
+
+    #[test]
+    fn report_names_unresolved_blockers_and_their_status() {
+        let (_tmp, storage) = storage_with_titles(&["A", "B", "C", "D"]);
+        for blocker in ["demo-2", "demo-3"] {
+            storage
+                .add_dependency("demo-1", blocker, DependencyType::Blocks, false)
+                .unwrap();
+        }
+        storage
+            .update_issue(
+                "demo-2",
+                HashMap::from([("status".to_string(), "in_progress".to_string())]),
+            )
+            .unwrap();
+        storage.close_issue("demo-3", "done").unwrap();
+
+        assert_eq!(
+            storage.readiness("demo-1").unwrap(),
+            ReadinessReport::Blocked {
+                by: vec![Blocker {
+                    id: "demo-2".to_string(),
+                    status: Some(Status::InProgress),
+                }],
+            }
+        );
+        assert_eq!(
+            storage.readiness("demo-2").unwrap(),
+            ReadinessReport::NotOpen {
+                status: Status::InProgress
+            }
+        );
+        assert_eq!(
+            storage.readiness("demo-3").unwrap(),
+            ReadinessReport::Closed
+        );
+        assert_eq!(storage.readiness("demo-4").unwrap(), ReadinessReport::Ready);
+        assert!(storage.readiness("demo-9").is_err());
+    }
+}
+
+#[cfg(test)]
+mod issue_reference_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    #[test]
+    fn mentions_resolve_in_order_and_unknown_ids_are_ignored() {
+        let (_tmp, storage) = empty_storage();
+        for (title, description) in [
+            (
+                "Main",
+                "Follows demo-3, see also demo-2 and demo-3; not demo-9 or demo-20",
+            ),
+            ("Second", ""),
+            ("Third", ""),
+        ] {
+            storage
+                .create_issue(IssueSpec::new(title).description(description.to_string()))
+                .unwrap();
+        }
+        storage.close_issue("demo-3", "done").unwrap();
+
+        let main = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(
+            storage.issue_references(&main).unwrap(),
+            vec![
+                IssueReference {
+                    id: "demo-3".to_string(),
+                    title: "Third".to_string(),
+                    status: Status::Closed,
+                },
+                IssueReference {
+                    id: "demo-2".to_string(),
+                    title: "Second".to_string(),
+                    status: Status::Open,
+                },
+            ]
+        );
+    }
+}
+
+#[cfg(test)]
+mod gc_tests {
+    use super::test_support::new_issue;
+    use super::*;
+
+    #[test]
+    fn removes_empty_unparseable_and_stale_temp_files_only() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        let storage = Storage::init(beads_dir.clone(), Some("demo".to_string()), false)
+            .expect("init storage");
+        new_issue(&storage, "Keep me");
+        let issues_dir = beads_dir.join("issues");
+        fs::write(issues_dir.join("demo-2.md"), "").unwrap();
+        fs::write(issues_dir.join("demo-3.md"), "no frontmatter here\n").unwrap();
+        let stale = issues_dir.join("demo-4.md.tmp");
+        fs::write(&stale, "partial").unwrap();
+        let an_hour_ago = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+        filetime::set_file_mtime(&stale, filetime::FileTime::from_system_time(an_hour_ago))
+            .unwrap();
+        let fresh = beads_dir.join("issues.jsonl.tmp");
+        fs::write(&fresh, "in progress").unwrap();
+
+        let dry_run = storage.gc(true).unwrap();
+        assert_eq!(dry_run.len(), 3);
+        assert_eq!(
+            dry_run[0],
+            Change::RemoveEmptyFile {
+                file: "issues/demo-2.md".to_string()
+            }
+        );
+        assert!(matches!(
+            &dry_run[1],
+            Change::RemoveUnparseableFile { file, .. } if file == "issues/demo-3.md"
+        ));
+        assert_eq!(
+            dry_run[2],
+            Change::RemoveTempFile {
+                file: "issues/demo-4.md.tmp".to_string()
+            }
+        );
+        assert!(issues_dir.join("demo-2.md").exists());
+
+        assert_eq!(storage.gc(false).unwrap(), dry_run);
+        assert!(!issues_dir.join("demo-2.md").exists());
+        assert!(!issues_dir.join("demo-3.md").exists());
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+        assert!(issues_dir.join("demo-1.md").exists());
+        assert!(storage.gc(false).unwrap().is_empty());
+    }
+}
+
+#[cfg(test)]
+mod import_mode_tests {
+    use super::*;
+
+    fn storage_with(dir: &Path, title: &str, created_at: &str) -> Storage {
+        let storage =
+            Storage::init(dir.to_path_buf(), Some("demo".to_string()), false).expect("init");
+        storage
+            .create_issue(IssueSpec::new(title).created_at(created_at.parse().unwrap()))
+            .unwrap();
+        storage
+    }
+
+    fn title(storage: &Storage) -> String {
+        storage.get_issue("demo-1").unwrap().unwrap().title
+    }
+
+    #[test]
+    fn modes_decide_what_happens_to_existing_issues() {
+        let tmp = tempfile::tempdir().unwrap();
+        let source = storage_with(
+            &tmp.path().join("source"),
+            "Imported",
+            "2024-01-02T00:00:00Z",
+        );
+        let path = tmp.path().join("export.jsonl");
+        source
+            .export_to_jsonl(
+                &path,
+                &IssueFilter::new(),
+                JsonlFlavor::Upstream,
+                false,
+                true,
+            )
+            .unwrap();
+
+        let older = storage_with(&tmp.path().join("older"), "Local", "2024-01-01T00:00:00Z");
+        assert_eq!(
+            older.import_from_jsonl(&path, ImportMode::Skip).unwrap().1,
+            1
+        );
+        assert_eq!(title(&older), "Local");
+        assert_eq!(
+            older.import_from_jsonl(&path, ImportMode::Merge).unwrap().0,
+            1
+        );
+        assert_eq!(title(&older), "Imported");
+
+        let newer = storage_with(&tmp.path().join("newer"), "Local", "2024-01-03T00:00:00Z");
+        assert_eq!(
+            newer.import_from_jsonl(&path, ImportMode::Merge).unwrap().1,
+            1
+        );
+        assert_eq!(title(&newer), "Local");
+        assert_eq!(
+            newer
+                .import_from_jsonl(&path, ImportMode::Overwrite)
+                .unwrap()
+                .0,
+            1
+        );
+        assert_eq!(title(&newer), "Imported");
+    }
+}
+
+#[cfg(test)]
+mod import_validation_tests {
+    use super::test_support::empty_storage;
+    use super::*;
+
+    const VALID: &str = r#"{"id":"demo-1","title":"Ok","status":"open","priority":2,"issue_type":"task","created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-01T00:00:00Z"}"#;
+
+    fn import(lines: &[&str]) -> (Storage, tempfile::TempDir, (usize, usize, Vec<String>)) {
+        let (tmp, storage) = empty_storage();
+        let path = tmp.path().join("import.jsonl");
+        fs::write(&path, lines.join("\n")).unwrap();
+        let result = storage.import_from_jsonl(&path, ImportMode::Skip).unwrap();
+        (storage, tmp, result)
+    }
+
+    #[test]
+    fn empty_id_is_rejected_without_writing_a_file() {
+        let line = VALID.replace(r#""id":"demo-1""#, r#""id":"""#);
+        let (storage, _tmp, (imported, _, errors)) = import(&[&line, VALID]);
+        assert_eq!(imported, 1);
+        assert_eq!(errors.len(), 1);
+        assert!(
+            errors[0].starts_with("Line 1: Invalid issue ID"),
+            "{errors:?}"
+        );
+        assert!(!storage.issues_dir.join(".md").exists());
+    }
+
+    #[test]
+    fn unsafe_id_is_rejected() {
+        let line = VALID.replace("demo-1", "../escape");
+        let (_storage, tmp, (imported, _, errors)) = import(&[&line]);
+        assert_eq!(imported, 0);
+        assert!(errors[0].contains("'../escape'"), "{errors:?}");
+        assert!(!tmp.path().join(".beads/escape.md").exists());
+    }
+
+    #[test]
+    fn id_not_matching_pattern_is_rejected() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        let storage = Storage::init(beads_dir.clone(), Some("demo".to_string()), false)
+            .expect("init storage");
+        let config_path = beads_dir.join("config-minibeads.yaml");
+        let config = fs::read_to_string(&config_path).unwrap();
+        fs::write(
+            &config_path,
+            format!("{config}mb-id-pattern: '^[a-z0-9]+(-[a-z0-9]+)+$'\n"),
+        )
+        .unwrap();
+        let line = VALID.replace("demo-1", "Demo_1");
+        let path = tmp.path().join("import.jsonl");
+        fs::write(&path, [line.as_str(), VALID].join("\n")).unwrap();
+
+        let (imported, _, errors) = storage.import_from_jsonl(&path, ImportMode::Skip).unwrap();
+        assert_eq!(imported, 1);
+        assert!(
+            errors[0].starts_with("Line 1: Invalid issue ID 'Demo_1'"),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn missing_timestamps_default_to_now() {
+        let line = VALID
+            .replace(r#","created_at":"2024-01-01T00:00:00Z""#, "")
+            .replace(r#","updated_at":"2024-01-01T00:00:00Z""#, "");
+        let before = chrono::Utc::now();
+        let (storage, _tmp, (imported, _, errors)) = import(&[&line]);
+        assert_eq!(imported, 1);
+        assert!(errors.is_empty(), "{errors:?}");
+        let issue = storage.get_issue("demo-1").unwrap().unwrap();
+        assert!(issue.created_at >= before - chrono::Duration::seconds(1));
+        assert!(issue.updated_at >= before - chrono::Duration::seconds(1));
+    }
+
+    #[test]
+    fn missing_required_field_is_reported_per_line() {
+        let line = VALID.replace(r#""title":"Ok","#, "");
+        let (_storage, _tmp, (imported, _, errors)) = import(&[VALID, &line, "not json"]);
+        assert_eq!(imported, 1);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("Line 2: Invalid issue"), "{errors:?}");
+        assert!(
+            errors[1].starts_with("Line 3: Failed to parse JSON"),
+            "{errors:?}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod snapshot_tests {
+    use super::test_support::new_issue;
+    use super::*;
+
+    #[test]
+    fn snapshot_is_a_readable_copy_without_local_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        let storage = Storage::init(beads_dir.clone(), Some("demo".to_string()), false)
+            .expect("init storage");
+        new_issue(&storage, "Frozen");
+        fs::write(beads_dir.join("command_history.log"), "mb create Frozen\n").unwrap();
+
+        let dest = tmp.path().join("snap");
+        assert!(storage.snapshot(&dest).unwrap() > 0);
+        assert!(!dest.join("command_history.log").exists());
+
+        storage.close_issue("demo-1", "done").unwrap();
+        let snapshot = Storage::open(dest.clone()).unwrap();
+        let issue = snapshot.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(issue.status, Status::Open);
+
+        assert!(storage.snapshot(&dest).is_err(), "non-empty destination");
+        assert!(storage.snapshot(&beads_dir.join("nested")).is_err());
+    }
+}
+
+#[cfg(test)]
+mod issue_history_tests {
+    use super::test_support::{empty_storage, new_issue};
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn history_is_none_outside_git() {
+        let (_tmp, storage) = empty_storage();
+        let issue = new_issue(&storage, "Untracked");
+        assert_eq!(storage.issue_history(&issue.id).unwrap(), None);
+    }
+
+    #[test]
+    fn history_lists_commits_touching_the_issue_file() {
+        let (tmp, storage) = empty_storage();
+        git(tmp.path(), &["init", "-q"]);
+        let issue = new_issue(&storage, "Tracked");
+        let other = new_issue(&storage, "Other");
+        assert_eq!(storage.issue_history(&issue.id).unwrap(), Some(Vec::new()));
+
+        git(tmp.path(), &["add", "-A"]);
+        git(tmp.path(), &["commit", "-qm", "Add issues"]);
+        storage
+            .update_issue(
+                &other.id,
+                HashMap::from([("title".to_string(), "Renamed".to_string())]),
+            )
+            .unwrap();
+        git(tmp.path(), &["commit", "-qam", "Rename other"]);
+        storage
+            .update_issue(
+                &issue.id,
+                HashMap::from([("status".to_string(), "in_progress".to_string())]),
+            )
+            .unwrap();
+        git(tmp.path(), &["commit", "-qam", "Start tracked"]);
+
+        let history = storage.issue_history(&issue.id).unwrap().unwrap();
+        let summaries: Vec<_> = history.iter().map(|e| e.summary.as_str()).collect();
+        assert_eq!(summaries, ["Start tracked", "Add issues"]);
+        assert_eq!(history[0].author, "Test");
+        assert!(storage.issue_history("demo-99").is_err());
+    }
+
+    #[test]
+    fn blame_reports_commit_that_last_changed_a_field() {
+        let (tmp, storage) = empty_storage();
+        git(tmp.path(), &["init", "-q"]);
+        let issue = new_issue(&storage, "Tracked");
+        let blame = storage.field_blame(&issue.id, "status").unwrap().unwrap();
+        assert_eq!(blame.value, "open");
+        assert_eq!(blame.commit, None);
+
+        git(tmp.path(), &["add", "-A"]);
+        git(tmp.path(), &["commit", "-qm", "Add issue"]);
+        storage
+            .update_issue(
+                &issue.id,
+                HashMap::from([("status".to_string(), "blocked".to_string())]),
+            )
+            .unwrap();
+        git(tmp.path(), &["commit", "-qam", "Block it"]);
+        storage
+            .update_issue(
+                &issue.id,
+                HashMap::from([("title".to_string(), "Renamed".to_string())]),
+            )
+            .unwrap();
+        git(tmp.path(), &["commit", "-qam", "Rename it"]);
+
+        let blame = storage.field_blame(&issue.id, "status").unwrap().unwrap();
+        assert_eq!(blame.value, "blocked");
+        let commit = blame.commit.unwrap();
+        assert_eq!(commit.summary, "Block it");
+        assert_eq!(commit.author, "Test");
+        assert_eq!(commit.date.len(), "2024-01-01".len());
+
+        let err = storage.field_blame(&issue.id, "nonexistent").unwrap_err();
+        assert!(err.to_string().contains("is not set"), "{err}");
+    }
+
+    #[test]
+    fn blame_is_none_outside_git() {
+        let (_tmp, storage) = empty_storage();
+        let issue = new_issue(&storage, "Untracked");
+        assert_eq!(storage.field_blame(&issue.id, "status").unwrap(), None);
+    }
+}
+
+#[cfg(test)]
+mod watcher_tests {
+    use super::test_support::{empty_storage, new_issue};
+    use crate::types::UpstreamIssue;
+
+    #[test]
+    fn watch_and_unwatch_update_the_list() {
+        let (_tmp, storage) = empty_storage();
+        let issue = new_issue(&storage, "Watched");
+
+        storage.add_watcher(&issue.id, "zoe").unwrap();
+        storage.add_watcher(&issue.id, "alice").unwrap();
+        let watched = storage.add_watcher(&issue.id, "zoe").unwrap();
+        assert_eq!(watched.watchers, ["alice", "zoe"]);
+        let stored = storage.get_issue(&issue.id).unwrap().unwrap();
+        assert_eq!(stored.watchers, ["alice", "zoe"]);
+
+        // Watchers stay out of the upstream bd schema
+        let upstream = serde_json::to_string(&UpstreamIssue::from(&stored)).unwrap();
+        assert!(!upstream.contains("watchers"), "{upstream}");
+
+        let unwatched = storage.remove_watcher(&issue.id, "zoe").unwrap();
+        assert_eq!(unwatched.watchers, ["alice"]);
+        let unchanged = storage.remove_watcher(&issue.id, "bob").unwrap();
+        assert_eq!(unchanged.updated_at, unwatched.updated_at);
+
+        assert!(storage.add_watcher("demo-99", "alice").is_err());
+    }
+}
+
+#[cfg(test)]
+mod transition_tests {
+    use super::test_support::{empty_storage, new_issue};
+    use super::*;
+
+    fn setup() -> (tempfile::TempDir, Storage, Issue) {
+        let (tmp, storage) = empty_storage();
+        let issue = new_issue(&storage, "Work");
+        (tmp, storage, issue)
+    }
+
+    #[test]
+    fn lifecycle_follows_allowed_transitions() {
+        let (_tmp, storage, issue) = setup();
+        let id = issue.id.as_str();
+
+        let blocked = storage
+            .transition_issue(id, Transition::Block, Some("waiting on review"))
+            .unwrap();
+        assert_eq!(blocked.status, Status::Blocked);
+        assert_eq!(blocked.notes, "waiting on review");
+        // Nobody is assigned, so unblocking returns it to open
+        let unblocked = storage
+            .transition_issue(id, Transition::Unblock, None)
+            .unwrap();
+        assert_eq!(unblocked.status, Status::Open);
+
+        let started = storage
+            .transition_issue(id, Transition::Start, None)
+            .unwrap();
+        assert_eq!(started.status, Status::InProgress);
+        let err = storage
+            .transition_issue(id, Transition::Start, None)
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Cannot start demo-1: it is in_progress"),
+            "{err}"
+        );
+
+        storage
+            .update_issue(
+                id,
+                HashMap::from([("assignee".to_string(), "amy".to_string())]),
+            )
+            .unwrap();
+        storage
+            .transition_issue(id, Transition::Block, None)
+            .unwrap();
+        let resumed = storage
+            .transition_issue(id, Transition::Unblock, None)
+            .unwrap();
+        assert_eq!(resumed.status, Status::InProgress);
+    }
+
+    #[test]
+    fn closed_issue_is_not_restarted() {
+        let (_tmp, storage, issue) = setup();
+        storage.close_issue(&issue.id, "done").unwrap();
+
+        for transition in [Transition::Start, Transition::Block, Transition::Unblock] {
+            let err = storage
+                .transition_issue(&issue.id, transition, None)
+                .unwrap_err();
+            assert!(err.to_string().contains("mb reopen"), "{err}");
+        }
+        let stored = storage.get_issue(&issue.id).unwrap().unwrap();
+        assert_eq!(stored.status, Status::Closed);
+    }
+}
+
+#[cfg(test)]
+mod resolution_tests {
+    use super::test_support::{empty_storage, storage_with_titles};
+    use super::*;
+
+    #[test]
+    fn resolution_is_stored_filtered_and_counted() {
+        let (_tmp, storage) = storage_with_titles(&["One", "Two", "Three", "Four"]);
+        storage
+            .close_issue_with_resolution("demo-1", "dup of demo-2", Some(Resolution::Duplicate))
+            .unwrap();
+        storage
+            .close_issue_with_resolution("demo-2", "shipped", Some(Resolution::Done))
+            .unwrap();
+        storage
+            .close_issue_with_resolution("demo-3", "also dup", Some(Resolution::Duplicate))
+            .unwrap();
+        storage.close_issue("demo-4", "done").unwrap();
+
+        let stored = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(stored.resolution, Some(Resolution::Duplicate));
+
+        let duplicates = storage
+            .list_issues(&IssueFilter::new().resolution(Resolution::Duplicate), None)
+            .unwrap();
+        let ids: Vec<_> = duplicates.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["demo-1", "demo-3"]);
+
+        let stats = storage.get_stats().unwrap();
+        assert_eq!(stats.closed_issues, 4);
+        assert_eq!(
+            stats.closed_by_resolution,
+            BTreeMap::from([(Resolution::Done, 1), (Resolution::Duplicate, 2)])
+        );
+
+        let reopened = storage.reopen_issue("demo-1").unwrap();
+        assert_eq!(reopened.resolution, None);
+    }
+
+    #[test]
+    fn mark_duplicate_closes_links_and_copies_labels() {
+        let (_tmp, storage) = empty_storage();
+        for (title, labels) in [("Canonical", vec!["ui"]), ("Dup", vec!["ui", "urgent"])] {
+            storage
+                .create_issue(IssueSpec {
+                    labels: labels.into_iter().map(String::from).collect(),
+                    ..IssueSpec::new(title)
+                })
+                .unwrap();
+        }
+
+        let (dup, canonical) = storage.mark_duplicate("demo-2", "demo-1", true).unwrap();
+        assert_eq!(dup.status, Status::Closed);
+        assert_eq!(dup.resolution, Some(Resolution::Duplicate));
+        assert_eq!(dup.depends_on.get("demo-1"), Some(&DependencyType::Related));
+        assert_eq!(canonical.labels, ["ui", "urgent"]);
+
+        let stored = storage.get_issue("demo-1").unwrap().unwrap();
+        assert_eq!(stored.labels, ["ui", "urgent"]);
+        assert_eq!(stored.status, Status::Open);
+
+        assert!(storage.mark_duplicate("demo-1", "demo-1", false).is_err());
+        assert!(storage.mark_duplicate("demo-1", "demo-9", false).is_err());
+    }
+}
+
+#[cfg(test)]
+mod with_all_issues_tests {
+    use super::test_support::storage_with_titles;
+    use super::*;
+
+    #[test]
+    fn lends_every_issue_from_one_load() {
+        let (_tmp, storage) = storage_with_titles(&["Parent", "Child"]);
+        storage
+            .add_dependency("demo-2", "demo-1", DependencyType::Blocks, false)
+            .unwrap();
+        storage.close_issue("demo-2", "done").unwrap();
+
+        let (ids, closed, dependents) = storage
+            .with_all_issues(|issues| {
+                let ids: Vec<String> = issues.iter().map(|i| i.id.clone()).collect();
+                let closed = issues.iter().filter(|i| i.status == Status::Closed).count();
+                (ids, closed, issues[0].dependents.len())
+            })
+            .unwrap();
+        assert_eq!(ids, ["demo-1", "demo-2"]);
+        assert_eq!(closed, 1);
+        assert_eq!(dependents, 1);
+
+        // The lock is not held while the closure runs
+        let nested = storage
+            .with_all_issues(|issues| storage.get_issue(&issues[0].id).unwrap().is_some())
+            .unwrap();
+        assert!(nested);
+    }
+}
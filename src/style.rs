@@ -0,0 +1,110 @@
+//! ANSI styling for human-readable output.
+//!
+//! Every helper takes a `color` flag and returns the text unchanged when it is
+//! false, so command handlers decide once (via `color_enabled`) and never
+//! branch on color themselves. JSON output never goes through here.
+
+use std::env;
+use std::io::IsTerminal;
+
+/// Whether stdout should be colored: it must be a terminal, `--no-color` must
+/// not be set, and neither `NO_COLOR`, `CLICOLOR=0`, nor `TERM=dumb` may be
+/// present in the environment.
+pub fn color_enabled(no_color: bool) -> bool {
+    !no_color
+        && std::io::stdout().is_terminal()
+        && env::var_os("NO_COLOR").is_none()
+        && env::var("CLICOLOR").map(|v| v != "0").unwrap_or(true)
+        && env::var("TERM").map(|v| v != "dumb").unwrap_or(true)
+}
+
+fn paint(code: &str, text: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+pub fn label(text: &str, color: bool) -> String {
+    paint("36;1", text, color)
+}
+
+pub fn title(text: &str, color: bool) -> String {
+    paint("1", text, color)
+}
+
+pub fn value(text: &str, color: bool) -> String {
+    paint("34", text, color)
+}
+
+/// Issue IDs in list-style output
+pub fn id(text: &str, color: bool) -> String {
+    paint("36", text, color)
+}
+
+/// Status names: open green, in_progress yellow, closed dim
+pub fn status(text: &str, color: bool) -> String {
+    let code = match text {
+        "open" => "32",
+        "in_progress" => "33",
+        "closed" => "90",
+        _ => "37",
+    };
+    paint(code, text, color)
+}
+
+/// Priorities: P0 bold red, P1 red, P2 plain, P3/P4 dim
+pub fn priority(priority: i32, color: bool) -> String {
+    let text = priority.to_string();
+    match priority {
+        0 => paint("31;1", &text, color),
+        1 => paint("31", &text, color),
+        2 => text,
+        _ => paint("90", &text, color),
+    }
+}
+
+/// Highlight the `====` underlines and `###` comment headings of the
+/// markdown rendered by `show` when no external highlighter is available.
+pub fn markdown_fallback(markdown: &str) -> String {
+    markdown
+        .lines()
+        .map(|line| {
+            if line.chars().all(|c| c == '=') && !line.is_empty() {
+                format!("\x1b[36;1m{}\x1b[0m", line)
+            } else if line.starts_with("### ") {
+                format!("\x1b[35;1m{}\x1b[0m", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_when_color_is_off() {
+        assert_eq!(status("closed", false), "closed");
+        assert_eq!(priority(0, false), "0");
+        assert_eq!(id("demo-1", false), "demo-1");
+    }
+
+    #[test]
+    fn priorities_are_colored_by_urgency() {
+        assert_eq!(priority(0, true), "\x1b[31;1m0\x1b[0m");
+        assert_eq!(priority(2, true), "2");
+        assert_eq!(priority(4, true), "\x1b[90m4\x1b[0m");
+        assert_eq!(status("closed", true), "\x1b[90mclosed\x1b[0m");
+    }
+
+    #[test]
+    fn no_color_flag_wins() {
+        assert!(!color_enabled(true));
+    }
+}
@@ -21,19 +21,23 @@
 //!    - markdown_newer: Update JSONL from markdown
 //!    - jsonl_newer: Update markdown from JSONL
 //!    - no_change: Skip (timestamps match)
-//!    - conflict: Skip with warning (same timestamp, different content)
+//!    - conflict: Skip with warning (same timestamp, different content), or
+//!      let one side win per the engine's [`ConflictPolicy`]
 //! 4. Apply changes bidirectionally
 //! 5. Preserve timestamps when writing (set file mtime)
 
 use anyhow::{anyhow, Context, Result};
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
-use crate::format::markdown_to_issue;
-use crate::types::Issue;
+use crate::format::{
+    issue_to_markdown_with_sections, markdown_to_issue_with_sections, SectionHeaders,
+};
+use crate::storage::{load_section_headers, write_atomic};
+use crate::types::{Issue, JsonlHeader};
 
 /// Timestamped issue from markdown (with filesystem mtime)
 #[derive(Debug, Clone)]
@@ -98,6 +102,34 @@ impl SyncPlan {
     }
 }
 
+/// How `apply` handles a conflict (same timestamp, different content)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Leave both sides alone and report the conflict
+    #[default]
+    Skip,
+    /// Overwrite the JSONL entry with the markdown version
+    Markdown,
+    /// Overwrite the markdown file with the JSONL version
+    Jsonl,
+}
+
+impl std::str::FromStr for ConflictPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(ConflictPolicy::Skip),
+            "markdown" => Ok(ConflictPolicy::Markdown),
+            "jsonl" => Ok(ConflictPolicy::Jsonl),
+            _ => Err(anyhow!(
+                "Invalid conflict policy: '{}'. Valid values are: markdown, jsonl, skip",
+                s
+            )),
+        }
+    }
+}
+
 /// Sync execution report
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct SyncReport {
@@ -106,7 +138,14 @@ pub struct SyncReport {
     pub updated_jsonl: usize,
     pub updated_markdown: usize,
     pub skipped_conflicts: usize,
+    /// Conflicts settled by the conflict policy; also counted in
+    /// `updated_jsonl` or `updated_markdown`
+    #[serde(default)]
+    pub resolved_conflicts: usize,
     pub errors: Vec<String>,
+    /// Copy of the JSONL file taken before it was rewritten, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backup_path: Option<PathBuf>,
 }
 
 impl SyncReport {
@@ -126,6 +165,7 @@ pub fn load_markdown_issues(beads_dir: &Path) -> Result<HashMap<String, Markdown
         return Ok(HashMap::new());
     }
 
+    let sections = load_section_headers(beads_dir)?;
     let mut result = HashMap::new();
 
     for entry in fs::read_dir(&issues_dir).context("Failed to read issues directory")? {
@@ -152,7 +192,7 @@ pub fn load_markdown_issues(beads_dir: &Path) -> Result<HashMap<String, Markdown
         // Read and parse markdown file
         let content = fs::read_to_string(&path)
             .with_context(|| format!("Failed to read {}", path.display()))?;
-        let issue = markdown_to_issue(issue_id, &content)
+        let issue = markdown_to_issue_with_sections(issue_id, &content, &sections)
             .with_context(|| format!("Failed to parse {}", path.display()))?;
 
         result.insert(issue.id.clone(), MarkdownIssue { issue, mtime, path });
@@ -173,7 +213,7 @@ pub fn load_jsonl_issues(jsonl_path: &Path) -> Result<HashMap<String, JsonlIssue
     let mut result = HashMap::new();
 
     for (line_num, line) in content.lines().enumerate() {
-        if line.trim().is_empty() {
+        if line.trim().is_empty() || JsonlHeader::from_line(line).is_some() {
             continue;
         }
 
@@ -197,23 +237,119 @@ pub fn load_jsonl_issues(jsonl_path: &Path) -> Result<HashMap<String, JsonlIssue
     Ok(result)
 }
 
+/// One field whose value differs between the markdown and JSONL copies of an issue
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct FieldDiff {
+    pub markdown: serde_json::Value,
+    pub jsonl: serde_json::Value,
+}
+
+/// Compare two copies of the same issue field by field
+///
+/// Fields are compared by their JSON serialization and keyed by JSON field
+/// name; only fields that differ are returned. `dependents` is skipped because
+/// it is derived from other issues rather than stored with this one, and
+/// `dependencies` is compared in ID order because its order carries no meaning.
+pub fn diff_issue_fields(markdown: &Issue, jsonl: &Issue) -> Result<BTreeMap<String, FieldDiff>> {
+    let to_object = |issue: &Issue| -> Result<serde_json::Map<String, serde_json::Value>> {
+        match serde_json::to_value(issue).context("Failed to serialize issue")? {
+            serde_json::Value::Object(mut map) => {
+                if let Some(serde_json::Value::Array(deps)) = map.get_mut("dependencies") {
+                    deps.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+                }
+                Ok(map)
+            }
+            _ => Err(anyhow!("Issue did not serialize to a JSON object")),
+        }
+    };
+    let md = to_object(markdown)?;
+    let json = to_object(jsonl)?;
+
+    let mut diffs = BTreeMap::new();
+    for key in md.keys().chain(json.keys()) {
+        if key == "dependents" || diffs.contains_key(key) {
+            continue;
+        }
+        let md_value = md.get(key).cloned().unwrap_or(serde_json::Value::Null);
+        let json_value = json.get(key).cloned().unwrap_or(serde_json::Value::Null);
+        if md_value != json_value {
+            diffs.insert(
+                key.clone(),
+                FieldDiff {
+                    markdown: md_value,
+                    jsonl: json_value,
+                },
+            );
+        }
+    }
+
+    Ok(diffs)
+}
+
+/// Read the schema header from the first non-empty line of a JSONL file, if any
+fn read_jsonl_header(jsonl_path: &Path) -> Result<Option<JsonlHeader>> {
+    if !jsonl_path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(jsonl_path)
+        .with_context(|| format!("Failed to read {}", jsonl_path.display()))?;
+
+    Ok(content
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .and_then(JsonlHeader::from_line))
+}
+
+/// Default tolerance for comparing markdown mtimes with JSONL `updated_at`
+pub const DEFAULT_TOLERANCE_MS: u64 = 1000;
+
 /// Main sync engine
 pub struct SyncEngine {
     /// Tolerance for timestamp comparison (in milliseconds)
     /// Allows small differences due to filesystem precision
     tolerance_ms: u64,
+    /// Copy the JSONL file to `issues.jsonl.bak` before rewriting it
+    backup: bool,
+    /// Which side wins a conflict
+    conflict_policy: ConflictPolicy,
 }
 
 impl SyncEngine {
     /// Create a new sync engine with default tolerance (1 second)
     pub fn new() -> Self {
-        Self { tolerance_ms: 1000 }
+        Self {
+            tolerance_ms: DEFAULT_TOLERANCE_MS,
+            backup: true,
+            conflict_policy: ConflictPolicy::Skip,
+        }
     }
 
     /// Create a sync engine with custom tolerance
-    #[allow(dead_code)]
+    ///
+    /// Markdown mtimes are only as precise as the filesystem: FAT keeps 2
+    /// seconds, and some network mounts round to whole seconds, so a tolerance
+    /// below that granularity makes unchanged issues look newer on one side.
+    /// On filesystems with nanosecond mtimes a tighter tolerance notices
+    /// edits made within the same second.
     pub fn with_tolerance_ms(tolerance_ms: u64) -> Self {
-        Self { tolerance_ms }
+        Self {
+            tolerance_ms,
+            backup: true,
+            conflict_policy: ConflictPolicy::Skip,
+        }
+    }
+
+    /// Enable or disable the JSONL backup taken before destructive writes
+    pub fn with_backup(mut self, backup: bool) -> Self {
+        self.backup = backup;
+        self
+    }
+
+    /// Choose which side wins a conflict (default: skip it)
+    pub fn with_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.conflict_policy = policy;
+        self
     }
 
     /// Compare two timestamps and determine which is newer
@@ -283,9 +419,13 @@ impl SyncEngine {
                             plan.jsonl_newer.push(id.clone());
                         }
                         std::cmp::Ordering::Equal => {
-                            // Timestamps match - assume no changes for now
-                            // TODO(minibeads-19): Implement content-based conflict detection
-                            plan.no_change.push(id.clone());
+                            // Timestamps match, so differing content means both
+                            // sides were edited
+                            if md_issue.issue.content_hash() == json_issue.issue.content_hash() {
+                                plan.no_change.push(id.clone());
+                            } else {
+                                plan.conflicts.push(id.clone());
+                            }
                         }
                     }
                 }
@@ -308,6 +448,7 @@ impl SyncEngine {
         let mut report = SyncReport::default();
         let issues_dir = beads_dir.join("issues");
         let jsonl_path = beads_dir.join("issues.jsonl");
+        let sections = load_section_headers(beads_dir)?;
 
         // Ensure issues directory exists
         if !dry_run && !issues_dir.exists() {
@@ -322,6 +463,7 @@ impl SyncEngine {
                 } else {
                     match self.write_markdown_issue(
                         &issues_dir,
+                        &sections,
                         &json_issue.issue,
                         json_issue.updated_at,
                     ) {
@@ -345,6 +487,7 @@ impl SyncEngine {
                 } else {
                     match self.write_markdown_issue(
                         &issues_dir,
+                        &sections,
                         &json_issue.issue,
                         json_issue.updated_at,
                     ) {
@@ -357,23 +500,60 @@ impl SyncEngine {
             }
         }
 
-        // 3. Create JSONL entries from markdown-only issues
+        // 2b. Resolve conflicts in favor of JSONL
+        if self.conflict_policy == ConflictPolicy::Jsonl {
+            for id in &plan.conflicts {
+                if let Some(json_issue) = jsonl_issues.get(id) {
+                    if dry_run {
+                        println!(
+                            "[DRY RUN] Would update markdown: {}.md (conflict, preferring JSONL)",
+                            id
+                        );
+                    } else {
+                        match self.write_markdown_issue(
+                            &issues_dir,
+                            &sections,
+                            &json_issue.issue,
+                            json_issue.updated_at,
+                        ) {
+                            Ok(_) => {
+                                report.updated_markdown += 1;
+                                report.resolved_conflicts += 1;
+                            }
+                            Err(e) => report
+                                .errors
+                                .push(format!("Failed to update {}.md: {}", id, e)),
+                        }
+                    }
+                }
+            }
+        }
+
+        // Back up the JSONL before the first write that touches it
+        let prefer_markdown = self.conflict_policy == ConflictPolicy::Markdown;
+        let writes_jsonl = !plan.markdown_only.is_empty()
+            || !plan.markdown_newer.is_empty()
+            || (prefer_markdown && !plan.conflicts.is_empty());
+        if !dry_run && self.backup && writes_jsonl && jsonl_path.exists() {
+            let backup_path = beads_dir.join("issues.jsonl.bak");
+            fs::copy(&jsonl_path, &backup_path)
+                .with_context(|| format!("Failed to back up JSONL to {}", backup_path.display()))?;
+            report.backup_path = Some(backup_path);
+        }
+
+        // 3 & 4. Collect JSONL creations (markdown-only issues) and updates
+        // (markdown newer), then rewrite the file once for all of them
+        let mut jsonl_changes: Vec<&Issue> = Vec::new();
         for id in &plan.markdown_only {
             if let Some(md_issue) = markdown_issues.get(id) {
                 if dry_run {
                     println!("[DRY RUN] Would create JSONL entry: {}", id);
                 } else {
-                    match self.append_jsonl_issue(&jsonl_path, &md_issue.issue) {
-                        Ok(_) => report.created_in_jsonl += 1,
-                        Err(e) => report
-                            .errors
-                            .push(format!("Failed to create JSONL entry {}: {}", id, e)),
-                    }
+                    jsonl_changes.push(&md_issue.issue);
                 }
             }
         }
-
-        // 4. Update JSONL entries when markdown is newer
+        let created_count = jsonl_changes.len();
         for id in &plan.markdown_newer {
             if let Some(md_issue) = markdown_issues.get(id) {
                 if dry_run {
@@ -382,23 +562,51 @@ impl SyncEngine {
                         id
                     );
                 } else {
-                    match self.update_jsonl_issue(&jsonl_path, &md_issue.issue) {
-                        Ok(_) => report.updated_jsonl += 1,
-                        Err(e) => report
-                            .errors
-                            .push(format!("Failed to update JSONL entry {}: {}", id, e)),
+                    jsonl_changes.push(&md_issue.issue);
+                }
+            }
+        }
+        let updated_count = jsonl_changes.len() - created_count;
+        if prefer_markdown {
+            for id in &plan.conflicts {
+                if let Some(md_issue) = markdown_issues.get(id) {
+                    if dry_run {
+                        println!(
+                            "[DRY RUN] Would update JSONL entry: {} (conflict, preferring markdown)",
+                            id
+                        );
+                    } else {
+                        jsonl_changes.push(&md_issue.issue);
                     }
                 }
             }
         }
 
-        // 5. Report conflicts (skip them)
-        for id in &plan.conflicts {
-            report.skipped_conflicts += 1;
-            if dry_run {
-                println!("[DRY RUN] Would skip conflict: {}", id);
-            } else {
-                report.errors.push(format!("Conflict skipped: {}", id));
+        if !jsonl_changes.is_empty() {
+            match self.write_jsonl_changes(&jsonl_path, &jsonl_changes) {
+                Ok(_) => {
+                    let resolved_count = jsonl_changes.len() - created_count - updated_count;
+                    report.created_in_jsonl += created_count;
+                    report.updated_jsonl += updated_count + resolved_count;
+                    report.resolved_conflicts += resolved_count;
+                }
+                Err(e) => {
+                    report
+                        .errors
+                        .push(format!("Failed to write {}: {}", jsonl_path.display(), e))
+                }
+            }
+        }
+
+        // 5. Report conflicts the policy leaves unresolved (skip them)
+        if self.conflict_policy == ConflictPolicy::Skip {
+            for id in &plan.conflicts {
+                report.skipped_conflicts += 1;
+                if dry_run {
+                    println!("[DRY RUN] Would skip conflict: {}", id);
+                } else {
+                    report.errors.push(format!("Conflict skipped: {}", id));
+                }
             }
         }
 
@@ -409,15 +617,15 @@ impl SyncEngine {
     fn write_markdown_issue(
         &self,
         issues_dir: &Path,
+        sections: &SectionHeaders,
         issue: &Issue,
         timestamp: DateTime<Utc>,
     ) -> Result<()> {
-        use crate::format::issue_to_markdown;
-
         let path = issues_dir.join(format!("{}.md", issue.id));
-        let content = issue_to_markdown(issue)?;
+        let content = issue_to_markdown_with_sections(issue, sections)?;
 
-        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        write_atomic(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
 
         // Set the file's mtime to match the JSONL timestamp
         let systime: SystemTime = timestamp.into();
@@ -427,24 +635,8 @@ impl SyncEngine {
         Ok(())
     }
 
-    /// Append a new issue to JSONL file
-    fn append_jsonl_issue(&self, jsonl_path: &Path, issue: &Issue) -> Result<()> {
-        let json = serde_json::to_string(issue).context("Failed to serialize issue to JSON")?;
-
-        let mut file = fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(jsonl_path)
-            .with_context(|| format!("Failed to open {}", jsonl_path.display()))?;
-
-        use std::io::Write;
-        writeln!(file, "{}", json).context("Failed to write to JSONL file")?;
-
-        Ok(())
-    }
-
-    /// Update an existing issue in JSONL file
-    fn update_jsonl_issue(&self, jsonl_path: &Path, issue: &Issue) -> Result<()> {
+    /// Insert or replace `issues` in the JSONL file with a single rewrite
+    fn write_jsonl_changes(&self, jsonl_path: &Path, issues: &[&Issue]) -> Result<()> {
         // Read all issues
         let mut all_issues = if jsonl_path.exists() {
             load_jsonl_issues(jsonl_path)?
@@ -452,26 +644,36 @@ impl SyncEngine {
             HashMap::new()
         };
 
-        // Update or insert the issue
-        all_issues.insert(
-            issue.id.clone(),
-            JsonlIssue {
-                issue: issue.clone(),
-                updated_at: issue.updated_at,
-            },
-        );
+        // Update or insert the changed issues
+        for issue in issues {
+            all_issues.insert(
+                issue.id.clone(),
+                JsonlIssue {
+                    issue: (*issue).clone(),
+                    updated_at: issue.updated_at,
+                },
+            );
+        }
 
-        // Write all issues back
-        let mut lines: Vec<String> = all_issues
-            .values()
+        // Write all issues back, sorted by ID (same order as `mb export`)
+        let mut sorted: Vec<&JsonlIssue> = all_issues.values().collect();
+        sorted.sort_by(|a, b| a.issue.id.cmp(&b.issue.id));
+        let mut lines: Vec<String> = sorted
+            .into_iter()
             .map(|json_issue| serde_json::to_string(&json_issue.issue))
             .collect::<Result<Vec<_>, _>>()
             .context("Failed to serialize issues")?;
 
-        lines.sort(); // Ensure deterministic order
+        // Keep the schema header if the file had one
+        if let Some(header) = read_jsonl_header(jsonl_path)? {
+            lines.insert(
+                0,
+                serde_json::to_string(&header).context("Failed to serialize JSONL header")?,
+            );
+        }
         let content = lines.join("\n") + "\n";
 
-        fs::write(jsonl_path, content)
+        write_atomic(jsonl_path, content)
             .with_context(|| format!("Failed to write {}", jsonl_path.display()))?;
 
         Ok(())
@@ -487,6 +689,7 @@ impl Default for SyncEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::storage::test_support::new_issue;
     use chrono::Duration;
 
     #[test]
@@ -539,4 +742,176 @@ mod tests {
             std::cmp::Ordering::Equal
         );
     }
+
+    /// Beads dir with one markdown-only issue and a pre-existing JSONL file.
+    fn beads_dir_with_pending_jsonl_write() -> (tempfile::TempDir, PathBuf, String) {
+        let tmp = tempfile::tempdir().unwrap();
+        let beads_dir = tmp.path().join(".beads");
+        let storage =
+            crate::storage::Storage::init(beads_dir.clone(), Some("demo".to_string()), false)
+                .unwrap();
+        new_issue(&storage, "A task");
+        let original = serde_json::to_string(&JsonlHeader::current()).unwrap() + "\n";
+        fs::write(beads_dir.join("issues.jsonl"), &original).unwrap();
+        (tmp, beads_dir, original)
+    }
+
+    fn run_sync(engine: &SyncEngine, beads_dir: &Path) -> SyncReport {
+        let md = load_markdown_issues(beads_dir).unwrap();
+        let jsonl = load_jsonl_issues(&beads_dir.join("issues.jsonl")).unwrap();
+        let plan = engine.analyze(md.clone(), jsonl.clone()).unwrap();
+        engine.apply(&plan, &md, &jsonl, beads_dir, false).unwrap()
+    }
+
+    #[test]
+    fn test_apply_backs_up_jsonl_before_writing() {
+        let (_tmp, beads_dir, original) = beads_dir_with_pending_jsonl_write();
+        let report = run_sync(&SyncEngine::new(), &beads_dir);
+
+        let backup_path = beads_dir.join("issues.jsonl.bak");
+        assert_eq!(report.backup_path.as_deref(), Some(backup_path.as_path()));
+        assert_eq!(fs::read_to_string(&backup_path).unwrap(), original);
+        assert_eq!(report.created_in_jsonl, 1);
+    }
+
+    #[test]
+    fn test_apply_without_backup() {
+        let (_tmp, beads_dir, _) = beads_dir_with_pending_jsonl_write();
+        let report = run_sync(&SyncEngine::new().with_backup(false), &beads_dir);
+
+        assert!(report.backup_path.is_none());
+        assert!(!beads_dir.join("issues.jsonl.bak").exists());
+        assert_eq!(report.created_in_jsonl, 1);
+    }
+
+    #[test]
+    fn test_apply_writes_jsonl_once_in_sorted_order() {
+        let (_tmp, beads_dir, _) = beads_dir_with_pending_jsonl_write();
+        let storage = crate::storage::Storage::open(beads_dir.clone()).unwrap();
+        for title in ["Second", "Third"] {
+            new_issue(&storage, title);
+        }
+
+        let report = run_sync(&SyncEngine::new(), &beads_dir);
+        assert_eq!(report.created_in_jsonl, 3);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+
+        let content = fs::read_to_string(beads_dir.join("issues.jsonl")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        // Header is kept first; issue lines follow in sorted order.
+        assert_eq!(lines.len(), 4);
+        assert!(JsonlHeader::from_line(lines[0]).is_some());
+        let mut sorted = lines[1..].to_vec();
+        sorted.sort();
+        assert_eq!(lines[1..], sorted[..]);
+    }
+
+    /// Synced beads dir whose JSONL title was edited without touching
+    /// `updated_at`, so the two sides conflict.
+    fn beads_dir_with_conflict() -> (tempfile::TempDir, PathBuf) {
+        let (tmp, beads_dir, _) = beads_dir_with_pending_jsonl_write();
+        run_sync(&SyncEngine::new().with_backup(false), &beads_dir);
+        let jsonl_path = beads_dir.join("issues.jsonl");
+        let content = fs::read_to_string(&jsonl_path).unwrap();
+        fs::write(&jsonl_path, content.replace("A task", "Edited in JSONL")).unwrap();
+        (tmp, beads_dir)
+    }
+
+    fn titles(beads_dir: &Path) -> (String, String) {
+        let md = load_markdown_issues(beads_dir).unwrap();
+        let jsonl = load_jsonl_issues(&beads_dir.join("issues.jsonl")).unwrap();
+        (
+            md["demo-1"].issue.title.clone(),
+            jsonl["demo-1"].issue.title.clone(),
+        )
+    }
+
+    #[test]
+    fn test_same_timestamp_different_content_is_a_conflict() {
+        let (_tmp, beads_dir) = beads_dir_with_conflict();
+        let report = run_sync(&SyncEngine::new().with_backup(false), &beads_dir);
+
+        assert_eq!(report.skipped_conflicts, 1);
+        assert_eq!(report.resolved_conflicts, 0);
+        assert_eq!(report.total_changes(), 0);
+        assert_eq!(
+            titles(&beads_dir),
+            ("A task".to_string(), "Edited in JSONL".to_string())
+        );
+    }
+
+    #[test]
+    fn test_conflict_policy_lets_one_side_win() {
+        for (policy, expected) in [
+            (ConflictPolicy::Markdown, "A task"),
+            (ConflictPolicy::Jsonl, "Edited in JSONL"),
+        ] {
+            let (_tmp, beads_dir) = beads_dir_with_conflict();
+            let engine = SyncEngine::new()
+                .with_backup(false)
+                .with_conflict_policy(policy);
+            let report = run_sync(&engine, &beads_dir);
+
+            assert_eq!(report.skipped_conflicts, 0);
+            assert_eq!(report.resolved_conflicts, 1);
+            assert!(report.errors.is_empty(), "{:?}", report.errors);
+            assert_eq!(
+                titles(&beads_dir),
+                (expected.to_string(), expected.to_string())
+            );
+
+            let again = run_sync(&engine, &beads_dir);
+            assert_eq!((again.total_changes(), again.resolved_conflicts), (0, 0));
+        }
+    }
+
+    #[test]
+    fn test_diff_issue_fields_reports_only_changed_fields() {
+        let markdown = Issue::new(
+            "demo-1".to_string(),
+            "Title".to_string(),
+            2,
+            crate::types::IssueType::Task,
+        );
+        let mut jsonl = markdown.clone();
+        jsonl.title = "Renamed".to_string();
+        jsonl.priority = 1;
+        jsonl.dependents.push(crate::types::Dependency {
+            id: "demo-2".to_string(),
+            dep_type: "blocks".to_string(),
+        });
+
+        let diffs = diff_issue_fields(&markdown, &jsonl).unwrap();
+        assert_eq!(
+            diffs.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["priority", "title"]
+        );
+        assert_eq!(diffs["title"].markdown, serde_json::json!("Title"));
+        assert_eq!(diffs["title"].jsonl, serde_json::json!("Renamed"));
+        assert!(diff_issue_fields(&markdown, &markdown).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diff_issue_fields_ignores_dependency_order() {
+        let mut markdown = Issue::new(
+            "demo-1".to_string(),
+            "Title".to_string(),
+            2,
+            crate::types::IssueType::Task,
+        );
+        let mut jsonl = markdown.clone();
+        // Two maps built in opposite orders iterate in unrelated orders
+        for n in 2..20 {
+            markdown
+                .depends_on
+                .insert(format!("demo-{n}"), crate::types::DependencyType::Blocks);
+        }
+        for n in (2..20).rev() {
+            jsonl
+                .depends_on
+                .insert(format!("demo-{n}"), crate::types::DependencyType::Blocks);
+        }
+
+        assert!(diff_issue_fields(&markdown, &jsonl).unwrap().is_empty());
+    }
 }
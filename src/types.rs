@@ -1,6 +1,66 @@
+use crate::error::invalid_input;
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Version of the JSONL export format (`issues.jsonl`).
+///
+/// Bumped whenever the shape of an exported issue line changes incompatibly
+/// (e.g. the `dependencies` map-to-array migration). Reported by
+/// `mb version --json` so agents can decide how to parse an export.
+pub const JSONL_SCHEMA_VERSION: u32 = 1;
+
+/// Value of the `_schema` key in a JSONL header line.
+pub const JSONL_SCHEMA_NAME: &str = "minibeads-jsonl";
+
+/// Optional first line of an exported JSONL file, written by
+/// `mb export --with-header`:
+///
+/// ```json
+/// {"_schema":"minibeads-jsonl","version":1}
+/// ```
+///
+/// Readers skip it when loading issues; importers use it to warn when the
+/// file was written with a different schema version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsonlHeader {
+    #[serde(rename = "_schema")]
+    pub schema: String,
+    pub version: u32,
+}
+
+impl JsonlHeader {
+    /// Header describing the format this build writes.
+    pub fn current() -> Self {
+        JsonlHeader {
+            schema: JSONL_SCHEMA_NAME.to_string(),
+            version: JSONL_SCHEMA_VERSION,
+        }
+    }
+
+    /// Parse `line` as a header, returning `None` for ordinary issue lines.
+    pub fn from_line(line: &str) -> Option<Self> {
+        serde_json::from_str(line).ok()
+    }
+
+    /// Describe why this header is incompatible with the current build, if it is.
+    pub fn mismatch(&self) -> Option<String> {
+        if self.schema != JSONL_SCHEMA_NAME {
+            Some(format!(
+                "unknown JSONL schema '{}' (expected '{}')",
+                self.schema, JSONL_SCHEMA_NAME
+            ))
+        } else if self.version != JSONL_SCHEMA_VERSION {
+            Some(format!(
+                "JSONL schema version {} does not match supported version {}",
+                self.version, JSONL_SCHEMA_VERSION
+            ))
+        } else {
+            None
+        }
+    }
+}
 
 /// Default lifetime of a claim when no explicit duration is given (48 hours).
 ///
@@ -30,7 +90,7 @@ impl std::str::FromStr for ClaimDuration {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let s = s.trim();
         if s.is_empty() {
-            anyhow::bail!("Empty claim duration");
+            return Err(invalid_input("Empty claim duration"));
         }
 
         // Split into the leading number and an optional unit suffix.
@@ -40,30 +100,139 @@ impl std::str::FromStr for ClaimDuration {
         };
 
         let value: i64 = num_part.trim().parse().map_err(|_| {
-            anyhow::anyhow!(
+            invalid_input(format!(
                 "Invalid claim duration: '{}'. Use forms like '48h', '2d', '90m', or a bare number of hours.",
                 s
-            )
+            ))
         })?;
         if value <= 0 {
-            anyhow::bail!("Claim duration must be positive, got '{}'", s);
+            return Err(invalid_input(format!(
+                "Claim duration must be positive, got '{}'",
+                s
+            )));
         }
 
         let duration = match unit {
             'm' => Duration::minutes(value),
             'h' => Duration::hours(value),
             'd' => Duration::days(value),
-            other => anyhow::bail!(
+            other => return Err(invalid_input(format!(
                 "Invalid claim duration unit '{}' in '{}'. Valid units: m (minutes), h (hours), d (days).",
                 other,
                 s
-            ),
+            ))),
         };
 
         Ok(ClaimDuration(duration))
     }
 }
 
+/// A parsed issue ID of the form `<prefix>-<suffix>`, e.g. `minibeads-42` or
+/// `bd-a3f9`.
+///
+/// The split is always at the *last* hyphen, so prefixes may themselves contain
+/// hyphens (`my-project-7` has prefix `my-project`). Numeric suffixes come from
+/// sequential numbering; anything else is treated as a hash ID. Keeping these
+/// rules in one type stops every caller from re-deriving them with `rfind('-')`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IssueId {
+    id: String,
+    split: usize,
+}
+
+impl IssueId {
+    /// Parse `id`, requiring a non-empty prefix and suffix around the last hyphen.
+    pub fn parse(id: &str) -> anyhow::Result<Self> {
+        match id.rfind('-') {
+            Some(split) if split > 0 && split + 1 < id.len() => Ok(IssueId {
+                id: id.to_string(),
+                split,
+            }),
+            _ => Err(invalid_input(format!(
+                "Invalid issue ID: '{}'. Expected the form <prefix>-<suffix>, e.g. 'bd-42'",
+                id
+            ))),
+        }
+    }
+
+    /// Check that a user-supplied ID (`mb create --id`) is safe to use as a
+    /// filename: IDs become `<id>.md` directly, so path separators, `..`, a
+    /// leading dot, whitespace, and other filesystem-hostile characters are
+    /// rejected before anything touches the disk.
+    pub fn validate_explicit(id: &str) -> anyhow::Result<()> {
+        const FORBIDDEN: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+        if id.is_empty() {
+            return Err(invalid_input("Invalid issue ID: ID must not be empty"));
+        }
+        if let Some(c) = id.chars().find(|c| FORBIDDEN.contains(c)) {
+            return Err(invalid_input(format!(
+                "Invalid issue ID: '{}' contains '{}'. IDs are used as filenames and may not contain path separators or any of / \\ : * ? \" < > |",
+                id,
+                c
+            )));
+        }
+        if id.contains("..") {
+            return Err(invalid_input(format!(
+                "Invalid issue ID: '{}' must not contain '..'",
+                id
+            )));
+        }
+        if id.starts_with('.') {
+            return Err(invalid_input(format!(
+                "Invalid issue ID: '{}' must not start with '.'",
+                id
+            )));
+        }
+        if id.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(invalid_input(format!(
+                "Invalid issue ID: '{}' must not contain whitespace or control characters",
+                id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Everything before the last hyphen.
+    pub fn prefix(&self) -> &str {
+        &self.id[..self.split]
+    }
+
+    /// Everything after the last hyphen.
+    pub fn suffix(&self) -> &str {
+        &self.id[self.split + 1..]
+    }
+
+    /// The suffix as a sequential issue number, if it is all ASCII digits
+    /// (`u32::from_str` alone would also take a leading `+`).
+    pub fn number(&self) -> Option<u32> {
+        let suffix = self.suffix();
+        if !suffix.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        suffix.parse().ok()
+    }
+
+    /// Whether the suffix is a sequential number rather than a hash.
+    pub fn is_numeric(&self) -> bool {
+        self.number().is_some()
+    }
+}
+
+impl std::fmt::Display for IssueId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+impl std::str::FromStr for IssueId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        IssueId::parse(s)
+    }
+}
+
 /// Issue status
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -116,10 +285,207 @@ impl std::str::FromStr for Status {
             "staged_ready" => Ok(Status::StagedReady),
             "staged_warnings" => Ok(Status::StagedWarnings),
             "closed" => Ok(Status::Closed),
-            _ => Err(anyhow::anyhow!(
+            _ => Err(invalid_input(format!(
                 "Invalid status: '{}'. Valid values are: open, in_progress, blocked, deferred, hooked, pinned, staged_ready, staged_warnings, closed",
                 s
-            )),
+            ))),
+        }
+    }
+}
+
+/// A guarded status change: `mb start`, `mb block` and `mb unblock`.
+///
+/// Unlike a free-form `mb update --status`, each one only applies from the
+/// statuses the lifecycle allows; see [`Transition::target`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    /// Begin work: open or deferred -> in_progress
+    Start,
+    /// Mark as blocked: open or in_progress -> blocked
+    Block,
+    /// Clear a block: blocked -> in_progress if someone is assigned, else open
+    Unblock,
+}
+
+impl Transition {
+    /// The command name, as used in messages
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Transition::Start => "start",
+            Transition::Block => "block",
+            Transition::Unblock => "unblock",
+        }
+    }
+
+    /// The status an issue in `from` moves to, or `None` if the transition is
+    /// not allowed from there. `assigned` decides where `Unblock` returns to.
+    pub fn target(self, from: Status, assigned: bool) -> Option<Status> {
+        match (self, from) {
+            (Transition::Start, Status::Open | Status::Deferred) => Some(Status::InProgress),
+            (Transition::Block, Status::Open | Status::InProgress) => Some(Status::Blocked),
+            (Transition::Unblock, Status::Blocked) if assigned => Some(Status::InProgress),
+            (Transition::Unblock, Status::Blocked) => Some(Status::Open),
+            _ => None,
+        }
+    }
+}
+
+/// Why a closed issue was closed (`mb close --resolution`); the free-form
+/// `--reason` stays a human note
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Resolution {
+    Done,
+    Wontfix,
+    Duplicate,
+    Obsolete,
+}
+
+impl Resolution {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Resolution::Done => "done",
+            Resolution::Wontfix => "wontfix",
+            Resolution::Duplicate => "duplicate",
+            Resolution::Obsolete => "obsolete",
+        }
+    }
+}
+
+impl std::fmt::Display for Resolution {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for Resolution {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "done" => Ok(Resolution::Done),
+            "wontfix" => Ok(Resolution::Wontfix),
+            "duplicate" => Ok(Resolution::Duplicate),
+            "obsolete" => Ok(Resolution::Obsolete),
+            _ => Err(invalid_input(format!(
+                "Invalid resolution: '{}'. Valid values are: done, wontfix, duplicate, obsolete",
+                s
+            ))),
+        }
+    }
+}
+
+/// Readiness post-filter for `mb list --ready-only` / `--blocked-only`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Readiness {
+    /// Open with no unresolved blocking dependencies (what `mb ready` shows)
+    Ready,
+    /// Not closed, blocked by at least one issue that is not closed (what
+    /// `mb blocked` shows)
+    Blocked,
+}
+
+/// Sort key for `mb list --sort`. Ties, and the default `id` order, fall back
+/// to the usual list order: numeric IDs ascending, then hash IDs by creation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ListSort {
+    #[default]
+    Id,
+    /// Least recently updated first
+    Updated,
+}
+
+impl std::str::FromStr for ListSort {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "id" => Ok(ListSort::Id),
+            "updated" => Ok(ListSort::Updated),
+            _ => Err(invalid_input(format!(
+                "Invalid sort: '{}'. Valid values are: id, updated",
+                s
+            ))),
+        }
+    }
+}
+
+/// Why an issue is or is not ready to work on (`Storage::readiness`,
+/// `mb show --why`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ReadinessReport {
+    /// Open with no unresolved blocking dependencies: `mb ready` lists it
+    Ready,
+    /// Held up by these unresolved `blocks` dependencies, sorted by ID
+    Blocked {
+        by: Vec<Blocker>,
+    },
+    /// Nothing blocks it, but its status (in_progress, or blocked set by
+    /// hand) keeps it out of `mb ready`
+    NotOpen {
+        status: Status,
+    },
+    Closed,
+}
+
+/// An unresolved `blocks` dependency in a [`ReadinessReport`]. `status` is
+/// `None` when the target issue does not exist.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Blocker {
+    pub id: String,
+    pub status: Option<Status>,
+}
+
+/// Which edges `Storage::dependency_closure` follows from its root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClosureDirection {
+    /// Everything the root depends on, transitively (`mb export --closure`)
+    Dependencies,
+    /// Everything that depends on the root, transitively
+    Dependents,
+    /// Both of the above (`mb export --closure --include-dependents`)
+    Both,
+}
+
+/// Status of every known issue, used to decide whether a `blocks` edge still
+/// blocks. A closed (or archived) blocker no longer does. A target that is not in the index
+/// (deleted or never created) still does, unless `ignore_missing` is set.
+#[derive(Debug, Clone, Default)]
+pub struct BlockerStatuses {
+    statuses: HashMap<String, Status>,
+    ignore_missing: bool,
+}
+
+impl BlockerStatuses {
+    /// An empty index; `ignore_missing` treats unknown targets as resolved
+    pub fn new(ignore_missing: bool) -> Self {
+        Self {
+            statuses: HashMap::new(),
+            ignore_missing,
+        }
+    }
+
+    /// Record (or update) the status of one issue
+    pub fn insert(&mut self, issue: &Issue) {
+        self.statuses.insert(issue.id.clone(), issue.status);
+    }
+
+    /// Record an archived issue, which is closed without having to be parsed
+    pub fn insert_archived(&mut self, id: String) {
+        self.statuses.insert(id, Status::Closed);
+    }
+
+    /// The recorded status of `id`, `None` if it is not in the index
+    pub fn status(&self, id: &str) -> Option<Status> {
+        self.statuses.get(id).copied()
+    }
+
+    /// Whether a `blocks` dependency on `id` is still unresolved
+    pub fn is_blocking(&self, id: &str) -> bool {
+        match self.statuses.get(id) {
+            Some(status) => *status != Status::Closed,
+            None => !self.ignore_missing,
         }
     }
 }
@@ -204,10 +570,10 @@ impl std::str::FromStr for IssueType {
             "merge-request" => Ok(IssueType::MergeRequest),
             "workflow" => Ok(IssueType::Workflow),
             "patrol" => Ok(IssueType::Patrol),
-            _ => Err(anyhow::anyhow!(
+            _ => Err(invalid_input(format!(
                 "Invalid issue type: '{}'. Valid values are: bug, feature, task, epic, chore, agent, role, rig, convoy, slot, queue, event, message, molecule, gate, merge-request, workflow, patrol",
                 s
-            )),
+            ))),
         }
     }
 }
@@ -257,10 +623,10 @@ impl std::str::FromStr for EditField {
             "design" => Ok(EditField::Design),
             "notes" => Ok(EditField::Notes),
             "acceptance" | "acceptance_criteria" => Ok(EditField::Acceptance),
-            _ => Err(anyhow::anyhow!(
+            _ => Err(invalid_input(format!(
                 "Invalid field: '{}'. Valid values are: title, description, design, notes, acceptance",
                 s
-            )),
+            ))),
         }
     }
 }
@@ -305,10 +671,10 @@ impl std::str::FromStr for DependencyType {
             "parent-child" => Ok(DependencyType::ParentChild),
             "discovered-from" => Ok(DependencyType::DiscoveredFrom),
             "tracks" => Ok(DependencyType::Tracks),
-            _ => Err(anyhow::anyhow!(
+            _ => Err(invalid_input(format!(
                 "Invalid dependency type: '{}'. Valid values are: blocks, related, parent-child, discovered-from, tracks",
                 s
-            )),
+            ))),
         }
     }
 }
@@ -316,6 +682,8 @@ impl std::str::FromStr for DependencyType {
 /// Dependency representation for JSON output (MCP compatibility)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dependency {
+    /// Upstream bd's `depends_on_id` is accepted when reading
+    #[serde(alias = "depends_on_id")]
     pub id: String,
     #[serde(rename = "type")]
     pub dep_type: String,
@@ -430,6 +798,13 @@ pub struct Issue {
     /// unclaimed (or claimed via a plain `--assignee` with no expiry).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub claimed_until: Option<DateTime<Utc>>,
+    /// Users to notify about changes to this issue (minibeads-specific).
+    /// Nothing acts on it yet; it is data for notifier integrations.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watchers: Vec<String>,
+    /// How a closed issue was resolved (minibeads-specific). Cleared on reopen.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<Resolution>,
 }
 
 impl Issue {
@@ -455,6 +830,8 @@ impl Issue {
             closed_at: None,
             claimed_at: None,
             claimed_until: None,
+            watchers: Vec::new(),
+            resolution: None,
         }
     }
 
@@ -485,21 +862,108 @@ impl Issue {
         }
     }
 
-    /// Get dependencies of a specific type
+    /// Get the unresolved `blocks` dependencies: those whose target is not
+    /// closed according to `blockers`.
     /// Returns an iterator to avoid unnecessary allocations
-    pub fn get_blocking_dependencies(&self) -> impl Iterator<Item = &String> + '_ {
+    pub fn get_blocking_dependencies<'a>(
+        &'a self,
+        blockers: &'a BlockerStatuses,
+    ) -> impl Iterator<Item = &'a String> + 'a {
         self.depends_on
             .iter()
-            .filter(|(_, dep_type)| **dep_type == DependencyType::Blocks)
+            .filter(|(id, dep_type)| {
+                **dep_type == DependencyType::Blocks && blockers.is_blocking(id)
+            })
             .map(|(id, _)| id)
     }
 
-    /// Check if there are any blocking dependencies (zero-cost check)
-    pub fn has_blocking_dependencies(&self) -> bool {
+    /// Check if there are any unresolved blocking dependencies
+    pub fn has_blocking_dependencies(&self, blockers: &BlockerStatuses) -> bool {
+        self.get_blocking_dependencies(blockers).next().is_some()
+    }
+
+    /// Whether any dependency edge is of type `blocks`, resolved or not
+    pub fn has_blocks_edges(&self) -> bool {
         self.depends_on
             .values()
             .any(|dep_type| *dep_type == DependencyType::Blocks)
     }
+
+    /// SHA-256 (hex) of the issue's stable content.
+    ///
+    /// Covers every user-visible field except `updated_at` (bumped by any write,
+    /// even a no-op one) and `dependents` (derived from other issues). Labels and
+    /// dependencies are hashed in sorted order, so two copies of an issue hash
+    /// equal exactly when their content matches, regardless of timestamp churn.
+    /// Every field and list element is length-prefixed, so no two different
+    /// issues feed the hasher the same bytes.
+    pub fn content_hash(&self) -> String {
+        fn field(hasher: &mut Sha256, value: &str) {
+            hasher.update((value.len() as u64).to_le_bytes());
+            hasher.update(value.as_bytes());
+        }
+        fn list<'a>(hasher: &mut Sha256, values: impl Iterator<Item = &'a str>) {
+            let mut values: Vec<&str> = values.collect();
+            values.sort_unstable();
+            hasher.update((values.len() as u64).to_le_bytes());
+            for value in values {
+                field(hasher, value);
+            }
+        }
+        fn opt_time(hasher: &mut Sha256, t: Option<DateTime<Utc>>) {
+            field(hasher, &t.map(|t| t.to_rfc3339()).unwrap_or_default());
+        }
+
+        let mut hasher = Sha256::new();
+        field(&mut hasher, &self.id);
+        field(&mut hasher, &self.title);
+        field(&mut hasher, &self.description);
+        field(&mut hasher, &self.design);
+        field(&mut hasher, &self.notes);
+        field(&mut hasher, &self.acceptance_criteria);
+        field(&mut hasher, self.status.as_str());
+        hasher.update(self.priority.to_le_bytes());
+        field(&mut hasher, self.issue_type.as_str());
+        field(&mut hasher, &self.assignee);
+        field(
+            &mut hasher,
+            self.external_ref.as_deref().unwrap_or_default(),
+        );
+        list(&mut hasher, self.labels.iter().map(String::as_str));
+
+        let mut deps: Vec<(&str, DependencyType)> = self
+            .depends_on
+            .iter()
+            .map(|(id, dep_type)| (id.as_str(), *dep_type))
+            .collect();
+        deps.sort_unstable_by_key(|(id, _)| *id);
+        hasher.update((deps.len() as u64).to_le_bytes());
+        for (id, dep_type) in deps {
+            field(&mut hasher, id);
+            field(&mut hasher, dep_type.as_str());
+        }
+
+        field(&mut hasher, &self.created_at.to_rfc3339());
+        opt_time(&mut hasher, self.closed_at);
+        opt_time(&mut hasher, self.claimed_at);
+        opt_time(&mut hasher, self.claimed_until);
+        list(&mut hasher, self.watchers.iter().map(String::as_str));
+        field(&mut hasher, self.resolution.map_or("", |r| r.as_str()));
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Whether this issue matches a [`Readiness`] filter, given the statuses
+    /// of its potential blockers
+    pub fn matches_readiness(&self, readiness: Readiness, blockers: &BlockerStatuses) -> bool {
+        match readiness {
+            Readiness::Ready => {
+                self.status == Status::Open && !self.has_blocking_dependencies(blockers)
+            }
+            Readiness::Blocked => {
+                self.status != Status::Closed && self.has_blocking_dependencies(blockers)
+            }
+        }
+    }
 }
 
 /// Statistics structure
@@ -511,9 +975,48 @@ pub struct Stats {
     pub blocked_issues: usize,
     pub closed_issues: usize,
     pub ready_issues: usize,
+    /// Ready issues with no assignee: the pool an idle agent can claim from
+    #[serde(default)]
+    pub ready_unassigned_issues: usize,
+    /// Length of the longest hash-ID suffix in use; `None` when every ID is
+    /// numeric
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_hash_id_length: Option<usize>,
+    /// Closed issues per `--resolution`; those closed without one are not
+    /// counted
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub closed_by_resolution: BTreeMap<Resolution, usize>,
     pub average_lead_time_hours: f64,
 }
 
+/// Another issue mentioned by ID in an issue's text, resolved for `mb show`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueReference {
+    pub id: String,
+    pub title: String,
+    pub status: Status,
+}
+
+/// A commit touching an issue's file, from `git log` for `mb show --history`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub commit: String,
+    pub date: String,
+    pub author: String,
+    pub summary: String,
+}
+
+/// The line of an issue's frontmatter holding a field, and the commit that
+/// last changed it, for `mb blame`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldBlame {
+    pub field: String,
+    pub line: usize,
+    pub value: String,
+    /// `None` if the line has uncommitted changes
+    pub commit: Option<HistoryEntry>,
+}
+
 /// Blocked issue structure (for blocked command)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BlockedIssue {
@@ -521,6 +1024,13 @@ pub struct BlockedIssue {
     pub issue: Issue,
     pub blocked_by: Vec<String>,
     pub blocked_by_count: usize,
+    /// Approximately when the issue became blocked. Block start times are not
+    /// recorded, so this is the latest of the issue's own `created_at` and its
+    /// open blockers' `created_at`; an issue whose status is `blocked` uses its
+    /// `updated_at` instead, as the last write is when it was marked blocked.
+    /// `None` when no blocker is known (e.g. only missing blockers).
+    #[serde(default)]
+    pub blocked_since: Option<DateTime<Utc>>,
 }
 
 /// Tree node for dependency tree visualization
@@ -534,6 +1044,251 @@ pub struct TreeNode {
     pub children: Vec<TreeNode>,
     pub is_cycle: bool,
     pub depth_exceeded: bool,
+    /// Dependencies left out of `children` by the `--max-children` cap
+    #[serde(default)]
+    pub omitted_children: usize,
+}
+
+/// Dependency graph of a set of issues, rendered by `mb graph` as DOT or
+/// Mermaid. Both renderers read this one structure, so they always show the
+/// same nodes and edges.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DependencyGraph {
+    /// Issues in ID order
+    pub nodes: Vec<GraphNode>,
+    /// Edges whose two ends are both in `nodes`, in `from`/`to` order
+    pub edges: Vec<GraphEdge>,
+}
+
+/// An issue in a [`DependencyGraph`]
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphNode {
+    pub id: String,
+    pub title: String,
+    pub status: Status,
+}
+
+/// A dependency in a [`DependencyGraph`]. Edges point from the issue depended
+/// on to the issue that depends on it, so they follow the order work happens.
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    #[serde(rename = "type")]
+    pub dep_type: DependencyType,
+}
+
+impl DependencyGraph {
+    /// Graphviz `digraph`; closed issues are filled gray
+    pub fn to_dot(&self) -> String {
+        fn quote(s: &str) -> String {
+            format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+
+        let mut out = String::from("digraph dependencies {\n");
+        for node in &self.nodes {
+            let style = if node.status == Status::Closed {
+                ", style=filled, fillcolor=lightgray"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "  {} [label={}{}];\n",
+                quote(&node.id),
+                quote(&format!("{}: {}", node.id, node.title)),
+                style
+            ));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "  {} -> {} [label={}];\n",
+                quote(&edge.from),
+                quote(&edge.to),
+                quote(&edge.dep_type.to_string())
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Mermaid `flowchart TD` for pasting into GitHub/GitLab Markdown;
+    /// closed issues get the `closed` class
+    pub fn to_mermaid(&self) -> String {
+        // Mermaid node IDs cannot contain '-', which would read as an edge.
+        // Replacing punctuation with '_' can map two issue IDs (`a-1`, `a_1`)
+        // to one node, so later ones get a numeric suffix until unique.
+        let mut node_ids: HashMap<&str, String> = HashMap::new();
+        let mut taken = HashSet::new();
+        let issue_ids = self.nodes.iter().map(|node| node.id.as_str()).chain(
+            self.edges
+                .iter()
+                .flat_map(|edge| [edge.from.as_str(), edge.to.as_str()]),
+        );
+        for id in issue_ids {
+            if node_ids.contains_key(id) {
+                continue;
+            }
+            let base: String = id
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            let mut candidate = base.clone();
+            let mut suffix = 2;
+            while !taken.insert(candidate.clone()) {
+                candidate = format!("{base}_{suffix}");
+                suffix += 1;
+            }
+            node_ids.insert(id, candidate);
+        }
+        let node_id = |id: &str| node_ids[id].clone();
+
+        let mut out = String::from("flowchart TD\n");
+        for node in &self.nodes {
+            let label = format!("{}: {}", node.id, node.title).replace('"', "#quot;");
+            out.push_str(&format!("    {}[\"{}\"]\n", node_id(&node.id), label));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "    {} -->|{}| {}\n",
+                node_id(&edge.from),
+                edge.dep_type,
+                node_id(&edge.to)
+            ));
+        }
+        let closed: Vec<String> = self
+            .nodes
+            .iter()
+            .filter(|node| node.status == Status::Closed)
+            .map(|node| node_id(&node.id))
+            .collect();
+        if !closed.is_empty() {
+            out.push_str("    classDef closed fill:#eee,stroke:#999,color:#666\n");
+            out.push_str(&format!("    class {} closed\n", closed.join(",")));
+        }
+        out
+    }
+}
+
+/// Which JSONL schema `mb export` writes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonlFlavor {
+    /// Upstream bd's issue schema, safe to `bd import` (the default)
+    Upstream,
+    /// minibeads' own schema: `{id, type}` dependencies, `dependents`, claim
+    /// fields, watchers and resolution (`--mb-native`)
+    Native,
+}
+
+/// What `Storage::import_from_jsonl` does with an issue that already has a
+/// markdown file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportMode {
+    /// Keep the existing file (the default)
+    #[default]
+    Skip,
+    /// Replace the existing file with the imported issue
+    Overwrite,
+    /// Keep whichever is newer: the imported issue if its `updated_at` is
+    /// later than the file's mtime, else the file (a one-shot sync)
+    Merge,
+}
+
+impl std::str::FromStr for ImportMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(ImportMode::Skip),
+            "overwrite" => Ok(ImportMode::Overwrite),
+            "merge" => Ok(ImportMode::Merge),
+            _ => Err(invalid_input(format!(
+                "Invalid import mode: '{}'. Valid values are: skip, overwrite, merge",
+                s
+            ))),
+        }
+    }
+}
+
+/// An issue in upstream bd's JSONL schema, for `mb export`.
+///
+/// Upstream reads dependencies as `{issue_id, depends_on_id, type,
+/// created_at}` records and silently drops minibeads' `{id, type}` form, so
+/// exports meant for bd use this shape. Empty optional fields are omitted as
+/// upstream does, and minibeads-only fields (`dependents`, claims, watchers,
+/// resolution) are left out. minibeads reads this schema back as well.
+#[derive(Debug, Serialize)]
+pub struct UpstreamIssue<'a> {
+    pub id: &'a str,
+    pub title: &'a str,
+    pub description: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub design: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub acceptance_criteria: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub notes: &'a str,
+    pub status: Status,
+    pub priority: i32,
+    pub issue_type: IssueType,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub assignee: &'a str,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_ref: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    pub labels: &'a [String],
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<UpstreamDependency<'a>>,
+}
+
+/// A dependency edge in upstream bd's JSONL schema. minibeads does not
+/// record when an edge was added, so `created_at` is the dependent issue's
+/// creation time.
+#[derive(Debug, Serialize)]
+pub struct UpstreamDependency<'a> {
+    pub issue_id: &'a str,
+    pub depends_on_id: &'a str,
+    #[serde(rename = "type")]
+    pub dep_type: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl<'a> From<&'a Issue> for UpstreamIssue<'a> {
+    fn from(issue: &'a Issue) -> Self {
+        let mut dependencies: Vec<UpstreamDependency> = issue
+            .depends_on
+            .iter()
+            .map(|(id, dep_type)| UpstreamDependency {
+                issue_id: &issue.id,
+                depends_on_id: id,
+                dep_type: dep_type.to_string(),
+                created_at: issue.created_at,
+            })
+            .collect();
+        dependencies.sort_by(|a, b| a.depends_on_id.cmp(b.depends_on_id));
+
+        UpstreamIssue {
+            id: &issue.id,
+            title: &issue.title,
+            description: &issue.description,
+            design: &issue.design,
+            acceptance_criteria: &issue.acceptance_criteria,
+            notes: &issue.notes,
+            status: issue.status,
+            priority: issue.priority,
+            issue_type: issue.issue_type,
+            assignee: &issue.assignee,
+            created_at: issue.created_at,
+            updated_at: issue.updated_at,
+            closed_at: issue.closed_at,
+            external_ref: issue.external_ref.as_deref(),
+            labels: &issue.labels,
+            dependencies,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -600,3 +1355,162 @@ mod claim_type_tests {
         assert!(issue.is_actively_claimed(now));
     }
 }
+
+#[cfg(test)]
+mod jsonl_header_tests {
+    use super::*;
+
+    #[test]
+    fn issue_lines_are_not_headers() {
+        let line = r#"{"id":"demo-1","title":"t","status":"open","priority":2}"#;
+        assert_eq!(JsonlHeader::from_line(line), None);
+    }
+
+    #[test]
+    fn mismatch_reports_version_and_schema() {
+        assert_eq!(JsonlHeader::current().mismatch(), None);
+        let newer = JsonlHeader {
+            version: JSONL_SCHEMA_VERSION + 1,
+            ..JsonlHeader::current()
+        };
+        assert!(newer.mismatch().unwrap().contains("version"));
+        let foreign = JsonlHeader {
+            schema: "beads".to_string(),
+            ..JsonlHeader::current()
+        };
+        assert!(foreign.mismatch().unwrap().contains("beads"));
+    }
+}
+
+#[cfg(test)]
+mod issue_id_tests {
+    use super::*;
+
+    #[test]
+    fn splits_at_last_hyphen() {
+        let id = IssueId::parse("my-project-7").unwrap();
+        assert_eq!(id.prefix(), "my-project");
+        assert_eq!(id.suffix(), "7");
+        assert_eq!(id.number(), Some(7));
+        assert!(id.is_numeric());
+        assert_eq!(id.to_string(), "my-project-7");
+    }
+
+    #[test]
+    fn hash_suffix_is_not_numeric() {
+        let id = IssueId::parse("bd-a3f9").unwrap();
+        assert_eq!(id.prefix(), "bd");
+        assert_eq!(id.suffix(), "a3f9");
+        assert!(!id.is_numeric());
+    }
+
+    #[test]
+    fn signed_suffix_is_not_numeric() {
+        let id = IssueId::parse("bd-+3").unwrap();
+        assert_eq!(id.number(), None);
+        assert!(!id.is_numeric());
+    }
+
+    #[test]
+    fn explicit_ids_must_be_filename_safe() {
+        for ok in ["bd-1", "my-project-a3f9", "bd-1.2"] {
+            assert!(IssueId::validate_explicit(ok).is_ok(), "rejected '{ok}'");
+        }
+        for bad in [
+            "",
+            "../escape",
+            "a/b",
+            "a\\b",
+            "..",
+            ".hidden",
+            "bd 1",
+            "bd:1",
+        ] {
+            assert!(IssueId::validate_explicit(bad).is_err(), "accepted '{bad}'");
+        }
+    }
+
+    #[test]
+    fn rejects_missing_prefix_or_suffix() {
+        for bad in ["bd", "-42", "bd-", ""] {
+            assert!(IssueId::parse(bad).is_err(), "accepted '{bad}'");
+        }
+    }
+}
+
+#[cfg(test)]
+mod content_hash_tests {
+    use super::*;
+
+    #[test]
+    fn ignores_updated_at_and_dependents() {
+        let issue = Issue::new("bd-1".to_string(), "Title".to_string(), 2, IssueType::Task);
+        let mut touched = issue.clone();
+        touched.updated_at = issue.updated_at + Duration::hours(1);
+        touched.dependents.push(Dependency {
+            id: "bd-2".to_string(),
+            dep_type: "blocks".to_string(),
+        });
+        assert_eq!(issue.content_hash(), touched.content_hash());
+    }
+
+    #[test]
+    fn changes_with_content_but_not_label_order() {
+        let mut issue = Issue::new("bd-1".to_string(), "Title".to_string(), 2, IssueType::Task);
+        issue.labels = vec!["a".to_string(), "b".to_string()];
+        let mut reordered = issue.clone();
+        reordered.labels = vec!["b".to_string(), "a".to_string()];
+        assert_eq!(issue.content_hash(), reordered.content_hash());
+
+        let mut edited = issue.clone();
+        edited.description = "new body".to_string();
+        assert_ne!(issue.content_hash(), edited.content_hash());
+        assert_eq!(issue.content_hash().len(), 64);
+    }
+
+    #[test]
+    fn list_and_field_boundaries_are_unambiguous() {
+        let issue = Issue::new("bd-1".to_string(), "Title".to_string(), 2, IssueType::Task);
+        let mut split = issue.clone();
+        split.labels = vec!["a".to_string(), "b".to_string()];
+        let mut joined = issue.clone();
+        joined.labels = vec!["a,b".to_string()];
+        assert_ne!(split.content_hash(), joined.content_hash());
+
+        let mut split = issue.clone();
+        split.watchers = vec!["alice".to_string(), "bob".to_string()];
+        let mut joined = issue.clone();
+        joined.watchers = vec!["alice,bob".to_string()];
+        assert_ne!(split.content_hash(), joined.content_hash());
+    }
+}
+
+#[cfg(test)]
+mod mermaid_tests {
+    use super::*;
+
+    #[test]
+    fn ids_differing_only_in_punctuation_get_distinct_nodes() {
+        let node = |id: &str| GraphNode {
+            id: id.to_string(),
+            title: id.to_string(),
+            status: Status::Open,
+        };
+        let graph = DependencyGraph {
+            nodes: vec![node("a-1"), node("a_1"), node("a_1_2")],
+            edges: vec![GraphEdge {
+                from: "a-1".to_string(),
+                to: "a_1".to_string(),
+                dep_type: DependencyType::Blocks,
+            }],
+        };
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.contains("    a_1[\"a-1: a-1\"]\n"), "{mermaid}");
+        assert!(mermaid.contains("    a_1_2[\"a_1: a_1\"]\n"), "{mermaid}");
+        assert!(
+            mermaid.contains("    a_1_2_2[\"a_1_2: a_1_2\"]\n"),
+            "{mermaid}"
+        );
+        assert!(mermaid.contains("    a_1 -->|blocks| a_1_2\n"), "{mermaid}");
+    }
+}
@@ -68,7 +68,7 @@ assert_contains() {
     local needle="$2"
     local message="${3:-Assertion failed}"
 
-    if echo "$haystack" | grep -qF -- "$needle"; then
+    if grep -qF -- "$needle" <<< "$haystack"; then
         success "$message"
     else
         fail "$message (expected to find: '$needle' in output)"
@@ -112,7 +112,7 @@ cd "$TEST_DIR"
 
 # Test 1: Initialize beads database
 echo -e "\n${YELLOW}Test 1: Initialize database${NC}"
-OUTPUT=$("$BD_BIN" init --prefix test 2>&1)
+OUTPUT=$("$BD_BIN" init --here --prefix test 2>&1)
 assert_contains "$OUTPUT" "Initialized beads database with prefix: test" "Initialize should report prefix"
 assert_equals "true" "$([ -d .minibeads/issues ] && echo true || echo false)" "Issues directory should exist"
 assert_equals "true" "$([ -f .minibeads/config.yaml ] && echo true || echo false)" "Config file should exist"
@@ -121,7 +121,17 @@ assert_equals "true" "$([ -f .minibeads/config.yaml ] && echo true || echo false
 echo -e "\n${YELLOW}Test 2: Create an issue${NC}"
 OUTPUT=$("$BD_BIN" create "Test issue 1" -p 1 -t task -d "Test description" 2>&1)
 assert_contains "$OUTPUT" "Created issue: test-1" "Should create test-1"
+assert_contains "$OUTPUT" "Path: $(pwd -P)/.minibeads/issues/test-1.md" "Should print the absolute path of the new file"
 assert_equals "true" "$([ -f .minibeads/issues/test-1.md ] && echo true || echo false)" "Issue file should exist"
+assert_equals "$(pwd -P)/.minibeads/issues/test-1.md" "$("$BD_BIN" path 1 2>&1)" "bd path should print the issue file"
+OUTPUT=$("$BD_BIN" path test-99 2>&1 || true)
+assert_contains "$OUTPUT" "Issue not found: test-99" "bd path should fail for a missing issue"
+CODE=0
+"$BD_BIN" show test-99 >/dev/null 2>&1 || CODE=$?
+assert_equals "2" "$CODE" "show on a missing issue exits with the not-found code"
+CODE=0
+"$BD_BIN" update test-1 --status nope >/dev/null 2>&1 || CODE=$?
+assert_equals "4" "$CODE" "an invalid status exits with the invalid-input code"
 
 # Test 3: Create another issue with dependency
 echo -e "\n${YELLOW}Test 3: Create issue with dependency${NC}"
@@ -185,6 +195,7 @@ assert comment["updated_at"]
 assert comment["source_url"] == "https://example.com/source/comment-1"
 assert comment["source_id"] == "remote-comment-1"
 assert comment["body"] == "Test comment"
+assert len(issues[0]["content_hash"]) == 64, "show --json should include content_hash"
 print("ok")
 PY
 )
@@ -262,6 +273,14 @@ echo -e "\n${YELLOW}Test 13: Add dependency${NC}"
 "$BD_BIN" create "Test issue 3" -p 1 -t task >/dev/null 2>&1
 OUTPUT=$("$BD_BIN" dep add test-3 test-2 --type related 2>&1)
 assert_contains "$OUTPUT" "Added dependency: test-3 depends on test-2" "Should add dependency"
+OUTPUT=$("$BD_BIN" dep add test-3 test-2 --type related 2>&1)
+assert_contains "$OUTPUT" "Dependency already exists: test-3 depends on test-2" "Re-adding the same edge is a no-op"
+OUTPUT=$("$BD_BIN" dep add test-3 test-2 --type blocks 2>&1 || true)
+assert_contains "$OUTPUT" "already has dependency on test-2 (related)" "Changing the type needs --force"
+OUTPUT=$("$BD_BIN" dep add test-3 test-2 --type related --bidirectional 2>&1)
+assert_contains "$OUTPUT" "Added dependency: test-2 depends on test-3 (related)" "--bidirectional adds the reverse related edge"
+OUTPUT=$("$BD_BIN" dep add test-3 test-1 --bidirectional 2>&1 || true)
+assert_contains "$OUTPUT" "--bidirectional only applies to related dependencies" "--bidirectional rejects directional types"
 
 # Test 14: Numeric shorthand for bd show
 echo -e "\n${YELLOW}Test 14: Numeric shorthand for bd show${NC}"
@@ -277,6 +296,11 @@ assert_contains "$OUTPUT" "ID: test-2" "Should show test-2"
 assert_contains "$OUTPUT" "ID: test-3" "Should show test-3"
 # Check for separator between issues
 assert_contains "$OUTPUT" "======" "Should have separator between issues"
+OUTPUT=$("$BD_BIN" show 1 --markdown 2>&1)
+assert_equals "$(cat .minibeads/issues/test-1.md)" "$OUTPUT" "show --markdown prints the stored file verbatim"
+OUTPUT=$("$BD_BIN" show 1 3 --markdown --normalized 2>&1)
+assert_contains "$OUTPUT" "title: Test issue 3" "show --markdown --normalized renders every issue"
+assert_contains "$OUTPUT" "======" "show --markdown separates multiple issues"
 
 # Test 16: Mixed format for bd show (full ID + shorthand)
 echo -e "\n${YELLOW}Test 16: Mixed format for bd show${NC}"
@@ -284,6 +308,68 @@ OUTPUT=$("$BD_BIN" show test-1 2 2>&1)
 assert_contains "$OUTPUT" "ID: test-1" "Should show test-1"
 assert_contains "$OUTPUT" "ID: test-2" "Should show test-2 from shorthand"
 
+# Test 16b: Create with body from stdin
+echo -e "\n${YELLOW}Test 16b: Create with --stdin body${NC}"
+OUTPUT=$("$BD_BIN" create "Piped body" --json --stdin 2>create_stderr.txt <<'BODY'
+Intro line
+
+# Design
+Use a heredoc
+```sh
+# not a header
+```
+
+# Notes
+Piped notes
+BODY
+)
+STDIN_ID=$(echo "$OUTPUT" | python3 -c 'import json,sys; print(json.load(sys.stdin)["id"])')
+assert_equals "Created issue: $STDIN_ID" "$(cat create_stderr.txt)" "create --json should report the ID on stderr"
+STDIN_PATH=$(echo "$OUTPUT" | python3 -c 'import json,sys; print(json.load(sys.stdin)["path"])')
+assert_equals "$(pwd -P)/.minibeads/issues/$STDIN_ID.md" "$STDIN_PATH" "create --json should include the file path"
+STDIN_RESULT=$("$BD_BIN" show "$STDIN_ID" --json 2>&1 | python3 -c '
+import json, sys
+issue = json.load(sys.stdin)[0]
+assert issue["description"] == "Intro line", issue["description"]
+assert issue["design"] == "Use a heredoc\n```sh\n# not a header\n```", issue["design"]
+assert issue["notes"] == "Piped notes", issue["notes"]
+print("ok")
+')
+assert_equals "ok" "$STDIN_RESULT" "create --stdin should route the body through the section parser"
+
+# Test 16b2: Typed dependencies with repeated --dep
+echo -e "\n${YELLOW}Test 16b2: create --dep ID:TYPE${NC}"
+OUTPUT=$("$BD_BIN" create "Typed deps" --dep test-1 --dep test-2:related --deps test-3 2>&1)
+DEP_ID=$(echo "$OUTPUT" | sed -n 's/^Created issue: //p')
+DEP_FILE=".minibeads/issues/$DEP_ID.md"
+assert_contains "$(cat "$DEP_FILE")" "test-1: blocks" "--dep without a type should default to blocks"
+assert_contains "$(cat "$DEP_FILE")" "test-2: related" "--dep ID:TYPE should record the type"
+assert_contains "$(cat "$DEP_FILE")" "test-3: blocks" "--deps should still work alongside --dep"
+assert_fails "--dep with an unknown type should fail" "$BD_BIN" create "Bad dep" --dep test-1:bogus
+
+# Test 16c: Table list and one-line show
+echo -e "\n${YELLOW}Test 16c: list --table and show --oneline${NC}"
+OUTPUT=$("$BD_BIN" list --table 2>&1)
+assert_equals "ID" "$(echo "$OUTPUT" | head -1 | awk '{print $1}')" "Table should start with a header row"
+assert_contains "$(echo "$OUTPUT" | head -1)" "STATUS  PRI" "Table header should list the columns"
+assert_contains "$OUTPUT" "P2   bug" "Table rows should show priority and type"
+OUTPUT=$("$BD_BIN" show test-1 --oneline 2>&1)
+assert_contains "$OUTPUT" "test-1: " "show --oneline should print a summary line"
+assert_equals "1" "$(echo "$OUTPUT" | wc -l | tr -d ' ')" "show --oneline should print one line per issue"
+
+# Test 16d: doctor reports duplicate titles
+echo -e "\n${YELLOW}Test 16d: doctor${NC}"
+OUTPUT=$("$BD_BIN" doctor 2>&1)
+assert_contains "$OUTPUT" "✓ No duplicate titles" "doctor should pass without duplicates"
+TWIN_A=$("$BD_BIN" create "Doctor twin" 2>&1 | sed -n 's/^Created issue: //p')
+TWIN_B=$("$BD_BIN" create "  doctor TWIN" 2>&1 | sed -n 's/^Created issue: //p')
+OUTPUT=$("$BD_BIN" doctor 2>&1)
+assert_contains "$OUTPUT" "✗ 1 group(s) of issues with the same title" "doctor should flag duplicate titles"
+assert_contains "$OUTPUT" "$TWIN_A" "doctor should list the first duplicate"
+assert_contains "$OUTPUT" "$TWIN_B" "doctor should list the second duplicate"
+OUTPUT=$("$BD_BIN" --json doctor 2>&1)
+assert_equals "2" "$(echo "$OUTPUT" | jq '.duplicate_titles[0] | length')" "doctor --json should group IDs"
+
 # Test 17: Legacy .beads fallback
 echo -e "\n${YELLOW}Test 17: Legacy .beads fallback${NC}"
 LEGACY_DIR=$(mktemp -d "${TMPDIR:-/tmp}/mb_legacy_fallback_XXXXXX")
@@ -296,6 +382,18 @@ assert_equals "true" "$([ -f .beads/issues/legacy-1.md ] && echo true || echo fa
 rm -rf "$LEGACY_DIR"
 cd "$TEST_DIR"
 
+# Test 18: init refuses to nest inside an existing database
+echo -e "\n${YELLOW}Test 18: Nested init needs --here${NC}"
+mkdir -p nested/deeper
+cd nested/deeper
+OUTPUT=$("$BD_BIN" init --prefix nested 2>&1 || true)
+assert_contains "$OUTPUT" "already exists in a parent directory: $TEST_DIR/.minibeads" "init should name the ancestor database"
+assert_equals "false" "$([ -d .minibeads ] && echo true || echo false)" "init should not create a nested database"
+OUTPUT=$("$BD_BIN" init --here --prefix nested 2>&1)
+assert_contains "$OUTPUT" "Initialized beads database with prefix: nested" "init --here should proceed"
+cd "$TEST_DIR"
+rm -rf nested
+
 # Print summary
 echo ""
 echo "=========================================="
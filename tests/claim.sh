@@ -60,7 +60,7 @@ assert_contains() {
     local haystack="$1"
     local needle="$2"
     local message="${3:-Assertion failed}"
-    if echo "$haystack" | grep -qF -- "$needle"; then
+    if grep -qF -- "$needle" <<< "$haystack"; then
         success "$message"
     else
         fail "$message (expected to find: '$needle' in output)"
@@ -100,7 +100,7 @@ mkdir -p "$TEST_DIR"
 cd "$TEST_DIR"
 
 # Setup: a repo with two open issues
-"$BD_BIN" init --prefix test >/dev/null 2>&1
+"$BD_BIN" init --here --prefix test >/dev/null 2>&1
 "$BD_BIN" create "First task" -p 1 -t task >/dev/null 2>&1
 "$BD_BIN" create "Second task" -p 2 -t task >/dev/null 2>&1
 
@@ -161,6 +161,14 @@ rm .minibeads/issues/test-1.md
 AFTER=$(cat .minibeads/issues/test-1.md)
 assert_equals "$BEFORE" "$AFTER" "Claimed issue should round-trip identically through JSONL"
 
+# Test 8: exporting the mirror does not make the next sync see conflicts
+echo -e "\n${YELLOW}Test 8: Plain export + sync keeps the claim${NC}"
+"$BD_BIN" export --mb-output-default >/dev/null 2>&1
+OUTPUT=$("$BD_BIN" sync 2>&1)
+assert_equals "false" "$(grep -q 'Conflict' <<<"$OUTPUT" && echo true || echo false)" "Sync after exporting the mirror should find no conflicts"
+"$BD_BIN" sync --prefer jsonl >/dev/null 2>&1
+assert_equals "$BEFORE" "$(cat .minibeads/issues/test-1.md)" "Claim should survive export + sync --prefer jsonl"
+
 # Print summary
 echo ""
 echo "=========================================="
@@ -86,7 +86,7 @@ assert_contains() {
     local needle="$2"
     local message="${3:-Assertion failed}"
 
-    if echo "$haystack" | grep -qF -- "$needle"; then
+    if grep -qF -- "$needle" <<< "$haystack"; then
         success "$message"
     else
         fail "$message (expected to find: '$needle' in output)"
@@ -94,6 +94,21 @@ assert_contains() {
     fi
 }
 
+# Assert not contains
+assert_not_contains() {
+    TESTS_RUN=$((TESTS_RUN + 1))
+    local haystack="$1"
+    local needle="$2"
+    local message="${3:-Assertion failed}"
+
+    if grep -qF -- "$needle" <<< "$haystack"; then
+        fail "$message (unexpectedly found: '$needle' in output)"
+        return 1
+    else
+        success "$message"
+    fi
+}
+
 # Assert file exists
 assert_file_exists() {
     TESTS_RUN=$((TESTS_RUN + 1))
@@ -152,7 +167,7 @@ cd "$TEST_DIR"
 
 # Test 1: Initialize minibeads database
 echo -e "\n${YELLOW}Test 1: Initialize minibeads database${NC}"
-OUTPUT=$("$BD_BIN" init --prefix exp 2>&1)
+OUTPUT=$("$BD_BIN" init --here --prefix exp 2>&1)
 assert_contains "$OUTPUT" "Initialized beads database with prefix: exp" "Initialize should report prefix"
 
 # Test 2: Create test issues with various types and states
@@ -220,15 +235,33 @@ assert_contains "$FIRST_ISSUE" '"issue_type":' "Issue should have issue_type fie
 assert_contains "$FIRST_ISSUE" '"created_at":' "Issue should have created_at field"
 assert_contains "$FIRST_ISSUE" '"updated_at":' "Issue should have updated_at field"
 
-# Test 7: Verify dependencies/dependents format (MCP compatible)
-echo -e "\n${YELLOW}Test 7: Verify dependencies/dependents format${NC}"
+# Test 7: Verify dependencies use upstream bd's schema by default
+echo -e "\n${YELLOW}Test 7: Verify upstream dependency format${NC}"
 # exp-3 depends on exp-1
-EXP3_JSON=$(grep '"id":"exp-3"' "$JSONL_FILE")
+UPSTREAM_EXPORT=$("$BD_BIN" export 2>/dev/null)
+EXP3_JSON=$(grep '"id":"exp-3"' <<< "$UPSTREAM_EXPORT")
+assert_contains "$EXP3_JSON" '"dependencies":[' "Should have dependencies array"
+assert_contains "$EXP3_JSON" '"issue_id":"exp-3","depends_on_id":"exp-1","type":"blocks"' "Should have upstream exp-1 dependency"
+EXP1_JSON=$(grep '"id":"exp-1"' <<< "$UPSTREAM_EXPORT")
+TESTS_RUN=$((TESTS_RUN + 1))
+if ! grep -qF '"dependents"' <<< "$EXP1_JSON"; then
+    success "Upstream export should omit dependents"
+else
+    fail "Upstream export should omit dependents"
+fi
+# The sync mirror keeps minibeads' own schema
+EXP1_JSON=$(grep '"id":"exp-1"' "$JSONL_FILE")
+assert_contains "$EXP1_JSON" '"dependents":[' "--mb-output-default should write the native schema"
+
+# Test 7b: Verify dependencies/dependents format with --mb-native (MCP compatible)
+echo -e "\n${YELLOW}Test 7b: Verify --mb-native dependencies/dependents format${NC}"
+NATIVE_EXPORT=$("$BD_BIN" export --mb-native 2>/dev/null)
+EXP3_JSON=$(grep '"id":"exp-3"' <<< "$NATIVE_EXPORT")
 assert_contains "$EXP3_JSON" '"dependencies":[' "Should have dependencies array"
 assert_contains "$EXP3_JSON" '"id":"exp-1"' "Should have exp-1 dependency"
 
 # exp-1 should have exp-3 as dependent
-EXP1_JSON=$(grep '"id":"exp-1"' "$JSONL_FILE")
+EXP1_JSON=$(grep '"id":"exp-1"' <<< "$NATIVE_EXPORT")
 assert_contains "$EXP1_JSON" '"dependents":[' "Should have dependents array"
 assert_contains "$EXP1_JSON" '"id":"exp-3"' "Should have exp-3 as dependent"
 
@@ -243,10 +276,81 @@ CLOSED_EXPORT=$("$BD_BIN" export --status closed 2>/dev/null)
 CLOSED_COUNT=$(echo "$CLOSED_EXPORT" | wc -l | tr -d ' ')
 assert_equals "1" "$CLOSED_COUNT" "Should export 1 closed issue (exp-1)"
 
+# Export accepts the same filters as list
+PRIORITY_EXPORT=$("$BD_BIN" export -p 0,1 2>/dev/null)
+PRIORITY_COUNT=$(echo "$PRIORITY_EXPORT" | wc -l | tr -d ' ')
+assert_equals "2" "$PRIORITY_COUNT" "Should export 2 issues with priority 0 or 1"
+READY_EXPORT=$("$BD_BIN" export --ready-only 2>/dev/null)
+assert_equals "exp-3" "$(echo "$READY_EXPORT" | jq -r '.id')" "Should export only ready exp-3"
+TITLE_EXPORT=$("$BD_BIN" export --title feature 2>/dev/null)
+assert_equals "exp-2" "$(echo "$TITLE_EXPORT" | jq -r '.id')" "Should export only exp-2 by title"
+
+# Test 8b: Export with schema header
+echo -e "\n${YELLOW}Test 8b: Export with --with-header${NC}"
+HEADER_EXPORT=$("$BD_BIN" export --mb-native --with-header 2>/dev/null)
+HEADER_LINE=$(echo "$HEADER_EXPORT" | head -n 1)
+assert_equals '{"_schema":"minibeads-jsonl","version":1}' "$HEADER_LINE" "First line should be the schema header"
+HEADER_COUNT=$(echo "$HEADER_EXPORT" | wc -l | tr -d ' ')
+assert_equals "4" "$HEADER_COUNT" "Header export should have header plus 3 issues"
+"$BD_BIN" export --mb-output-default --with-header --no-dependents >/dev/null 2>&1
+assert_equals '{"_schema":"minibeads-jsonl","version":1}' "$(head -n 1 "$JSONL_FILE")" "--with-header is accepted with --mb-output-default"
+"$BD_BIN" export --mb-output-default >/dev/null 2>&1
+
+# Test 8c: Export as a pretty JSON array
+echo -e "\n${YELLOW}Test 8c: Export with --format json${NC}"
+JSON_EXPORT=$("$BD_BIN" export --format json 2>/dev/null)
+JSON_COUNT=$(echo "$JSON_EXPORT" | jq 'length')
+assert_equals "3" "$JSON_COUNT" "JSON array export should contain 3 issues"
+assert_contains "$JSON_EXPORT" '  {' "JSON array export should be pretty-printed"
+
+# Test 8c2: Streaming NDJSON from export and list
+echo -e "\n${YELLOW}Test 8c2: Export and list with --format ndjson-stream${NC}"
+STREAM_EXPORT=$("$BD_BIN" export --format ndjson-stream 2>/dev/null)
+assert_equals "$("$BD_BIN" export 2>/dev/null)" "$STREAM_EXPORT" "Streamed export should match the JSONL export"
+STREAM_LIST=$("$BD_BIN" list --format ndjson-stream 2>/dev/null)
+STREAM_COUNT=$(echo "$STREAM_LIST" | jq -c '.id' | wc -l | tr -d ' ')
+assert_equals "3" "$STREAM_COUNT" "Streamed list should print one JSON object per issue"
+OUTPUT=$("$BD_BIN" export --format ndjson-stream -o "$TEST_DIR/stream.jsonl" 2>&1 || true)
+assert_contains "$OUTPUT" "writes to stdout" "ndjson-stream should refuse -o"
+
+# Test 8d: Diff one issue against the JSONL mirror
+echo -e "\n${YELLOW}Test 8d: Diff markdown against JSONL${NC}"
+OUTPUT=$("$BD_BIN" diff exp-2 2>&1)
+assert_contains "$OUTPUT" "No differences" "Freshly exported issue should match JSONL"
+"$BD_BIN" update exp-2 --title "Feature B renamed" >/dev/null 2>&1
+DIFF_JSON=$("$BD_BIN" diff exp-2 --json 2>&1)
+DIFF_TITLE=$(echo "$DIFF_JSON" | jq -r '.title.jsonl')
+assert_equals "Feature B" "$DIFF_TITLE" "Diff should report the JSONL title"
+DIFF_MD_TITLE=$(echo "$DIFF_JSON" | jq -r '.title.markdown')
+assert_equals "Feature B renamed" "$DIFF_MD_TITLE" "Diff should report the markdown title"
+
+# Test 8e: Watchers survive the mirror round-trip
+echo -e "\n${YELLOW}Test 8e: Watchers survive export + sync${NC}"
+"$BD_BIN" watch exp-3 alice >/dev/null 2>&1
+"$BD_BIN" export --mb-output-default >/dev/null 2>&1
+OUTPUT=$("$BD_BIN" sync 2>&1)
+assert_not_contains "$OUTPUT" "Conflict" "Sync after exporting watchers should find no conflicts"
+"$BD_BIN" sync --prefer jsonl >/dev/null 2>&1
+assert_contains "$("$BD_BIN" show exp-3 2>&1)" "Watchers: alice" "Watchers should survive export + sync --prefer jsonl"
+
+# Test 8f: A close resolution survives the mirror round-trip
+echo -e "\n${YELLOW}Test 8f: Resolution survives export + sync${NC}"
+"$BD_BIN" reopen exp-1 >/dev/null 2>&1
+"$BD_BIN" close exp-1 --resolution wontfix >/dev/null 2>&1
+"$BD_BIN" export --mb-output-default >/dev/null 2>&1
+OUTPUT=$("$BD_BIN" sync 2>&1)
+assert_not_contains "$OUTPUT" "Conflict" "Sync after exporting a resolution should find no conflicts"
+"$BD_BIN" sync --prefer jsonl >/dev/null 2>&1
+assert_contains "$(cat .minibeads/issues/exp-1.md)" "resolution: wontfix" "Resolution should survive export + sync --prefer jsonl"
+
 # Test 9: Upstream bd interoperability (if available)
 if [ -n "$UPSTREAM_BD" ]; then
     echo -e "\n${YELLOW}Test 9: Upstream bd interoperability${NC}"
 
+    # The mirror is in minibeads' schema; upstream bd gets a plain export
+    UPSTREAM_JSONL="$TEST_DIR/upstream_export.jsonl"
+    "$BD_BIN" export -o "$UPSTREAM_JSONL" >/dev/null 2>&1
+
     # Create a clean directory for upstream bd testing
     UPSTREAM_DIR="$TEST_DIR/upstream_test"
     echo -e "${BLUE}→ mkdir -p $UPSTREAM_DIR${NC}"
@@ -259,8 +363,8 @@ if [ -n "$UPSTREAM_BD" ]; then
     echo -e "${BLUE}  (init completed)${NC}"
 
     # Copy exported issues.jsonl to .beads directory
-    echo -e "${BLUE}→ cp $JSONL_FILE .beads/issues.jsonl${NC}"
-    cp "$JSONL_FILE" .beads/issues.jsonl
+    echo -e "${BLUE}→ cp $UPSTREAM_JSONL .beads/issues.jsonl${NC}"
+    cp "$UPSTREAM_JSONL" .beads/issues.jsonl
 
     # Import the JSONL into the database (required since bd init starts a daemon)
     echo -e "${BLUE}→ $UPSTREAM_BD import -i .beads/issues.jsonl${NC}"
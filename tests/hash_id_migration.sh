@@ -68,7 +68,7 @@ assert_contains() {
     local needle="$2"
     local message="${3:-Assertion failed}"
 
-    if echo "$haystack" | grep -qF -- "$needle"; then
+    if grep -qF -- "$needle" <<< "$haystack"; then
         success "$message"
     else
         fail "$message (expected to find: '$needle' in output)"
@@ -127,7 +127,7 @@ cd "$TEST_DIR"
 
 # Test 1: Initialize database with numeric IDs
 echo -e "\n${YELLOW}Test 1: Initialize database (default: numeric IDs)${NC}"
-OUTPUT=$("$BD_BIN" init --prefix test 2>&1)
+OUTPUT=$("$BD_BIN" init --here --prefix test 2>&1)
 assert_contains "$OUTPUT" "Initialized beads database with prefix: test" "Initialize should succeed"
 # Check config-minibeads.yaml has mb-hash-ids: false
 CONFIG_VALUE=$(grep "mb-hash-ids:" .minibeads/config-minibeads.yaml | awk '{print $2}' | tr -d "'\"")
@@ -38,7 +38,7 @@ assert_contains() {
     local needle="$2"
     local message="${3:-Assertion failed}"
 
-    if echo "$haystack" | grep -qF -- "$needle"; then
+    if grep -qF -- "$needle" <<< "$haystack"; then
         success "$message"
     else
         fail "$message (expected to find: '$needle' in output)"
@@ -105,6 +105,13 @@ OUTPUT=$("$BD_BIN" version 2>&1)
 assert_contains "$OUTPUT" "mb version" "Version should show version string"
 assert_contains "$OUTPUT" "0." "Version should show version number"
 
+# Test 7b: bd version --json
+echo -e "\n${YELLOW}Test 7b: bd version --json${NC}"
+OUTPUT=$("$BD_BIN" version --json 2>&1)
+assert_contains "$OUTPUT" '"version": "0.' "Version JSON should include version"
+assert_contains "$OUTPUT" '"build_date"' "Version JSON should include build date"
+assert_contains "$OUTPUT" '"jsonl_schema_version": 1' "Version JSON should include JSONL schema version"
+
 # Test 8: bd --version
 echo -e "\n${YELLOW}Test 8: bd --version${NC}"
 OUTPUT=$("$BD_BIN" --version 2>&1)
@@ -48,7 +48,7 @@ assert_contains() {
     local needle="$2"
     local message="${3:-Assertion failed}"
 
-    if echo "$haystack" | grep -qF -- "$needle"; then
+    if grep -qF -- "$needle" <<< "$haystack"; then
         success "$message"
     else
         fail "$message (expected to find: '$needle' in output)"
@@ -62,7 +62,7 @@ assert_not_contains() {
     local needle="$2"
     local message="${3:-Assertion failed}"
 
-    if echo "$haystack" | grep -qF -- "$needle"; then
+    if grep -qF -- "$needle" <<< "$haystack"; then
         fail "$message (unexpectedly found: '$needle' in output)"
         return 1
     else
@@ -88,7 +88,7 @@ echo "Creating test directory: $TEST_DIR"
 mkdir -p "$TEST_DIR"
 cd "$TEST_DIR"
 
-"$BD_BIN" init --prefix test >/dev/null 2>&1
+"$BD_BIN" init --here --prefix test >/dev/null 2>&1
 
 # Fixture issues covering the various filter dimensions.
 "$BD_BIN" create "Fix login bug" -t bug -p 1 -l backend -l urgent >/dev/null
@@ -106,6 +106,13 @@ OUTPUT=$("$BD_BIN" ready 2>&1)
 assert_contains "$OUTPUT" "test-1: Fix login bug" "test-1 is ready"
 assert_not_contains "$OUTPUT" "test-3: Write docs" "test-3 is blocked, not ready"
 
+echo -e "\n${YELLOW}critical-path${NC}"
+OUTPUT=$("$BD_BIN" critical-path test-3 2>&1)
+assert_contains "$OUTPUT" "Critical path to test-3 (2 issues)" "critical-path reports the chain length"
+assert_contains "$OUTPUT" "1. test-1: Fix login bug" "critical-path lists the blocker first"
+OUTPUT=$("$BD_BIN" critical-path test-3 --json 2>&1)
+assert_contains "$OUTPUT" '"length": 2' "critical-path --json includes the length"
+
 # --label (must have ALL specified labels).
 echo -e "\n${YELLOW}--label filter${NC}"
 OUTPUT=$("$BD_BIN" ready --label backend 2>&1)
@@ -208,6 +215,119 @@ OUTPUT=$("$BD_BIN" ready --label backend --priority 1 2>&1)
 assert_contains "$OUTPUT" "test-1: Fix login bug" "combined: backend + p1 includes test-1"
 assert_contains "$OUTPUT" "test-4: Refactor auth" "combined: backend + p1 includes test-4"
 
+# list --ready-only / --blocked-only compose with list's other filters.
+echo -e "\n${YELLOW}list --ready-only / --blocked-only${NC}"
+OUTPUT=$("$BD_BIN" list --ready-only 2>&1)
+assert_contains "$OUTPUT" "test-1: Fix login bug" "ready-only includes test-1"
+assert_not_contains "$OUTPUT" "test-3: Write docs" "ready-only excludes blocked test-3"
+OUTPUT=$("$BD_BIN" list --ready-only --label backend 2>&1)
+assert_contains "$OUTPUT" "test-4: Refactor auth" "ready-only + label includes test-4"
+assert_not_contains "$OUTPUT" "test-2: Add dark mode" "ready-only + label excludes frontend issue"
+OUTPUT=$("$BD_BIN" list --blocked-only 2>&1)
+assert_contains "$OUTPUT" "test-3: Write docs" "blocked-only includes test-3"
+assert_not_contains "$OUTPUT" "test-1: Fix login bug" "blocked-only excludes ready test-1"
+TESTS_RUN=$((TESTS_RUN + 1))
+if "$BD_BIN" list --ready-only --blocked-only >/dev/null 2>&1; then
+    fail "--ready-only and --blocked-only should conflict"
+else
+    success "--ready-only and --blocked-only conflict"
+fi
+
+# --unassigned keeps only issues nobody has picked up.
+echo -e "\n${YELLOW}--unassigned filter${NC}"
+"$BD_BIN" update test-2 --assignee alice >/dev/null
+OUTPUT=$("$BD_BIN" ready --unassigned 2>&1)
+assert_contains "$OUTPUT" "test-1: Fix login bug" "ready --unassigned includes unassigned test-1"
+assert_not_contains "$OUTPUT" "test-2: Add dark mode" "ready --unassigned excludes assigned test-2"
+OUTPUT=$("$BD_BIN" list --unassigned 2>&1)
+assert_contains "$OUTPUT" "test-3: Write docs" "list --unassigned includes unassigned test-3"
+assert_not_contains "$OUTPUT" "test-2: Add dark mode" "list --unassigned excludes assigned test-2"
+"$BD_BIN" update test-2 --unassign >/dev/null
+
+# --ids-only prints bare IDs for piping (a string array with --json).
+echo -e "\n${YELLOW}--ids-only output${NC}"
+TESTS_RUN=$((TESTS_RUN + 1))
+OUTPUT=$("$BD_BIN" ready --label backend --sort oldest --ids-only 2>&1)
+if [ "$OUTPUT" = "$(printf 'test-1\ntest-4')" ]; then
+    success "ready --ids-only prints one bare ID per line"
+else
+    fail "ready --ids-only should print bare IDs (got: $OUTPUT)"
+fi
+TESTS_RUN=$((TESTS_RUN + 1))
+OUTPUT=$("$BD_BIN" blocked --ids-only 2>&1)
+if [ "$OUTPUT" = "test-3" ]; then
+    success "blocked --ids-only prints only the blocked ID"
+else
+    fail "blocked --ids-only should print test-3 (got: $OUTPUT)"
+fi
+OUTPUT=$("$BD_BIN" list --blocked-only --ids-only --json 2>&1)
+assert_contains "$(echo "$OUTPUT" | tr -d ' \n')" '["test-3"]' "list --ids-only --json prints a string array"
+
+echo -e "\n${YELLOW}Closing a blocker unblocks its dependents${NC}"
+"$BD_BIN" close test-1 >/dev/null
+OUTPUT=$("$BD_BIN" ready 2>&1)
+assert_contains "$OUTPUT" "test-3: Write docs" "test-3 is ready once test-1 is closed"
+OUTPUT=$("$BD_BIN" blocked 2>&1)
+assert_not_contains "$OUTPUT" "test-3" "test-3 is no longer blocked"
+"$BD_BIN" reopen test-1 >/dev/null
+OUTPUT=$("$BD_BIN" ready 2>&1)
+assert_not_contains "$OUTPUT" "test-3: Write docs" "reopening test-1 blocks test-3 again"
+
+echo -e "\n${YELLOW}--ignore-missing-blockers${NC}"
+"$BD_BIN" dep add test-2 test-404 -t blocks >/dev/null 2>&1
+OUTPUT=$("$BD_BIN" ready 2>&1)
+assert_not_contains "$OUTPUT" "test-2: Add dark mode" "a dangling blocker keeps test-2 out of ready"
+OUTPUT=$("$BD_BIN" ready --ignore-missing-blockers 2>&1)
+assert_contains "$OUTPUT" "test-2: Add dark mode" "--ignore-missing-blockers makes test-2 ready"
+OUTPUT=$("$BD_BIN" blocked --ignore-missing-blockers 2>&1)
+assert_not_contains "$OUTPUT" "test-2" "blocked --ignore-missing-blockers drops test-2"
+OUTPUT=$("$BD_BIN" list --blocked-only 2>&1)
+assert_contains "$OUTPUT" "test-2: Add dark mode" "list --blocked-only still counts the dangling blocker"
+
+echo -e "\n${YELLOW}archive${NC}"
+"$BD_BIN" close test-1 >/dev/null
+OUTPUT=$("$BD_BIN" archive --closed-before 2999-01-01 --dry-run 2>&1)
+assert_contains "$OUTPUT" "Would archive 1 issue(s):" "archive --dry-run reports the closed issue"
+assert_contains "$("$BD_BIN" list --status all 2>&1)" "test-1:" "dry run leaves test-1 in place"
+OUTPUT=$("$BD_BIN" archive --closed-before 2999-01-01 2>&1)
+assert_contains "$OUTPUT" "Archived 1 issue(s):" "archive moves the closed issue"
+assert_not_contains "$("$BD_BIN" list --status all 2>&1)" "test-1:" "list skips archived issues"
+assert_contains "$("$BD_BIN" list --status all --include-archived 2>&1)" "test-1:" "list --include-archived shows them"
+OUTPUT=$("$BD_BIN" ready 2>&1)
+assert_contains "$OUTPUT" "test-3: Write docs" "an archived blocker counts as resolved"
+assert_contains "$("$BD_BIN" show test-1 --include-archived 2>&1)" "test-1" "show --include-archived reads the archive"
+OUTPUT=$("$BD_BIN" restore test-1 2>&1)
+assert_contains "$OUTPUT" "Restored 1 issue(s):" "restore moves the issue back"
+assert_contains "$("$BD_BIN" list --status all 2>&1)" "test-1:" "restored issue is listed again"
+OUTPUT=$("$BD_BIN" restore test-1 2>&1 || true)
+assert_contains "$OUTPUT" "Issue not found in archive: test-1" "restoring twice is refused"
+"$BD_BIN" block 3 >/dev/null
+assert_contains "$("$BD_BIN" show test-3 2>&1)" "blocked" "block expands numeric shorthand"
+"$BD_BIN" unblock 3 >/dev/null
+assert_not_contains "$("$BD_BIN" show test-3 2>&1)" "blocked" "unblock expands numeric shorthand"
+"$BD_BIN" --actor zed block test-3 --reason "waiting on vendor" >/dev/null
+assert_contains "$("$BD_BIN" show test-3 2>&1)" "(zed): Blocked: waiting on vendor" "block --reason credits --actor"
+"$BD_BIN" unblock test-3 >/dev/null
+
+echo -e "\n${YELLOW}ready --capacity${NC}"
+WIP_IDS=()
+for i in 1 2 3 4; do
+    WIP_IDS+=("$("$BD_BIN" create "WIP task $i" --assignee alice 2>&1 | sed -n 's/^Created issue: //p')")
+done
+"$BD_BIN" start "${WIP_IDS[0]}" >/dev/null
+OUTPUT=$("$BD_BIN" ready --assignee alice --capacity 3 --sort oldest 2>&1)
+assert_contains "$OUTPUT" "WIP task 3" "capacity 3 with 1 in progress offers 2 issues"
+assert_not_contains "$OUTPUT" "WIP task 4" "capacity caps the ready list"
+OUTPUT=$("$BD_BIN" ready --assignee alice --capacity 3 --sort oldest --limit 1 2>&1)
+assert_contains "$OUTPUT" "WIP task 2" "--limit below the remaining capacity still applies"
+assert_not_contains "$OUTPUT" "WIP task 3" "--limit 1 shows one issue"
+"$BD_BIN" start "${WIP_IDS[1]}" "${WIP_IDS[2]}" >/dev/null
+OUTPUT=$("$BD_BIN" ready --assignee alice --capacity 3 2>&1)
+assert_contains "$OUTPUT" "alice is at capacity: 3 in progress, limit 3" "at capacity reports why"
+assert_not_contains "$OUTPUT" "WIP task" "at capacity offers no work"
+OUTPUT=$("$BD_BIN" ready --capacity 3 2>&1 || true)
+assert_contains "$OUTPUT" "--assignee" "--capacity requires --assignee"
+
 echo ""
 echo "=========================================="
 echo "Test Summary"
@@ -60,7 +60,7 @@ assert_contains() {
     local haystack="$1"
     local needle="$2"
     local message="${3:-Assertion failed}"
-    if echo "$haystack" | grep -qF -- "$needle"; then
+    if grep -qF -- "$needle" <<< "$haystack"; then
         success "$message"
     else
         fail "$message (expected to find: '$needle' in output)"
@@ -100,7 +100,7 @@ mkdir -p "$TEST_DIR"
 cd "$TEST_DIR"
 
 # Setup: a repo with one issue whose description has a repeated token.
-"$BD_BIN" init --prefix test >/dev/null 2>&1
+"$BD_BIN" init --here --prefix test >/dev/null 2>&1
 "$BD_BIN" create "A task" -d "I have a CLI that lets agents file tasks. The CLI is clumsy to edit." >/dev/null 2>&1
 
 # Read back the description body of test-1 (everything after the "# Description" heading).